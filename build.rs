@@ -0,0 +1,35 @@
+/*
+ * Generates include/mbedtls/ctr_drbg.h, the C header of the ffi module,
+ * with cbindgen. Only the files that make up the C interface are parsed;
+ * the header is rewritten only when its contents change.
+ */
+
+use std::env;
+use std::path::PathBuf;
+
+const SOURCES: [&str; 4] = [
+    "src/rng/header/error.rs",
+    "src/rng/header/ctr_drbg.rs",
+    "src/rng/header/ffi.rs",
+    "src/rng/ffi.rs",
+];
+
+const HEADER: &str = "include/mbedtls/ctr_drbg.h";
+
+fn main() {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let config = cbindgen::Config::from_file(root.join("cbindgen.toml"))
+        .expect("cannot read cbindgen.toml");
+
+    let mut builder = cbindgen::Builder::new().with_config(config);
+    for src in SOURCES.iter() {
+        builder = builder.with_src(root.join(src));
+        println!("cargo:rerun-if-changed={}", src);
+    }
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed={}", HEADER);
+
+    builder.generate()
+        .expect("cannot generate the C header")
+        .write_to_file(root.join(HEADER));
+}
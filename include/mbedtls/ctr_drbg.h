@@ -0,0 +1,372 @@
+/**
+ * \file ctr_drbg.h
+ *
+ * \brief    C interface of the Rust CTR_DRBG module.
+ *
+ * This header declares the functions exported by the cdylib and staticlib
+ * of the crate, with the names and signatures of mbedTLS's ctr_drbg.h, so
+ * that C code written against mbedTLS builds and links against the Rust
+ * implementation unchanged.
+ *
+ * It is generated by build.rs with cbindgen, from src/rng/ffi.rs,
+ * src/rng/header/ffi.rs, src/rng/header/ctr_drbg.rs and
+ * src/rng/header/error.rs. Do not edit it: the next build overwrites it.
+ *
+ * The context only holds a pointer to the Rust context, allocated by
+ * mbedtls_ctr_drbg_init() and released by mbedtls_ctr_drbg_free(). Code
+ * that reads the fields of an mbedTLS context must be ported.
+ *
+ * As in mbedTLS without MBEDTLS_THREADING_C, a context must not be used
+ * by several threads at the same time.
+ */
+
+#ifndef MBEDTLS_CTR_DRBG_H
+#define MBEDTLS_CTR_DRBG_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+/**
+ * Generic error
+ */
+#define MBEDTLS_ERR_ERROR_GENERIC_ERROR -1
+
+/**
+ * The entropy source failed.
+ */
+#define MBEDTLS_ERR_CTR_DRBG_ENTROPY_SOURCE_FAILED -52
+
+/**
+ * The requested random buffer length is too big.
+ */
+#define MBEDTLS_ERR_CTR_DRBG_REQUEST_TOO_BIG -54
+
+/**
+ * The input (entropy + additional data) is too large.
+ */
+#define MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG -56
+
+/**
+ * Read or write error in file.
+ */
+#define MBEDTLS_ERR_CTR_DRBG_FILE_IO_ERROR -58
+
+/**
+ * The seed file ends before its integrity tag.
+ */
+#define MBEDTLS_ERR_CTR_DRBG_SEED_FILE_TRUNCATED -59
+
+/**
+ * The seed file integrity tag does not match its contents.
+ */
+#define MBEDTLS_ERR_CTR_DRBG_SEED_FILE_CORRUPTED -123
+
+/**
+ * The seed file has an unsupported format version.
+ */
+#define MBEDTLS_ERR_CTR_DRBG_SEED_FILE_BAD_VERSION -125
+
+/**
+ * The block size used by the cipher.
+ */
+#define MBEDTLS_CTR_DRBG_BLOCKSIZE 16
+
+#if defined(MBEDTLS_CTR_DRBG_USE_128_BIT_KEY)
+/**
+ * The key size in bytes used by the cipher.
+ *
+ * Compile-time choice: 16 bytes (128 bits)
+ * because the `ctr-drbg-128-bit-key` feature
+ * (\c MBEDTLS_CTR_DRBG_USE_128_BIT_KEY) is enabled.
+ * It takes precedence over `ctr-drbg-192-bit-key`.
+ */
+#define MBEDTLS_CTR_DRBG_KEYSIZE 16
+#endif
+
+#if (defined(MBEDTLS_CTR_DRBG_USE_192_BIT_KEY) && !defined(MBEDTLS_CTR_DRBG_USE_128_BIT_KEY))
+/**
+ * The key size in bytes used by the cipher.
+ *
+ * Compile-time choice: 24 bytes (192 bits)
+ * because the `ctr-drbg-192-bit-key` feature is enabled
+ * and the `ctr-drbg-128-bit-key` feature is disabled.
+ */
+#define MBEDTLS_CTR_DRBG_KEYSIZE 24
+#endif
+
+#if !(defined(MBEDTLS_CTR_DRBG_USE_128_BIT_KEY) || defined(MBEDTLS_CTR_DRBG_USE_192_BIT_KEY))
+/**
+ * The key size in bytes used by the cipher.
+ *
+ * Compile-time choice: 32 bytes (256 bits)
+ * because neither the `ctr-drbg-128-bit-key` feature
+ * (\c MBEDTLS_CTR_DRBG_USE_128_BIT_KEY) nor the
+ * `ctr-drbg-192-bit-key` feature is enabled.
+ */
+#define MBEDTLS_CTR_DRBG_KEYSIZE 32
+#endif
+
+/**
+ * The key size for the DRBG operation, in bits.
+ */
+#define MBEDTLS_CTR_DRBG_KEYBITS (MBEDTLS_CTR_DRBG_KEYSIZE * 8)
+
+/**
+ * The seed length, calculated as (counter + AES key).
+ */
+#define MBEDTLS_CTR_DRBG_SEEDLEN (MBEDTLS_CTR_DRBG_KEYSIZE + MBEDTLS_CTR_DRBG_BLOCKSIZE)
+
+/**
+ * The amount of entropy used per seed by default, in bytes.
+ *
+ * This is 48 bytes because the entropy module uses SHA-512
+ * (\c MBEDTLS_ENTROPY_FORCE_SHA256 is disabled).
+ */
+#define MBEDTLS_CTR_DRBG_ENTROPY_LEN 48
+
+/**
+ * The interval before reseed is performed by default.
+ */
+#define MBEDTLS_CTR_DRBG_RESEED_INTERVAL 10000
+
+/**
+ * The maximum number of additional input Bytes.
+ */
+#define MBEDTLS_CTR_DRBG_MAX_INPUT 256
+
+/**
+ * The maximum number of requested Bytes per call.
+ */
+#define MBEDTLS_CTR_DRBG_MAX_REQUEST 1024
+
+/**
+ * The maximum size of seed or reseed buffer in bytes.
+ */
+#define MBEDTLS_CTR_DRBG_MAX_SEED_INPUT 384
+
+/**
+ * The seed file format version written by write_seed_file().
+ */
+#define MBEDTLS_CTR_DRBG_SEED_FILE_VERSION 1
+
+/**
+ * The length of the seed file header: magic, version and seed length.
+ */
+#define MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN 7
+
+/**
+ * The length of the seed file integrity tag (SHA-256).
+ */
+#define MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN 32
+
+/**
+ * Prediction resistance is disabled.
+ */
+#define MBEDTLS_CTR_DRBG_PR_OFF false
+
+/**
+ * Prediction resistance is enabled.
+ */
+#define MBEDTLS_CTR_DRBG_PR_ON true
+
+/**
+ * The amount of entropy used per seed as a nonce by default.
+ */
+#define MBEDTLS_CTR_DRBG_ENTROPY_NONCE_LEN 0
+
+/**
+ * The CTR_DRBG context structure.
+ */
+typedef struct mbedtls_ctr_drbg_rust_context mbedtls_ctr_drbg_rust_context;
+
+/**
+ * The CTR_DRBG context as seen from C.
+ *
+ * Unlike in mbedTLS, the C structure only holds a pointer to the Rust
+ * context, which mbedtls_ctr_drbg_init() allocates and
+ * mbedtls_ctr_drbg_free() releases. C code that only goes through the
+ * functions, and never reads the fields, works unchanged.
+ */
+typedef struct mbedtls_ctr_drbg_context {
+  struct mbedtls_ctr_drbg_rust_context *ctx;
+} mbedtls_ctr_drbg_context;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * This function initializes the CTR_DRBG context, and prepares it for
+ * mbedtls_ctr_drbg_seed().
+ *
+ * # Safety
+ *
+ * `ctx` must be NULL or point to writable memory for a context. A
+ * context that was initialised must be freed before it is initialised
+ * again, or its memory leaks.
+ */
+void mbedtls_ctr_drbg_init(struct mbedtls_ctr_drbg_context *ctx);
+
+/**
+ * This function seeds and sets up the CTR_DRBG entropy source for
+ * future reseeds.
+ *
+ * # Safety
+ *
+ * `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+ * `f_entropy` must be safe to call with `p_entropy` until the context
+ * is freed. `custom` must point to `len` readable bytes.
+ */
+int mbedtls_ctr_drbg_seed(struct mbedtls_ctr_drbg_context *ctx,
+                          int (*f_entropy)(void *p_entropy, unsigned char *output, size_t len),
+                          void *p_entropy,
+                          const unsigned char *custom,
+                          size_t len);
+
+/**
+ * This function clears CTR_DRBG context data and releases it.
+ *
+ * # Safety
+ *
+ * `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init(),
+ * possibly freed already.
+ */
+void mbedtls_ctr_drbg_free(struct mbedtls_ctr_drbg_context *ctx);
+
+/**
+ * This function turns prediction resistance on or off.
+ *
+ * # Safety
+ *
+ * `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+ */
+void mbedtls_ctr_drbg_set_prediction_resistance(struct mbedtls_ctr_drbg_context *ctx,
+                                                int resistance);
+
+/**
+ * This function sets the amount of entropy grabbed on each seed or reseed.
+ *
+ * # Safety
+ *
+ * `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+ */
+void mbedtls_ctr_drbg_set_entropy_len(struct mbedtls_ctr_drbg_context *ctx, size_t len);
+
+/**
+ * This function sets the amount of entropy grabbed as a nonce for the
+ * initial seeding.
+ *
+ * # Safety
+ *
+ * `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+ */
+int mbedtls_ctr_drbg_set_nonce_len(struct mbedtls_ctr_drbg_context *ctx, size_t len);
+
+/**
+ * This function sets the reseed interval. As in mbedTLS, the value is
+ * not checked: an interval below 1 makes every request reseed first.
+ *
+ * # Safety
+ *
+ * `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+ */
+void mbedtls_ctr_drbg_set_reseed_interval(struct mbedtls_ctr_drbg_context *ctx, int interval);
+
+/**
+ * This function reseeds the CTR_DRBG context, that is extracts data from
+ * the entropy source.
+ *
+ * # Safety
+ *
+ * `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+ * `additional` must point to `len` readable bytes.
+ */
+int mbedtls_ctr_drbg_reseed(struct mbedtls_ctr_drbg_context *ctx,
+                            const unsigned char *additional,
+                            size_t len);
+
+/**
+ * This function updates the state of the CTR_DRBG context.
+ *
+ * # Safety
+ *
+ * `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+ * `additional` must point to `add_len` readable bytes.
+ */
+int mbedtls_ctr_drbg_update_ret(struct mbedtls_ctr_drbg_context *ctx,
+                                const unsigned char *additional,
+                                size_t add_len);
+
+/**
+ * This function updates the state of the CTR_DRBG context, ignoring
+ * errors. It is deprecated in mbedTLS in favour of
+ * mbedtls_ctr_drbg_update_ret().
+ *
+ * # Safety
+ *
+ * As for mbedtls_ctr_drbg_update_ret().
+ */
+void mbedtls_ctr_drbg_update(struct mbedtls_ctr_drbg_context *ctx,
+                             const unsigned char *additional,
+                             size_t add_len);
+
+/**
+ * This function updates a CTR_DRBG instance with additional data and
+ * uses it to generate random data. It has the f_rng shape with
+ * additional data, `p_rng` being the context.
+ *
+ * # Safety
+ *
+ * `p_rng` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+ * `output` must point to `output_len` writable bytes and `additional`
+ * to `add_len` readable bytes.
+ */
+int mbedtls_ctr_drbg_random_with_add(void *p_rng,
+                                     unsigned char *output,
+                                     size_t output_len,
+                                     const unsigned char *additional,
+                                     size_t add_len);
+
+/**
+ * This function uses CTR_DRBG to generate random data. It is the f_rng
+ * callback of the module, `p_rng` being the context.
+ *
+ * # Safety
+ *
+ * `p_rng` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+ * `output` must point to `output_len` writable bytes.
+ */
+int mbedtls_ctr_drbg_random(void *p_rng, unsigned char *output, size_t output_len);
+
+/**
+ * This function writes a seed file.
+ *
+ * # Safety
+ *
+ * `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+ * `path` must be NULL or a NUL-terminated string.
+ */
+int mbedtls_ctr_drbg_write_seed_file(struct mbedtls_ctr_drbg_context *ctx, const char *path);
+
+/**
+ * This function reads and updates a seed file. The seed is added to
+ * this instance.
+ *
+ * # Safety
+ *
+ * `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+ * `path` must be NULL or a NUL-terminated string.
+ */
+int mbedtls_ctr_drbg_update_seed_file(struct mbedtls_ctr_drbg_context *ctx, const char *path);
+
+/**
+ * The CTR_DRBG checkup routine. Returns 0 on success and 1 on failure;
+ * with `verbose`, the report is printed to standard output.
+ */
+int mbedtls_ctr_drbg_self_test(int verbose);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MBEDTLS_CTR_DRBG_H */
@@ -0,0 +1,416 @@
+/*
+ * ctr-drbg: generate random data and manage seed files with CTR_DRBG
+ *
+ * In the spirit of programs/random/gen_random_ctr_drbg.c: the DRBG is
+ * seeded from the platform entropy source, with a personalization string
+ * of "RANDOM_GEN" unless told otherwise.
+ */
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, ErrorKind, Write};
+use std::path::PathBuf;
+use std::process;
+
+use mbed::rng::header::ctr_drbg::CtrDrbg; // The CTR_DRBG context structure.
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_ENTROPY_LEN; // The amount of entropy used per seed by default.
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_MAX_REQUEST; // The maximum number of requested bytes per call.
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_MAX_SEED_INPUT; // The maximum size of seed or reseed buffer.
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_SEED_FILE_MAGIC; // The magic bytes a seed file starts with.
+use mbed::rng::header::error::Error;
+use mbed::rng::header::platform_util::Zeroizing; // A buffer wiped when it goes out of scope.
+
+use mbed::rng::ctr_drbg::seed_file_decode;
+use mbed::rng::ctr_drbg::self_test;
+
+const USAGE: &str = "\
+usage: ctr-drbg [OPTIONS] COMMAND
+
+commands:
+    generate N              write N random bytes to standard output
+    seed create FILE        write a fresh seed file
+    seed refresh FILE       add a seed file to the DRBG and write a fresh one
+    seed verify FILE        check a seed file without modifying it
+    selftest                run the CTR_DRBG self-test
+
+options:
+    -f, --format FORMAT     output of generate: raw, hex or base64 (default raw)
+    -p, --prediction-resistance
+                            reseed from the entropy source before every request
+    -r, --reseed-interval N reseed after N requests (default 10000)
+    -s, --personalization STRING
+                            personalization string (default RANDOM_GEN)
+    -e, --entropy-len N     bytes of entropy per seed or reseed, 48 to 384 (default 48)
+    -h, --help              print this help
+";
+
+/* Bytes generated per write; a multiple of 3 keeps base64 unpadded between writes. */
+const CHUNK_LEN: usize = 3 * MBEDTLS_CTR_DRBG_MAX_REQUEST;
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Raw,
+    Hex,
+    Base64,
+}
+
+enum Command {
+    Generate(u64),
+    SeedCreate(PathBuf),
+    SeedRefresh(PathBuf),
+    SeedVerify(PathBuf),
+    SelfTest,
+}
+
+struct Options {
+    format: Format,                 /* output format of generate */
+    prediction_resistance: bool,    /* enables prediction resistance */
+    reseed_interval: Option<i32>,   /* reseed interval, or the default */
+    personalization: Vec<u8>,       /* personalization string */
+    entropy_len: Option<usize>,     /* entropy per seed, or the default */
+}
+
+/*
+ * Failures of the program: bad usage, a failed self-test, a DRBG error,
+ * or an I/O error
+ */
+enum Failure {
+    Usage(String),
+    SelfTest,
+    Drbg(&'static str, Error),
+    Io(String, io::Error),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Usage(msg) => write!(f, "{}", msg),
+            Failure::SelfTest => write!(f, "the self-test failed"),
+            Failure::Drbg(what, err) => write!(f, "{} failed: {} (-0x{:04X})", what, err, err.code().unsigned_abs()),
+            Failure::Io(what, err) => write!(f, "{}: {}", what, err),
+        }
+    }
+}
+
+fn usage<T>(msg: String) -> Result<T, Failure> {
+    Err(Failure::Usage(msg))
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, Failure> {
+    value.parse().or_else(|_| usage(format!("invalid value for {}: {}", name, value)))
+}
+
+/*
+ * Parses the command line; options may come before or after the command
+ */
+fn parse_args(args: Vec<String>) -> Result<(Options, Command), Failure> {
+    let mut options = Options {
+        format: Format::Raw,
+        prediction_resistance: false,
+        reseed_interval: None,
+        personalization: b"RANDOM_GEN".to_vec(),
+        entropy_len: None,
+    };
+    let mut operands = Vec::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            operands.extend(args.by_ref());
+            break;
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            operands.push(arg);
+            continue;
+        }
+
+        /* --name=value or --name value */
+        let (name, inline) = match arg.find('=') {
+            Some(i) if arg.starts_with("--") => (arg[..i].to_string(), Some(arg[i + 1..].to_string())),
+            _ => (arg.clone(), None),
+        };
+        let value = |args: &mut dyn Iterator<Item = String>| match inline.clone() {
+            Some(v) => Ok(v),
+            None => args.next().map_or_else(|| usage(format!("missing value for {}", name)), Ok),
+        };
+
+        match name.as_str() {
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                process::exit(0);
+            }
+            "-f" | "--format" => {
+                options.format = match value(&mut args)?.as_str() {
+                    "raw" => Format::Raw,
+                    "hex" => Format::Hex,
+                    "base64" => Format::Base64,
+                    other => return usage(format!("unknown format: {}", other)),
+                };
+            }
+            "-p" | "--prediction-resistance" if inline.is_none() => options.prediction_resistance = true,
+            "-r" | "--reseed-interval" => {
+                options.reseed_interval = Some(parse_number(&name, &value(&mut args)?)?);
+            }
+            "-s" | "--personalization" => options.personalization = value(&mut args)?.into_bytes(),
+            "-e" | "--entropy-len" => {
+                let len = parse_number(&name, &value(&mut args)?)?;
+                /* Less than the default falls short of the security
+                 * strength; more does not fit in the seed buffer. */
+                if !(MBEDTLS_CTR_DRBG_ENTROPY_LEN..=MBEDTLS_CTR_DRBG_MAX_SEED_INPUT).contains(&len) {
+                    return usage(format!("invalid value for {}: {} (must be {} to {})", name, len,
+                                         MBEDTLS_CTR_DRBG_ENTROPY_LEN, MBEDTLS_CTR_DRBG_MAX_SEED_INPUT));
+                }
+                options.entropy_len = Some(len);
+            }
+            _ => return usage(format!("unknown option: {}", arg)),
+        }
+    }
+
+    let operands: Vec<&str> = operands.iter().map(String::as_str).collect();
+    let command = match operands[..] {
+        ["generate", n] => Command::Generate(parse_number("N", n)?),
+        ["seed", "create", file] => Command::SeedCreate(PathBuf::from(file)),
+        ["seed", "refresh", file] => Command::SeedRefresh(PathBuf::from(file)),
+        ["seed", "verify", file] => Command::SeedVerify(PathBuf::from(file)),
+        ["selftest"] => Command::SelfTest,
+        [] => return usage("missing command".to_string()),
+        _ => return usage(format!("invalid command: {}", operands.join(" "))),
+    };
+
+    Ok((options, command))
+}
+
+/*
+ * Sets up a DRBG seeded from the platform entropy source
+ */
+fn setup(options: &Options) -> Result<CtrDrbg<'static>, Failure> {
+    let mut ctx = CtrDrbg::new();
+
+    if let Some(len) = options.entropy_len {
+        ctx.set_entropy_len(len);
+    }
+    ctx.seed_default(&options.personalization).map_err(|e| Failure::Drbg("seeding the DRBG", e))?;
+
+    ctx.set_prediction_resistance(options.prediction_resistance);
+    if let Some(interval) = options.reseed_interval {
+        ctx.set_reseed_interval(interval);
+    }
+
+    Ok(ctx)
+}
+
+fn base64_encode(input: &[u8], output: &mut Vec<u8>) {
+    for group in input.chunks(3) {
+        let b = [group[0], *group.get(1).unwrap_or(&0), *group.get(2).unwrap_or(&0)];
+        let n = (b[0] as usize) << 16 | (b[1] as usize) << 8 | b[2] as usize;
+
+        output.push(BASE64_ALPHABET[n >> 18]);
+        output.push(BASE64_ALPHABET[(n >> 12) & 0x3F]);
+        output.push(if group.len() > 1 { BASE64_ALPHABET[(n >> 6) & 0x3F] } else { b'=' });
+        output.push(if group.len() > 2 { BASE64_ALPHABET[n & 0x3F] } else { b'=' });
+    }
+}
+
+fn hex_encode(input: &[u8], output: &mut Vec<u8>) {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    for b in input {
+        output.push(DIGITS[(b >> 4) as usize]);
+        output.push(DIGITS[(b & 0x0F) as usize]);
+    }
+}
+
+/*
+ * Writes len random bytes to standard output, MBEDTLS_CTR_DRBG_MAX_REQUEST
+ * bytes per generate request; encoded formats end with a newline
+ */
+fn generate(ctx: &mut CtrDrbg<'_>, format: Format, len: u64) -> Result<(), Failure> {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut buf = Zeroizing::new([0u8; CHUNK_LEN]);
+    let mut encoded = Zeroizing::new(Vec::with_capacity(2 * CHUNK_LEN));
+    let mut left = len;
+    let write_err = |e| Failure::Io("writing the output".to_string(), e);
+
+    while left > 0 {
+        let n = left.min(CHUNK_LEN as u64) as usize;
+        ctx.fill_bytes(&mut buf[..n]).map_err(|e| Failure::Drbg("generating random data", e))?;
+
+        encoded.clear();
+        match format {
+            Format::Raw => encoded.extend_from_slice(&buf[..n]),
+            Format::Hex => hex_encode(&buf[..n], &mut encoded),
+            Format::Base64 => base64_encode(&buf[..n], &mut encoded),
+        }
+        out.write_all(&encoded).map_err(write_err)?;
+        left -= n as u64;
+    }
+
+    if format != Format::Raw {
+        out.write_all(b"\n").map_err(write_err)?;
+    }
+    out.flush().map_err(write_err)
+}
+
+/*
+ * Checks a seed file the way update_seed_file() reads it, without
+ * seeding a DRBG or writing the file back
+ */
+fn verify_seed_file(path: &PathBuf) -> Result<(), Failure> {
+    let data = Zeroizing::new(fs::read(path).map_err(|e| Failure::Io(path.display().to_string(), e))?);
+
+    seed_file_decode(&data).map_err(|e| Failure::Drbg("reading the seed file", e))?;
+
+    if data.starts_with(&MBEDTLS_CTR_DRBG_SEED_FILE_MAGIC) {
+        println!("{}: valid seed file, version {}", path.display(), data[MBEDTLS_CTR_DRBG_SEED_FILE_MAGIC.len()]);
+    } else {
+        println!("{}: legacy raw seed of {} bytes, without integrity tag", path.display(), data.len());
+    }
+    Ok(())
+}
+
+fn run(options: Options, command: Command) -> Result<(), Failure> {
+    match command {
+        Command::Generate(len) => generate(&mut setup(&options)?, options.format, len),
+        Command::SeedCreate(path) => {
+            setup(&options)?.write_seed_file(&path).map_err(|e| Failure::Drbg("writing the seed file", e))
+        }
+        Command::SeedRefresh(path) => {
+            setup(&options)?.update_seed_file(&path).map_err(|e| Failure::Drbg("updating the seed file", e))
+        }
+        Command::SeedVerify(path) => verify_seed_file(&path),
+        Command::SelfTest => {
+            let report = self_test();
+            println!("{}", report);
+            if report.passed() { Ok(()) } else { Err(Failure::SelfTest) }
+        }
+    }
+}
+
+fn main() {
+    let (options, command) = match parse_args(env::args().skip(1).collect()) {
+        Ok(parsed) => parsed,
+        Err(failure) => {
+            eprintln!("ctr-drbg: {}\n\n{}", failure, USAGE);
+            process::exit(2);
+        }
+    };
+
+    match run(options, command) {
+        Ok(()) => {}
+        /* The reader went away, as with `ctr-drbg generate N | head -c 16`. */
+        Err(Failure::Io(_, ref e)) if e.kind() == ErrorKind::BrokenPipe => process::exit(1),
+        Err(failure) => {
+            eprintln!("ctr-drbg: {}", failure);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<(Options, Command), Failure> {
+        parse_args(args.iter().map(|a| a.to_string()).collect())
+    }
+
+    /* The message of a usage error, or a panic on anything else. */
+    fn usage_error(args: &[&str]) -> String {
+        match parse(args) {
+            Err(Failure::Usage(msg)) => msg,
+            Err(failure) => panic!("{:?}: not a usage error: {}", args, failure),
+            Ok(_) => panic!("{:?}: accepted", args),
+        }
+    }
+
+    fn base64(input: &[u8]) -> String {
+        let mut output = Vec::new();
+        base64_encode(input, &mut output);
+        String::from_utf8(output).unwrap()
+    }
+
+    fn hex(input: &[u8]) -> String {
+        let mut output = Vec::new();
+        hex_encode(input, &mut output);
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn parse_args_defaults() {
+        let (options, command) = parse(&["selftest"]).ok().unwrap();
+
+        assert!(options.format == Format::Raw);
+        assert!(!options.prediction_resistance);
+        assert_eq!(options.reseed_interval, None);
+        assert_eq!(options.personalization, b"RANDOM_GEN");
+        assert_eq!(options.entropy_len, None);
+        assert!(matches!(command, Command::SelfTest));
+    }
+
+    #[test]
+    fn parse_args_options_around_the_command() {
+        let args = ["-f", "hex", "generate", "16", "--reseed-interval=5", "-p", "-s", "pers", "--entropy-len", "64"];
+        let (options, command) = parse(&args).ok().unwrap();
+
+        assert!(options.format == Format::Hex);
+        assert!(options.prediction_resistance);
+        assert_eq!(options.reseed_interval, Some(5));
+        assert_eq!(options.personalization, b"pers");
+        assert_eq!(options.entropy_len, Some(64));
+        assert!(matches!(command, Command::Generate(16)));
+
+        let (options, _) = parse(&["--format=base64", "generate", "1"]).ok().unwrap();
+        assert!(options.format == Format::Base64);
+    }
+
+    #[test]
+    fn parse_args_seed_commands() {
+        assert!(matches!(parse(&["seed", "create", "a"]).ok().unwrap().1, Command::SeedCreate(p) if p.to_str() == Some("a")));
+        assert!(matches!(parse(&["seed", "refresh", "b"]).ok().unwrap().1, Command::SeedRefresh(p) if p.to_str() == Some("b")));
+        assert!(matches!(parse(&["seed", "verify", "c"]).ok().unwrap().1, Command::SeedVerify(p) if p.to_str() == Some("c")));
+
+        /* After "--", operands may start with a dash. */
+        assert!(matches!(parse(&["seed", "verify", "--", "-d"]).ok().unwrap().1, Command::SeedVerify(p) if p.to_str() == Some("-d")));
+    }
+
+    #[test]
+    fn parse_args_errors() {
+        assert_eq!(usage_error(&["-x", "selftest"]), "unknown option: -x");
+        assert_eq!(usage_error(&["--prediction-resistance=1", "selftest"]), "unknown option: --prediction-resistance=1");
+        assert_eq!(usage_error(&["selftest", "-f"]), "missing value for -f");
+        assert_eq!(usage_error(&["-f", "octal", "selftest"]), "unknown format: octal");
+        assert_eq!(usage_error(&["-r", "often", "selftest"]), "invalid value for -r: often");
+        assert_eq!(usage_error(&["generate", "-1"]), "unknown option: -1");
+        assert_eq!(usage_error(&["generate", "many"]), "invalid value for N: many");
+        assert_eq!(usage_error(&["-e", "32", "selftest"]), "invalid value for -e: 32 (must be 48 to 384)");
+        assert_eq!(usage_error(&["--entropy-len=385", "selftest"]), "invalid value for --entropy-len: 385 (must be 48 to 384)");
+        assert_eq!(usage_error(&["-e", "-1", "selftest"]), "invalid value for -e: -1");
+        assert_eq!(usage_error(&[]), "missing command");
+        assert_eq!(usage_error(&["seed", "delete", "f"]), "invalid command: seed delete f");
+    }
+
+    #[test]
+    fn base64_encode_pads() {
+        /* RFC 4648, section 10. */
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64(b"fooba"), "Zm9vYmE=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+
+        /* The last two letters of the alphabet. */
+        assert_eq!(base64(&[0xFB, 0xFF]), "+/8=");
+    }
+
+    #[test]
+    fn hex_encode_lowercase() {
+        assert_eq!(hex(&[]), "");
+        assert_eq!(hex(&[0x00, 0x0F, 0xA5, 0xFF]), "000fa5ff");
+    }
+}
@@ -0,0 +1,3 @@
+//! Rust port of the Mbed TLS random number generation module.
+
+pub mod rng;
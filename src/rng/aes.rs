@@ -0,0 +1,495 @@
+use crate::rng::header::aes::AesContext;
+use crate::rng::header::aes::MBEDTLS_AES_ENCRYPT; // AES encryption.
+use crate::rng::header::aes::MBEDTLS_AES_DECRYPT; // AES decryption.
+use crate::rng::header::aes::MBEDTLS_AES_BLOCKSIZE; // The block size of AES, in bytes.
+use crate::rng::header::aes::MBEDTLS_AES_MAX_ROUNDS; // The number of rounds for a 256-bit key.
+use crate::rng::header::error::Error;
+use crate::rng::header::platform_util::Zeroizing; // A buffer wiped when it goes out of scope.
+use crate::rng::platform_util::platform_zeroize;
+
+/*
+ * Forward S-box
+ */
+const FSB: [u8; 256] = [
+    0x63, 0x7C, 0x77, 0x7B, 0xF2, 0x6B, 0x6F, 0xC5,
+    0x30, 0x01, 0x67, 0x2B, 0xFE, 0xD7, 0xAB, 0x76,
+    0xCA, 0x82, 0xC9, 0x7D, 0xFA, 0x59, 0x47, 0xF0,
+    0xAD, 0xD4, 0xA2, 0xAF, 0x9C, 0xA4, 0x72, 0xC0,
+    0xB7, 0xFD, 0x93, 0x26, 0x36, 0x3F, 0xF7, 0xCC,
+    0x34, 0xA5, 0xE5, 0xF1, 0x71, 0xD8, 0x31, 0x15,
+    0x04, 0xC7, 0x23, 0xC3, 0x18, 0x96, 0x05, 0x9A,
+    0x07, 0x12, 0x80, 0xE2, 0xEB, 0x27, 0xB2, 0x75,
+    0x09, 0x83, 0x2C, 0x1A, 0x1B, 0x6E, 0x5A, 0xA0,
+    0x52, 0x3B, 0xD6, 0xB3, 0x29, 0xE3, 0x2F, 0x84,
+    0x53, 0xD1, 0x00, 0xED, 0x20, 0xFC, 0xB1, 0x5B,
+    0x6A, 0xCB, 0xBE, 0x39, 0x4A, 0x4C, 0x58, 0xCF,
+    0xD0, 0xEF, 0xAA, 0xFB, 0x43, 0x4D, 0x33, 0x85,
+    0x45, 0xF9, 0x02, 0x7F, 0x50, 0x3C, 0x9F, 0xA8,
+    0x51, 0xA3, 0x40, 0x8F, 0x92, 0x9D, 0x38, 0xF5,
+    0xBC, 0xB6, 0xDA, 0x21, 0x10, 0xFF, 0xF3, 0xD2,
+    0xCD, 0x0C, 0x13, 0xEC, 0x5F, 0x97, 0x44, 0x17,
+    0xC4, 0xA7, 0x7E, 0x3D, 0x64, 0x5D, 0x19, 0x73,
+    0x60, 0x81, 0x4F, 0xDC, 0x22, 0x2A, 0x90, 0x88,
+    0x46, 0xEE, 0xB8, 0x14, 0xDE, 0x5E, 0x0B, 0xDB,
+    0xE0, 0x32, 0x3A, 0x0A, 0x49, 0x06, 0x24, 0x5C,
+    0xC2, 0xD3, 0xAC, 0x62, 0x91, 0x95, 0xE4, 0x79,
+    0xE7, 0xC8, 0x37, 0x6D, 0x8D, 0xD5, 0x4E, 0xA9,
+    0x6C, 0x56, 0xF4, 0xEA, 0x65, 0x7A, 0xAE, 0x08,
+    0xBA, 0x78, 0x25, 0x2E, 0x1C, 0xA6, 0xB4, 0xC6,
+    0xE8, 0xDD, 0x74, 0x1F, 0x4B, 0xBD, 0x8B, 0x8A,
+    0x70, 0x3E, 0xB5, 0x66, 0x48, 0x03, 0xF6, 0x0E,
+    0x61, 0x35, 0x57, 0xB9, 0x86, 0xC1, 0x1D, 0x9E,
+    0xE1, 0xF8, 0x98, 0x11, 0x69, 0xD9, 0x8E, 0x94,
+    0x9B, 0x1E, 0x87, 0xE9, 0xCE, 0x55, 0x28, 0xDF,
+    0x8C, 0xA1, 0x89, 0x0D, 0xBF, 0xE6, 0x42, 0x68,
+    0x41, 0x99, 0x2D, 0x0F, 0xB0, 0x54, 0xBB, 0x16 ];
+
+/*
+ * Reverse S-box, computed from the forward one
+ */
+const RSB: [u8; 256] = {
+    let mut rsb = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        rsb[FSB[i] as usize] = i as u8;
+        i += 1;
+    }
+    rsb
+};
+
+/*
+ * Round constants
+ */
+const RCON: [u8; 10] = [
+    0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1B, 0x36 ];
+
+/*
+ * Multiplication by x in GF(2^8)
+ */
+fn xtime(x: u8) -> u8 {
+    (x << 1) ^ if x & 0x80 != 0 { 0x1B } else { 0x00 }
+}
+
+/*
+ * Multiplication in GF(2^8)
+ */
+fn mul(mut a: u8, mut b: u8) -> u8 {
+    let mut r = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            r ^= a;
+        }
+        a = xtime(a);
+        b >>= 1;
+    }
+    r
+}
+
+fn add_round_key(state: &mut [u8; MBEDTLS_AES_BLOCKSIZE], rk: &[u8]) {
+    for i in 0..MBEDTLS_AES_BLOCKSIZE {
+        state[i] ^= rk[i];
+    }
+}
+
+fn sub_bytes(state: &mut [u8; MBEDTLS_AES_BLOCKSIZE]) {
+    for b in state.iter_mut() {
+        *b = FSB[*b as usize];
+    }
+}
+
+/*
+ * The state is stored column by column, so row r of column c is
+ * state[4 * c + r].
+ */
+fn shift_rows(state: &mut [u8; MBEDTLS_AES_BLOCKSIZE]) {
+    let s = Zeroizing::new(*state);
+    for c in 0..4 {
+        for r in 1..4 {
+            state[4 * c + r] = s[4 * ((c + r) % 4) + r];
+        }
+    }
+}
+
+fn mix_columns(state: &mut [u8; MBEDTLS_AES_BLOCKSIZE]) {
+    for col in state.chunks_exact_mut(4) {
+        let (a0, a1, a2, a3) = (col[0], col[1], col[2], col[3]);
+        let t = a0 ^ a1 ^ a2 ^ a3;
+        col[0] ^= t ^ xtime(a0 ^ a1);
+        col[1] ^= t ^ xtime(a1 ^ a2);
+        col[2] ^= t ^ xtime(a2 ^ a3);
+        col[3] ^= t ^ xtime(a3 ^ a0);
+    }
+}
+
+fn inv_sub_bytes(state: &mut [u8; MBEDTLS_AES_BLOCKSIZE]) {
+    for b in state.iter_mut() {
+        *b = RSB[*b as usize];
+    }
+}
+
+fn inv_shift_rows(state: &mut [u8; MBEDTLS_AES_BLOCKSIZE]) {
+    let s = Zeroizing::new(*state);
+    for c in 0..4 {
+        for r in 1..4 {
+            state[4 * ((c + r) % 4) + r] = s[4 * c + r];
+        }
+    }
+}
+
+fn inv_mix_columns(state: &mut [u8; MBEDTLS_AES_BLOCKSIZE]) {
+    for col in state.chunks_exact_mut(4) {
+        let (a0, a1, a2, a3) = (col[0], col[1], col[2], col[3]);
+        col[0] = mul(a0, 0x0E) ^ mul(a1, 0x0B) ^ mul(a2, 0x0D) ^ mul(a3, 0x09);
+        col[1] = mul(a0, 0x09) ^ mul(a1, 0x0E) ^ mul(a2, 0x0B) ^ mul(a3, 0x0D);
+        col[2] = mul(a0, 0x0D) ^ mul(a1, 0x09) ^ mul(a2, 0x0E) ^ mul(a3, 0x0B);
+        col[3] = mul(a0, 0x0B) ^ mul(a1, 0x0D) ^ mul(a2, 0x09) ^ mul(a3, 0x0E);
+    }
+}
+
+impl AesContext {
+    /// This function initializes the specified AES context.
+    pub fn new() -> Self {
+        AesContext {
+            nr: 0,
+            rk: [0; MBEDTLS_AES_BLOCKSIZE * (MBEDTLS_AES_MAX_ROUNDS + 1)],
+        }
+    }
+
+    /// This function sets the encryption key. `keybits` must be 128, 192
+    /// or 256 and `key` must hold at least `keybits / 8` bytes.
+    pub fn setkey_enc(&mut self, key: &[u8], keybits: usize) -> Result<(), Error> {
+        let nk = match keybits {
+            128 => 4,
+            192 => 6,
+            256 => 8,
+            _ => return Err(Error::AesInvalidKeyLength),
+        };
+        if key.len() < nk * 4 {
+            return Err(Error::AesInvalidKeyLength);
+        }
+
+        self.nr = nk + 6;
+        self.rk[..nk * 4].copy_from_slice(&key[..nk * 4]);
+
+        /*
+         * Key expansion (FIPS-197 section 5.2), one 32-bit word at a time
+         */
+        for i in nk..4 * (self.nr + 1) {
+            let mut temp = Zeroizing::new([0u8; 4]);
+            temp.copy_from_slice(&self.rk[4 * (i - 1)..4 * i]);
+
+            if i % nk == 0 {
+                temp.rotate_left(1);
+                for b in temp.iter_mut() {
+                    *b = FSB[*b as usize];
+                }
+                temp[0] ^= RCON[i / nk - 1];
+            } else if nk > 6 && i % nk == 4 {
+                for b in temp.iter_mut() {
+                    *b = FSB[*b as usize];
+                }
+            }
+
+            for (j, t) in temp.iter().enumerate() {
+                self.rk[4 * i + j] = self.rk[4 * (i - nk) + j] ^ t;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// This function sets the decryption key. The inverse cipher walks
+    /// the encryption key schedule backwards, so both directions share it.
+    pub fn setkey_dec(&mut self, key: &[u8], keybits: usize) -> Result<(), Error> {
+        self.setkey_enc(key, keybits)
+    }
+
+    /// This function performs an AES single-block encryption or decryption
+    /// operation, depending on `mode` (MBEDTLS_AES_ENCRYPT or MBEDTLS_AES_DECRYPT).
+    pub fn crypt_ecb(&self, mode: i32, input: &[u8; MBEDTLS_AES_BLOCKSIZE], output: &mut [u8; MBEDTLS_AES_BLOCKSIZE]) -> Result<(), Error> {
+        match mode {
+            MBEDTLS_AES_ENCRYPT => self.encrypt_ecb(input, output),
+            MBEDTLS_AES_DECRYPT => self.decrypt_ecb(input, output),
+            _ => return Err(Error::AesBadInputData),
+        }
+        Ok(())
+    }
+
+    /// This function performs an AES single-block encryption operation.
+    pub fn encrypt_ecb(&self, input: &[u8; MBEDTLS_AES_BLOCKSIZE], output: &mut [u8; MBEDTLS_AES_BLOCKSIZE]) {
+        let mut state = Zeroizing::new(*input);
+
+        add_round_key(&mut state, &self.rk[..MBEDTLS_AES_BLOCKSIZE]);
+
+        for round in 1..self.nr {
+            sub_bytes(&mut state);
+            shift_rows(&mut state);
+            mix_columns(&mut state);
+            add_round_key(&mut state, &self.rk[MBEDTLS_AES_BLOCKSIZE * round..]);
+        }
+
+        sub_bytes(&mut state);
+        shift_rows(&mut state);
+        add_round_key(&mut state, &self.rk[MBEDTLS_AES_BLOCKSIZE * self.nr..]);
+
+        *output = *state;
+    }
+
+    /// This function performs an AES single-block decryption operation.
+    pub fn decrypt_ecb(&self, input: &[u8; MBEDTLS_AES_BLOCKSIZE], output: &mut [u8; MBEDTLS_AES_BLOCKSIZE]) {
+        let mut state = Zeroizing::new(*input);
+
+        add_round_key(&mut state, &self.rk[MBEDTLS_AES_BLOCKSIZE * self.nr..]);
+
+        for round in (1..self.nr).rev() {
+            inv_shift_rows(&mut state);
+            inv_sub_bytes(&mut state);
+            add_round_key(&mut state, &self.rk[MBEDTLS_AES_BLOCKSIZE * round..]);
+            inv_mix_columns(&mut state);
+        }
+
+        inv_shift_rows(&mut state);
+        inv_sub_bytes(&mut state);
+        add_round_key(&mut state, &self.rk[..MBEDTLS_AES_BLOCKSIZE]);
+
+        *output = *state;
+    }
+}
+
+impl Drop for AesContext {
+    /// This function releases and clears the specified AES context.
+    fn drop(&mut self) {
+        platform_zeroize(&mut self.rk);
+    }
+}
+
+impl Default for AesContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/*
+ * FIPS-197 Appendix C example vectors (AES-128, AES-192, AES-256)
+ */
+const AES_TEST_FIPS197_KEY: [u8; 32] = [
+      0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+      0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+      0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+      0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f ];
+
+const AES_TEST_FIPS197_PT: [u8; 16] = [
+      0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77,
+      0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff ];
+
+const AES_TEST_FIPS197_CT: [[u8; 16]; 3] = [
+    [ 0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30,
+      0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5, 0x5a ],
+    [ 0xdd, 0xa9, 0x7c, 0xa4, 0x86, 0x4c, 0xdf, 0xe0,
+      0x6e, 0xaf, 0x70, 0xa0, 0xec, 0x0d, 0x71, 0x91 ],
+    [ 0x8e, 0xa2, 0xb7, 0xca, 0x51, 0x67, 0x45, 0xbf,
+      0xea, 0xfc, 0x49, 0x90, 0x4b, 0x49, 0x60, 0x89 ] ];
+
+/*
+ * NIST AESAVS known-answer vectors (GFSbox, KeySbox, VarTxt and VarKey),
+ * as (key, plaintext, ciphertext)
+ */
+const AES_TEST_AESAVS: [(&[u8], [u8; 16], [u8; 16]); 18] = [
+    ( &[ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0xf3, 0x44, 0x81, 0xec, 0x3c, 0xc6, 0x27, 0xba,
+        0xcd, 0x5d, 0xc3, 0xfb, 0x08, 0xf2, 0x73, 0xe6 ],
+      [ 0x03, 0x36, 0x76, 0x3e, 0x96, 0x6d, 0x92, 0x59,
+        0x5a, 0x56, 0x7c, 0xc9, 0xce, 0x53, 0x7f, 0x5e ] ),
+    ( &[ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x97, 0x98, 0xc4, 0x64, 0x0b, 0xad, 0x75, 0xc7,
+        0xc3, 0x22, 0x7d, 0xb9, 0x10, 0x17, 0x4e, 0x72 ],
+      [ 0xa9, 0xa1, 0x63, 0x1b, 0xf4, 0x99, 0x69, 0x54,
+        0xeb, 0xc0, 0x93, 0x95, 0x7b, 0x23, 0x45, 0x89 ] ),
+    ( &[ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x96, 0xab, 0x5c, 0x2f, 0xf6, 0x12, 0xd9, 0xdf,
+        0xaa, 0xe8, 0xc3, 0x1f, 0x30, 0xc4, 0x21, 0x68 ],
+      [ 0xff, 0x4f, 0x83, 0x91, 0xa6, 0xa4, 0x0c, 0xa5,
+        0xb2, 0x5d, 0x23, 0xbe, 0xdd, 0x44, 0xa5, 0x97 ] ),
+    ( &[ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0xdb, 0x4f, 0x1a, 0xa5, 0x30, 0x96, 0x7d, 0x67,
+        0x32, 0xce, 0x47, 0x15, 0xeb, 0x0e, 0xe2, 0x4b ] ),
+    ( &[ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0xff, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0xa8, 0x17, 0x38, 0x25, 0x26, 0x21, 0xdd, 0x18,
+        0x0a, 0x34, 0xf3, 0x45, 0x5b, 0x4b, 0xaa, 0x2f ] ),
+    ( &[ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0xff, 0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x77, 0xe2, 0xb5, 0x08, 0xdb, 0x7f, 0xd8, 0x92,
+        0x34, 0xca, 0xf7, 0x93, 0x9e, 0xe5, 0x62, 0x1a ] ),
+    ( &[ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x15, 0x6f, 0x07, 0x76, 0x7a, 0x85, 0xa4, 0x31,
+        0x23, 0x21, 0xf6, 0x39, 0x68, 0x33, 0x8a, 0x01 ] ),
+    ( &[ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xfc, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x15, 0xee, 0xc9, 0xeb, 0xf4, 0x2b, 0x9c, 0xa7,
+        0x68, 0x97, 0xd2, 0xcd, 0x6c, 0x5a, 0x12, 0xe2 ] ),
+    ( &[ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xfe, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0xdb, 0x0d, 0x3a, 0x6f, 0xdc, 0xc1, 0x3f, 0x91,
+        0x5e, 0x2b, 0x30, 0x2c, 0xee, 0xb7, 0x0f, 0xd8 ] ),
+    ( &[ 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+         0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+         0xf0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0xbb, 0x28, 0x52, 0xc8, 0x91, 0xc5, 0x94, 0x7d,
+        0x2e, 0xd4, 0x40, 0x32, 0xc4, 0x21, 0xb8, 0x5f ] ),
+    ( &[ 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+         0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+         0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x1b, 0x9f, 0x5f, 0xbd, 0x5e, 0x8a, 0x42, 0x64,
+        0xc0, 0xa8, 0x5b, 0x80, 0x40, 0x9a, 0xfa, 0x5e ] ),
+    ( &[ 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+         0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+         0xfc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x30, 0xda, 0xb8, 0x09, 0xf8, 0x5a, 0x91, 0x7f,
+        0xe9, 0x24, 0x73, 0x3f, 0x42, 0x4a, 0xc5, 0x89 ] ),
+    ( &[ 0xc1, 0xcc, 0x35, 0x8b, 0x44, 0x99, 0x09, 0xa1,
+         0x94, 0x36, 0xcf, 0xbb, 0x3f, 0x85, 0x2e, 0xf8,
+         0xbc, 0xb5, 0xed, 0x12, 0xac, 0x70, 0x58, 0x32,
+         0x5f, 0x56, 0xe6, 0x09, 0x9a, 0xab, 0x1a, 0x1c ],
+      [ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x35, 0x20, 0x65, 0x27, 0x21, 0x69, 0xab, 0xf9,
+        0x85, 0x68, 0x43, 0x92, 0x7d, 0x06, 0x74, 0xfd ] ),
+    ( &[ 0x98, 0x4c, 0xa7, 0x5f, 0x4e, 0xe8, 0xd7, 0x06,
+         0xf4, 0x6c, 0x2d, 0x98, 0xc0, 0xbf, 0x4a, 0x45,
+         0xf5, 0xb0, 0x0d, 0x79, 0x1c, 0x2d, 0xfe, 0xb1,
+         0x91, 0xb5, 0xed, 0x8e, 0x42, 0x0f, 0xd6, 0x27 ],
+      [ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x43, 0x07, 0x45, 0x6a, 0x9e, 0x67, 0x81, 0x3b,
+        0x45, 0x2e, 0x15, 0xfa, 0x8f, 0xff, 0xe3, 0x98 ] ),
+    ( &[ 0xb4, 0x3d, 0x08, 0xa4, 0x47, 0xac, 0x86, 0x09,
+         0xba, 0xad, 0xae, 0x4f, 0xf1, 0x29, 0x18, 0xb9,
+         0xf6, 0x8f, 0xc1, 0x65, 0x3f, 0x12, 0x69, 0x22,
+         0x2f, 0x12, 0x39, 0x81, 0xde, 0xd7, 0xa9, 0x2f ],
+      [ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x46, 0x63, 0x44, 0x66, 0x07, 0x35, 0x49, 0x89,
+        0x47, 0x7a, 0x5c, 0x6f, 0x0f, 0x00, 0x7e, 0xf4 ] ),
+    ( &[ 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+         0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+         0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xf0,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0xed, 0xf6, 0x1a, 0xe3, 0x62, 0xe8, 0x82, 0xdd,
+        0xc0, 0x16, 0x74, 0x74, 0xa7, 0xa7, 0x7f, 0x3a ] ),
+    ( &[ 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+         0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+         0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xf8,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x61, 0x68, 0xb0, 0x0b, 0xa7, 0x85, 0x9e, 0x09,
+        0x70, 0xec, 0xfd, 0x75, 0x7e, 0xfe, 0xcf, 0x7c ] ),
+    ( &[ 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+         0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+         0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfc,
+         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 ],
+      [ 0xd1, 0x41, 0x54, 0x47, 0x86, 0x62, 0x30, 0xd2,
+        0x8b, 0xb1, 0xea, 0x18, 0xa4, 0xcd, 0xfd, 0x02 ] ),
+];
+
+/*
+ * Checks one (key, plaintext, ciphertext) triple in both directions
+ */
+fn aes_test_kat(key: &[u8], pt: &[u8; 16], ct: &[u8; 16]) -> Result<(), Error> {
+    let mut ctx = AesContext::new();
+    let mut buf = [0u8; 16];
+
+    ctx.setkey_enc(key, key.len() * 8)?;
+    ctx.crypt_ecb(MBEDTLS_AES_ENCRYPT, pt, &mut buf)?;
+    if buf != *ct {
+        return Err(Error::AesBadInputData);
+    }
+
+    ctx.setkey_dec(key, key.len() * 8)?;
+    ctx.crypt_ecb(MBEDTLS_AES_DECRYPT, ct, &mut buf)?;
+    if buf != *pt {
+        return Err(Error::AesBadInputData);
+    }
+
+    Ok(())
+}
+
+/*
+ * Checkup routine
+ */
+
+/// The AES checkup routine. Returns 0 on success and 1 on failure.
+pub fn self_test(verbose: bool) -> i32 {
+    for (i, ct) in AES_TEST_FIPS197_CT.iter().enumerate() {
+        let keybits = 128 + i * 64;
+
+        if verbose {
+            print!("  AES-ECB-{} (FIPS-197): ", keybits);
+        }
+
+        if aes_test_kat(&AES_TEST_FIPS197_KEY[..keybits / 8], &AES_TEST_FIPS197_PT, ct).is_err() {
+            if verbose {
+                println!("failed");
+            }
+            return 1;
+        }
+
+        if verbose {
+            println!("passed");
+        }
+    }
+
+    for (i, (key, pt, ct)) in AES_TEST_AESAVS.iter().enumerate() {
+        if verbose {
+            print!("  AES-ECB-{} (AESAVS #{}): ", key.len() * 8, i + 1);
+        }
+
+        if aes_test_kat(key, pt, ct).is_err() {
+            if verbose {
+                println!("failed");
+            }
+            return 1;
+        }
+
+        if verbose {
+            println!("passed");
+        }
+    }
+
+    if verbose {
+        println!();
+    }
+
+    0
+}
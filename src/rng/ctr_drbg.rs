@@ -1,187 +1,166 @@
-pub mod header;
-use ctr_drbg
-use error
-use platform_util
-use std::ffi::c_void;
-
-use crate::ctr_drbg::MBEDTLS_ERR_CTR_DRBG_ENTROPY_SOURCE_FAILED; // The entropy source failed.
-use crate::ctr_drbg::MBEDTLS_ERR_CTR_DRBG_REQUEST_TOO_BIG; // The requested random buffer length is too big.
-use crate::ctr_drbg::MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG; // The input (entropy + additional data) is too large.
-use crate::ctr_drbg::MBEDTLS_ERR_CTR_DRBG_FILE_IO_ERROR; // Read or write error in file.
-use crate::ctr_drbg::MBEDTLS_CTR_DRBG_BLOCKSIZE; // The block size used by the cipher.
-use crate::ctr_drbg::MBEDTLS_CTR_DRBG_KEYSIZE; // The key size used by the cipher (compile-time choice: 256 bits).
-use crate::ctr_drbg::MBEDTLS_CTR_DRBG_KEYBITS; // The key size for the DRBG operation, in bits.
-use crate::ctr_drbg::MBEDTLS_CTR_DRBG_SEEDLEN; // The seed length, calculated as (counter + AES key).
-use crate::ctr_drbg::MBEDTLS_CTR_DRBG_ENTROPY_LEN; // The amount of entropy used per seed by default.
-use crate::ctr_drbg::MBEDTLS_CTR_DRBG_RESEED_INTERVAL; // The interval before reseed is performed by default.
-use crate::ctr_drbg::MBEDTLS_CTR_DRBG_MAX_INPUT; // The maximum number of additional input Bytes.
-use crate::ctr_drbg::MBEDTLS_CTR_DRBG_MAX_REQUEST; // The maximum number of requested Bytes per call.
-use crate::ctr_drbg::MBEDTLS_CTR_DRBG_MAX_SEED_INPUT; // The maximum size of seed or reseed buffer.
-use crate::ctr_drbg::MBEDTLS_CTR_DRBG_PR_OFF; // Prediction resistance is disabled.
-use crate::ctr_drbg::MBEDTLS_CTR_DRBG_PR_ON; // Prediction resistance is enabled.
-use crate::ctr_drbg::MBEDTLS_CTR_DRBG_ENTROPY_NONCE_LEN;
-use crate::ctr_drbg::mbedtls_ctr_drbg_context; // The CTR_DRBG context structure.
-
-use crate::ctr_drbg::f_ptr;
-
-use crate::error::MBEDTLS_ERR_ERROR_GENERIC_ERROR;
-use crate::error::MBEDTLS_ERR_ERROR_CORRUPTION_DETECTED;
-
-use std::mem;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, Read, Write};
+use std::os::raw::c_void;
+use std::path::Path;
+use std::process;
+use std::sync::{Arc, MutexGuard};
+
+use crate::rng::header::aes::AesContext;
+use crate::rng::header::error::Error;
+
+use crate::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_BLOCKSIZE; // The block size used by the cipher.
+use crate::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_KEYSIZE; // The key size used by the cipher (compile-time choice: 128, 192 or 256 bits).
+use crate::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_KEYBITS; // The key size for the DRBG operation, in bits.
+use crate::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_SEEDLEN; // The seed length, calculated as (counter + AES key).
+use crate::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_ENTROPY_LEN; // The amount of entropy used per seed by default.
+use crate::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_RESEED_INTERVAL; // The interval before reseed is performed by default.
+use crate::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_MAX_INPUT; // The maximum number of additional input Bytes.
+use crate::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_MAX_REQUEST; // The maximum number of requested Bytes per call.
+use crate::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_MAX_SEED_INPUT; // The maximum size of seed or reseed buffer.
+use crate::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_PR_ON; // Prediction resistance is enabled.
+use crate::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_SEED_FILE_MAGIC; // The magic bytes a seed file starts with.
+use crate::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_SEED_FILE_VERSION; // The seed file format version.
+use crate::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN; // The length of the seed file header.
+use crate::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN; // The length of the seed file integrity tag.
+use crate::rng::header::ctr_drbg::CtrDrbg; // The CTR_DRBG context structure.
+use crate::rng::header::ctr_drbg::EntropyCallback; // The entropy callback.
+use crate::rng::header::ctr_drbg::EntropySource; // A source of entropy for seeding a DRBG.
+use crate::rng::header::entropy_poll::PlatformEntropy; // The platform entropy source.
+use crate::rng::header::ctr_drbg::EntropyFn; // The C-style entropy callback.
+use crate::rng::header::ctr_drbg::LegacyEntropySource; // An entropy source calling a C-style callback.
+use crate::rng::header::ctr_drbg::SharedCtrDrbg; // A CTR_DRBG context shared between threads.
+use crate::rng::header::ctr_drbg::CtrDrbgReader; // A CTR_DRBG context read as a stream.
+use crate::rng::header::ctr_drbg::SelfTestCase; // The outcome of one known-answer test.
+use crate::rng::header::ctr_drbg::SelfTestReport; // The report of the checkup routine.
+
+use crate::rng::header::platform_util::Zeroizing; // A buffer wiped when it goes out of scope.
+use crate::rng::header::sha256::MBEDTLS_SHA256_OUTPUT_SIZE; // The length of a SHA-256 checksum, in bytes.
+
+use crate::rng::platform_util::{platform_zeroize, read_file, write_file_atomic};
+use crate::rng::sha256::sha256;
+use crate::rng::threading::{mutex_init, mutex_lock};
 
 /*
  * CTR_DRBG context initialization
  */
 
+impl<'a> CtrDrbg<'a> {
+    /// This function initializes the CTR_DRBG context, and prepares it for seed().
+    pub fn new() -> Self {
+        CtrDrbg {
+            counter: [0; MBEDTLS_CTR_DRBG_BLOCKSIZE],
+            /* Indicate that the entropy nonce length is not set explicitly.
+             * See set_nonce_len(). */
+            reseed_counter: -1,
+            prediction_resistance: false,
+            entropy_len: 0,
+            reseed_interval: MBEDTLS_CTR_DRBG_RESEED_INTERVAL,
+            use_df: true,
+            pid: process::id(),
+            aes_ctx: AesContext::new(),
+            f_entropy: None,
+        }
+    }
 
-// line 51
-// This function initializes the CTR_DRBG context, and prepares it for mbedtls_ctr_drbg_seed() or mbedtls_ctr_drbg_free().
-pub fn mbedtls_ctr_drbg_init(ctx: &mut mbedtls_ctr_drbg_context) -> ()
-{
-    let siz: usize = mem::size_of::<mbedtls_ctr_drbg_context>();
-
-    for i in &mut ctx { *i = 0; }
-
-    /* Indicate that the entropy nonce length is not set explicitly.
-     * See mbedtls_ctr_drbg_set_nonce_len(). */
-    (*ctx).reseed_counter = -1;
-
-    (*ctx).reseed_interval = MBEDTLS_CTR_DRBG_RESEED_INTERVAL;
-    mbedtls_mutex_init( &mut ctx.mutex );
-
-}
-
-/*
- *  This function resets CTR_DRBG context to the state immediately
- *  after initial call of mbedtls_ctr_drbg_init().
- */
-
-
-// line 69
-// This function clears CTR_CRBG context data.
-pub fn mbedtls_ctr_drbg_free( ctx: &mut mbedtls_ctr_drbg_context ) ->()
-{
-    if( ctx == None ){
-        return;
-    }
-    
-    mbedtls_mutex_free( &mut ctx.mutex );
-    
-    mbedtls_aes_free( &mut ctx.aes_ctx );
-    mbedtls_platform_zeroize( ctx, sizeof( mbedtls_ctr_drbg_context ) );
-    (*ctx).reseed_interval = MBEDTLS_CTR_DRBG_RESEED_INTERVAL;
-    (*ctx).reseed_counter = -1;
-    
-    mbedtls_mutex_free( &mut ctx.mutex );
-}
-
-
-// line 86
-// This function turns prediction resistance on or off. The default value is off.
-pub fn mbedtls_ctr_drbg_set_prediction_resistance( ctx: &mut mbedtls_ctr_drbg_context, resistance: i32 ) -> ()
-{
-    (*ctx).prediction_resistance = resistance;
-}
-
+    /// This function initializes a CTR_DRBG context that runs without the
+    /// derivation function (SP 800-90A &sect;10.2.1.3.1), and prepares it
+    /// for seed().
+    ///
+    /// Such an instance needs full-entropy input: every seed or reseed
+    /// takes exactly MBEDTLS_CTR_DRBG_SEEDLEN bytes of entropy and no
+    /// nonce, and the personalization string and additional data are at
+    /// most MBEDTLS_CTR_DRBG_SEEDLEN bytes long. Other lengths are
+    /// rejected with Error::CtrDrbgInputTooBig.
+    pub fn new_no_df() -> Self {
+        let mut ctx = CtrDrbg::new();
+        ctx.use_df = false;
+        ctx
+    }
 
-//line 92
-// This function sets the amount of entropy grabbed on each seed or reseed.
-pub fn mbedtls_ctr_drbg_set_entropy_len(ctx: &mut [mbedtls_ctr_drbg_context], len:usize ) -> ()
-{
-    (*ctx).entropy_len = len;
-}
+    /*
+     *  This function resets CTR_DRBG context to the state immediately
+     *  after initial call of new() or new_no_df().
+     */
 
+    /// This function clears CTR_DRBG context data.
+    pub fn free(&mut self) {
+        let use_df = self.use_df;
 
-// line 98
-pub fn mbedtls_ctr_drbg_set_nonce_len(ctx: &mut [mbedtls_ctr_drbg_context], len: usize) -> i32
-{
-    /* If mbedtls_ctr_drbg_seed() has already been called, it's
-     * too late. Return the error code that's closest to making sense. */
-    if (*ctx).f_entropy != None {
-        return MBEDTLS_ERR_CTR_DRBG_ENTROPY_SOURCE_FAILED ;
+        *self = CtrDrbg::new();
+        self.use_df = use_df;
     }
 
-    if len > MBEDTLS_CTR_DRBG_MAX_SEED_INPUT {
-        return MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG ;
+    /// This function turns prediction resistance on or off. The default value is off.
+    pub fn set_prediction_resistance(&mut self, resistance: bool) {
+        self.prediction_resistance = resistance;
     }
 
-
-    /* This shouldn't be an issue because
-     * MBEDTLS_CTR_DRBG_MAX_SEED_INPUT < INT_MAX in any sensible
-     * configuration, but make sure anyway. */
-    if len > std::u32::MAX{
-        return MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG;
+    /// This function sets the amount of entropy grabbed on each seed or reseed.
+    pub fn set_entropy_len(&mut self, len: usize) {
+        self.entropy_len = len;
     }
 
+    /// This function sets the amount of entropy grabbed as a nonce for the
+    /// initial seeding. It must be called before seed().
+    pub fn set_nonce_len(&mut self, len: usize) -> Result<(), Error> {
+        /* If seed() has already been called, it's
+         * too late. Return the error code that's closest to making sense. */
+        if self.f_entropy.is_some() {
+            return Err(Error::CtrDrbgEntropySourceFailed);
+        }
 
-    /* For backward compatibility with Mbed TLS <= 2.19, store the
-     * entropy nonce length in a field that already exists, but isn't
-     * used until after the initial seeding. */
-    /* Due to the capping of len above, the value fits in an int. */
-    (*ctx).reseed_counter = len as i32;
-    return 0 ;
-}
+        if len > MBEDTLS_CTR_DRBG_MAX_SEED_INPUT {
+            return Err(Error::CtrDrbgInputTooBig);
+        }
 
+        /* For backward compatibility with Mbed TLS <= 2.19, store the
+         * entropy nonce length in a field that already exists, but isn't
+         * used until after the initial seeding. */
+        /* Due to the capping of len above, the value fits in an i32. */
+        self.reseed_counter = len as i32;
+        Ok(())
+    }
 
-// line 124
-// This function sets the reseed interval.
-pub fn mbedtls_ctr_drbg_set_reseed_interval(ctx: &mut [mbedtls_ctr_drbg_context], interval:i32) -> ()
-{
-    (*ctx).reseed_interval = interval;
+    /// This function sets the reseed interval: the number of generate
+    /// requests after which the entropy source is called again. The default
+    /// value is MBEDTLS_CTR_DRBG_RESEED_INTERVAL.
+    ///
+    /// As in mbedTLS, the value is not checked. A request reseeds when more
+    /// than `interval` requests were made since the last (re)seeding, so an
+    /// interval below 1 makes every request reseed first, like
+    /// prediction resistance.
+    pub fn set_reseed_interval(&mut self, interval: i32) {
+        self.reseed_interval = interval;
+    }
 }
 
-
-pub fn fun_exit(buf: &mut [u8], tmp: &mut [u8], key: &mut [u8], chain: &mut [u8], ret: u8, output: &mut [u8], aes_ctx: &mut mbedtls_aes_context) ->i32 {
-    mbedtls_aes_free( &aes_ctx );
-    /*
-    * tidy up the stack
-    */
-    mbedtls_platform_zeroize( buf, MBEDTLS_CTR_DRBG_MAX_SEED_INPUT + MBEDTLS_CTR_DRBG_BLOCKSIZE + 16 );
-    mbedtls_platform_zeroize( tmp, MBEDTLS_CTR_DRBG_SEEDLEN );
-    mbedtls_platform_zeroize( key, MBEDTLS_CTR_DRBG_KEYSIZE );
-    mbedtls_platform_zeroize( chain, MBEDTLS_CTR_DRBG_BLOCKSIZE );
-    if( 0 != ret )
-    {
-        /*
-        * wipe partial seed from memory
-        */
-        mbedtls_platform_zeroize( output, MBEDTLS_CTR_DRBG_SEEDLEN );
+impl Default for CtrDrbg<'_> {
+    fn default() -> Self {
+        Self::new()
     }
-
-    return ret ;
 }
 
-//line 130 
-pub fn block_cipher_df(output: &mut u8 , data: & u8 , data_len:usize ) -> i32{
-    let x:i32 = MBEDTLS_CTR_DRBG_MAX_SEED_INPUT + MBEDTLS_CTR_DRBG_BLOCKSIZE + 16;
-    let mut buf: [u8; x]= Default::default();
-    let mut tmp: [u8; MBEDTLS_CTR_DRBG_SEEDLEN]= Default::default();
-    let mut key: [u8; MBEDTLS_CTR_DRBG_KEYSIZE]= Default::default();
-    let mut chain: [u8; MBEDTLS_CTR_DRBG_BLOCKSIZE]= Default::default();
-    let mut p: &u8;
-    let mut iv: &u8;
-    let mut aes_ctx: mbedtls_aes_context;
-    let mut ret: u8;
-    ret =0;
-
-    let mut i;
-    let mut j;
-    buf_len:usize;
-    use_len:usize;
-
-    if data_len > MBEDTLS_CTR_DRBG_MAX_SEED_INPUT{
-        return MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG ;
+impl Drop for CtrDrbg<'_> {
+    /// This function wipes the counter V of the CTR_DRBG context. The key,
+    /// held as the AES key schedule, is wiped when aes_ctx is dropped.
+    fn drop(&mut self) {
+        platform_zeroize(&mut self.counter);
     }
+}
 
-    // memset( buf, 0, MBEDTLS_CTR_DRBG_MAX_SEED_INPUT + MBEDTLS_CTR_DRBG_BLOCKSIZE + 16 );
+pub(crate) fn block_cipher_df(output: &mut [u8; MBEDTLS_CTR_DRBG_SEEDLEN], data: &[u8]) -> Result<(), Error> {
+    let mut buf = Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_MAX_SEED_INPUT + MBEDTLS_CTR_DRBG_BLOCKSIZE + 16]);
+    let mut tmp = Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_SEEDLEN]);
+    let mut key = Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_KEYSIZE]);
+    let mut chain = Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_BLOCKSIZE]);
+    let mut aes_ctx = AesContext::new();
 
-    let limit: i32 = MBEDTLS_CTR_DRBG_MAX_SEED_INPUT + MBEDTLS_CTR_DRBG_BLOCKSIZE + 16;
-    for i in 0..limit {
-        buf[i] = 0;
+    let data_len = data.len();
+    if data_len > MBEDTLS_CTR_DRBG_MAX_SEED_INPUT {
+        return Err(Error::CtrDrbgInputTooBig);
     }
 
-    mbedtls_aes_init(&mut aes_ctx);
-    
     /*
      * Construct IV (16 bytes) and S in buffer
      * IV = Counter (in 32-bits) padded to 16 with zeroes
@@ -189,606 +168,899 @@ pub fn block_cipher_df(output: &mut u8 , data: & u8 , data_len:usize ) -> i32{
      *     data || 0x80
      *     (Total is padded to a multiple of 16-bytes with zeroes)
      */
-
-    p = buf + MBEDTLS_CTR_DRBG_BLOCKSIZE;
-    *p = ( data_len >> 24 ) & 0xff;
-    p += 1;
-    *p = ( data_len >> 16 ) & 0xff;
-    p += 1;
-    *p = ( data_len >> 8  ) & 0xff;
-    p += 1;
-    *p = ( data_len       ) & 0xff;
-    p += 3;
-    *p = MBEDTLS_CTR_DRBG_SEEDLEN;
-    p += 1;
-
-    //memcpy( p, data, data_len );
-
-    for i in 0..data_len {
-        p[i] = data[i];
+    {
+        let p = &mut buf[MBEDTLS_CTR_DRBG_BLOCKSIZE..];
+        p[..4].copy_from_slice(&(data_len as u32).to_be_bytes());
+        p[7] = MBEDTLS_CTR_DRBG_SEEDLEN as u8;
+        p[8..8 + data_len].copy_from_slice(data);
+        p[8 + data_len] = 0x80;
     }
 
-    p[data_len] = 0x80;
-
-    buf_len = MBEDTLS_CTR_DRBG_BLOCKSIZE + 8 + data_len + 1;
+    let buf_len = MBEDTLS_CTR_DRBG_BLOCKSIZE + 8 + data_len + 1;
 
-    for i in 0..MBEDTLS_CTR_DRBG_KEYSIZE{
-        key[i] = i;
+    for (i, k) in key.iter_mut().enumerate() {
+        *k = i as u8;
     }
 
-    if ( ret = mbedtls_aes_setkey_enc( &aes_ctx, key, MBEDTLS_CTR_DRBG_KEYBITS ) ) != 0 {
-        ret = fun_exit(&mut buf, &mut tmp, &mut key, &mut chain, &mut aes_ctx, &mut ret, &mut output, &mut aes_ctx);
-        return ret;
-    }
+    let ret = (|| {
+        aes_ctx.setkey_enc(&key[..], MBEDTLS_CTR_DRBG_KEYBITS)?;
 
-    /*
-     * Reduce data to MBEDTLS_CTR_DRBG_SEEDLEN bytes of data
-     */
-    
-    for j in 0..MBEDTLS_CTR_DRBG_SEEDLEN{
-        j += MBEDTLS_CTR_DRBG_BLOCKSIZE - 1;
-        p = buf;
+        /*
+         * Reduce data to MBEDTLS_CTR_DRBG_SEEDLEN bytes of data
+         */
+        for t in tmp.chunks_mut(MBEDTLS_CTR_DRBG_BLOCKSIZE) {
+            *chain = [0; MBEDTLS_CTR_DRBG_BLOCKSIZE];
+
+            for block in buf[..buf_len].chunks(MBEDTLS_CTR_DRBG_BLOCKSIZE) {
+                for (c, b) in chain.iter_mut().zip(block) {
+                    *c ^= *b;
+                }
+                let input = Zeroizing::new(*chain);
+                aes_ctx.encrypt_ecb(&input, &mut chain);
+            }
 
-        //memset( chain, 0, MBEDTLS_CTR_DRBG_BLOCKSIZE );
+            /*
+             * The last block is truncated when SEEDLEN is not a multiple
+             * of the block size (AES-192)
+             */
+            t.copy_from_slice(&chain[..t.len()]);
 
-        for i in 0..MBEDTLS_CTR_DRBG_BLOCKSIZE {
-            chain[i] = 0;
+            /*
+             * Update IV
+             */
+            buf[3] += 1;
         }
 
-        use_len = buf_len;
+        /*
+         * Do final encryption with reduced data
+         */
+        aes_ctx.setkey_enc(&tmp[..], MBEDTLS_CTR_DRBG_KEYBITS)?;
 
-        while use_len > 0 {
-            for i in 0..MBEDTLS_CTR_DRBG_BLOCKSIZE{
-                chain[i] ^= p[i];
-            }
-            p += MBEDTLS_CTR_DRBG_BLOCKSIZE;
-            use_len -= if use_len >= MBEDTLS_CTR_DRBG_BLOCKSIZE { MBEDTLS_CTR_DRBG_BLOCKSIZE } else { use_len };
+        let mut iv = Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_BLOCKSIZE]);
+        iv.copy_from_slice(&tmp[MBEDTLS_CTR_DRBG_KEYSIZE..]);
 
-            if ( ret = mbedtls_aes_crypt_ecb( &aes_ctx, MBEDTLS_AES_ENCRYPT, chain, chain ) ) != 0 {
-                ret = fun_exit(&mut buf, &mut tmp, &mut key, &mut chain, &mut aes_ctx, &mut ret, &mut output, &mut aes_ctx);
-                return ret;
-            }
+        for out in output.chunks_mut(MBEDTLS_CTR_DRBG_BLOCKSIZE) {
+            let input = Zeroizing::new(*iv);
+            aes_ctx.encrypt_ecb(&input, &mut iv);
+            out.copy_from_slice(&iv[..out.len()]);
         }
 
-        //memcpy( tmp + j, chain, MBEDTLS_CTR_DRBG_BLOCKSIZE );
-
-        for i in 0..MBEDTLS_CTR_DRBG_BLOCKSIZE {
-            tmp[j+i] = chain[i];
-        }
+        Ok(())
+    })();
 
+    /*
+     * buf, tmp, key and chain wipe themselves when they go out of scope
+     */
+    if ret.is_err() {
         /*
-         * Update IV
+         * wipe partial seed from memory
          */
-        buf[3] += 1;
+        platform_zeroize(output);
     }
-    
-    /*
-     * Do final encryption with reduced data
-     */
 
-    if ( ret = mbedtls_aes_setkey_enc( &aes_ctx, tmp, MBEDTLS_CTR_DRBG_KEYBITS ) ) != 0 {
-        ret = fun_exit(&mut buf, &mut tmp, &mut key, &mut chain, &mut aes_ctx, &mut ret, &mut output, &mut aes_ctx);
-        return ret;
+    ret
+}
+
+/* Return a "good" nonce length for CTR_DRBG. The chosen nonce length
+ * is sufficient to achieve the maximum security strength given the key
+ * size and entropy length. If there is enough entropy in the initial
+ * call to the entropy function to serve as both the entropy input and
+ * the nonce, don't make a second call to get a nonce. */
+fn good_nonce_len(entropy_len: usize) -> usize {
+    if entropy_len >= MBEDTLS_CTR_DRBG_KEYSIZE * 3 / 2 {
+        0
+    } else {
+        entropy_len.div_ceil(2)
     }
-    iv = tmp + MBEDTLS_CTR_DRBG_KEYSIZE;
-    p = output;
+}
 
-    for j in 0..MBEDTLS_CTR_DRBG_SEEDLEN{
-        j += MBEDTLS_CTR_DRBG_BLOCKSIZE - 1;
-        if ( ret = mbedtls_aes_crypt_ecb( &aes_ctx, MBEDTLS_AES_ENCRYPT, iv, iv ) ) != 0 {
-            ret = fun_exit(&mut buf, &mut tmp, &mut key, &mut chain, &mut aes_ctx, &mut ret, &mut output, &mut aes_ctx);
-            return ret;
+impl<'a> CtrDrbg<'a> {
+    /* CTR_DRBG_Update (SP 800-90A &sect;10.2.1.2)
+     * update_internal(provided_data)
+     * implements
+     * CTR_DRBG_Update(provided_data, Key, V)
+     * with inputs and outputs
+     *   self.aes_ctx = Key
+     *   self.counter = V
+     */
+    pub(crate) fn update_internal(&mut self, data: &[u8; MBEDTLS_CTR_DRBG_SEEDLEN]) -> Result<(), Error> {
+        let mut tmp = Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_SEEDLEN]);
+
+        for t in tmp.chunks_mut(MBEDTLS_CTR_DRBG_BLOCKSIZE) {
+            /*
+             * Increase counter
+             */
+            self.increment_counter();
+
+            /*
+             * Crypt counter block
+             */
+            let mut block = Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_BLOCKSIZE]);
+            self.aes_ctx.encrypt_ecb(&self.counter, &mut block);
+            t.copy_from_slice(&block[..t.len()]);
         }
 
-        //memcpy( p, iv, MBEDTLS_CTR_DRBG_BLOCKSIZE );
-
-        for i in 0..MBEDTLS_CTR_DRBG_BLOCKSIZE {
-            p[i] = iv[i];
+        for (t, d) in tmp.iter_mut().zip(data.iter()) {
+            *t ^= *d;
         }
 
-        p += MBEDTLS_CTR_DRBG_BLOCKSIZE;
+        /*
+         * Update key and counter
+         */
+        self.aes_ctx.setkey_enc(&tmp[..], MBEDTLS_CTR_DRBG_KEYBITS)?;
+        self.counter.copy_from_slice(&tmp[MBEDTLS_CTR_DRBG_KEYSIZE..]);
+
+        Ok(())
     }
 
-    ret = fun_exit(&mut buf, &mut tmp, &mut key, &mut chain, &mut aes_ctx, &mut ret, &mut output, &mut aes_ctx);
+    fn increment_counter(&mut self) {
+        for i in (0..MBEDTLS_CTR_DRBG_BLOCKSIZE).rev() {
+            self.counter[i] = self.counter[i].wrapping_add(1);
+            if self.counter[i] != 0 {
+                break;
+            }
+        }
+    }
 
-    return ret;
+    /* CTR_DRBG_Instantiate with derivation function (SP 800-90A &sect;10.2.1.3.2)
+     * update(additional)
+     * implements
+     * CTR_DRBG_Instantiate(entropy_input, nonce, personalization_string,
+     *                      security_strength) -> initial_working_state
+     * with inputs
+     *   self.counter = all-bits-0
+     *   self.aes_ctx = context from all-bits-0 key
+     *   additional = entropy_input || nonce || personalization_string
+     * and with outputs
+     *   self = initial_working_state
+     */
 
-}
+    /*
+     * Turn input data into seedlen bytes: through block_cipher_df() with
+     * the derivation function, or by padding it with zeros without it
+     * (SP 800-90A &sect;10.2.1.4.1 and &sect;10.2.1.5.1).
+     */
+    fn derive(&self, output: &mut [u8; MBEDTLS_CTR_DRBG_SEEDLEN], data: &[u8]) -> Result<(), Error> {
+        if self.use_df {
+            return block_cipher_df(output, data);
+        }
 
+        if data.len() > MBEDTLS_CTR_DRBG_SEEDLEN {
+            return Err(Error::CtrDrbgInputTooBig);
+        }
 
-/* CTR_DRBG_Update (SP 800-90A &sect;10.2.1.2)
- * ctr_drbg_update_internal(ctx, provided_data)
- * implements
- * CTR_DRBG_Update(provided_data, Key, V)
- * with inputs and outputs
- *   ctx->aes_ctx = Key
- *   ctx->counter = V
- */
+        output[..data.len()].copy_from_slice(data);
+        output[data.len()..].fill(0);
+        Ok(())
+    }
 
+    /// This function updates the state of the CTR_DRBG context.
+    pub fn update(&mut self, additional: &[u8]) -> Result<(), Error> {
+        let mut add_input = Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_SEEDLEN]);
 
-pub fn func_exit(tmp: &mut [u8], tmp_size: &mut usize, ret: i32) -> i32{
-    mbedtls_platform_zeroize( tmp, tmp_size );
-    return ret ;
-}
+        if additional.is_empty() {
+            return Ok(());
+        }
 
-//line 261
-pub fn ctr_drbg_update_internal( ctx: &mut mbedtls_ctr_drbg_context, data: &[u8; MBEDTLS_CTR_DRBG_SEEDLEN] ) -> i32
-{
-    let mut tmp: [u8; MBEDTLS_CTR_DRBG_SEEDLEN];
-    let &mut p: u8 = tmp;
-    let mut i;
-    let mut j;
-    let mut ret: i32 = 0;
+        self.derive(&mut add_input, additional)?;
+        self.update_internal(&add_input)
+    }
 
-    //memset( tmp, 0, MBEDTLS_CTR_DRBG_SEEDLEN );
+    /* CTR_DRBG_Reseed with derivation function (SP 800-90A &sect;10.2.1.4.2)
+     * reseed_internal(additional, nonce_len)
+     * implements
+     * CTR_DRBG_Reseed(working_state, entropy_input, additional_input)
+     *                -> new_working_state
+     * with inputs
+     *   self contains working_state
+     *   additional = additional_input
+     * and entropy_input comes from calling self.f_entropy
+     *                              for (self.entropy_len + nonce_len) bytes
+     * and with output
+     *   self contains new_working_state
+     *
+     * Without the derivation function (&sect;10.2.1.4.1), the entropy input
+     * is exactly seedlen bytes, there is no nonce, and the seed material
+     * is entropy_input XOR additional_input padded with zeros.
+     */
+    pub(crate) fn reseed_internal(&mut self, additional: &[u8], nonce_len: usize) -> Result<(), Error> {
+        let mut seed = Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_MAX_SEED_INPUT]);
+        let mut seedlen: usize = 0;
+        let len = additional.len();
 
-    for i in 0..MBEDTLS_CTR_DRBG_SEEDLEN {
-        tmp[i] = 0;
-    }
+        if self.entropy_len > MBEDTLS_CTR_DRBG_MAX_SEED_INPUT {
+            return Err(Error::CtrDrbgInputTooBig);
+        }
+        if nonce_len > MBEDTLS_CTR_DRBG_MAX_SEED_INPUT - self.entropy_len {
+            return Err(Error::CtrDrbgInputTooBig);
+        }
+        if len > MBEDTLS_CTR_DRBG_MAX_SEED_INPUT - self.entropy_len - nonce_len {
+            return Err(Error::CtrDrbgInputTooBig);
+        }
+        if !self.use_df &&
+            (self.entropy_len != MBEDTLS_CTR_DRBG_SEEDLEN || nonce_len != 0 || len > MBEDTLS_CTR_DRBG_SEEDLEN) {
+            return Err(Error::CtrDrbgInputTooBig);
+        }
 
-    for j in 0..MBEDTLS_CTR_DRBG_SEEDLEN{
-        j += MBEDTLS_CTR_DRBG_BLOCKSIZE - 1;
-        /*
-         * Increase counter
-         */
-        i = MBEDTLS_CTR_DRBG_BLOCKSIZE;
-        while i>0{
-            ctx += 1 ;
-            if (*ctx).counter[i - 1] != 0{
-                break;
+        let entropy_len = self.entropy_len;
+        let f_entropy = self.f_entropy.as_mut()
+            .ok_or(Error::CtrDrbgEntropySourceFailed)?;
+
+        /* Gather entropy_len bytes of entropy to seed state. */
+        if f_entropy.fill(&mut seed[..entropy_len]).is_err() {
+            return Err(Error::CtrDrbgEntropySourceFailed);
+        }
+        seedlen += entropy_len;
+
+        /* Gather entropy for a nonce if requested. */
+        if nonce_len != 0 {
+            if f_entropy.fill(&mut seed[seedlen..seedlen + nonce_len]).is_err() {
+                return Err(Error::CtrDrbgEntropySourceFailed);
             }
-            i -= 1;
+            seedlen += nonce_len;
         }
-        /*
-         * Crypt counter block
-         */
-        if ( ret = mbedtls_aes_crypt_ecb( &mut ctx.aes_ctx, MBEDTLS_AES_ENCRYPT, (*ctx).counter, p ) ) != 0 {
-            ret = func_exit(&mut tmp, &mut MBEDTLS_CTR_DRBG_SEEDLEN, ret);
-            return ret;
+
+        /* Add additional data if provided. */
+        if len != 0 {
+            seed[seedlen..seedlen + len].copy_from_slice(additional);
+            seedlen += len;
         }
 
-        p += MBEDTLS_CTR_DRBG_BLOCKSIZE;
-    }
+        /* Reduce to 384 bits, or without the derivation function,
+         * combine the full-entropy input with the padded additional data. */
+        let mut reduced = Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_SEEDLEN]);
+        if self.use_df {
+            block_cipher_df(&mut reduced, &seed[..seedlen])?;
+        } else {
+            self.derive(&mut reduced, additional)?;
+            for (r, s) in reduced.iter_mut().zip(&seed[..entropy_len]) {
+                *r ^= *s;
+            }
+        }
 
-    for i in 0..MBEDTLS_CTR_DRBG_SEEDLEN{
-        tmp[i] ^= data[i];
-    }
+        /* Update state. */
+        self.update_internal(&reduced)?;
+        self.reseed_counter = 1;
+        self.pid = process::id();
 
-    /*
-     * Update key and counter
-     */
-    if ( ret = mbedtls_aes_setkey_enc( &mut ctx.aes_ctx, tmp, MBEDTLS_CTR_DRBG_KEYBITS ) ) != 0 {
-        ret = func_exit(&mut tmp, &mut MBEDTLS_CTR_DRBG_SEEDLEN, ret);
-        return ret;
+        /* seed and reduced wipe themselves when they go out of scope. */
+        Ok(())
     }
 
-    //memcpy( ctx->counter, tmp + MBEDTLS_CTR_DRBG_KEYSIZE, MBEDTLS_CTR_DRBG_BLOCKSIZE );
-    for i in 0..MBEDTLS_CTR_DRBG_BLOCKSIZE {
-        ctx.counter[i] = tmp[MBEDTLS_CTR_DRBG_KEYSIZE + i];
+    /// This function reseeds the CTR_DRBG context, that is extracts data from the entropy source.
+    pub fn reseed(&mut self, additional: &[u8]) -> Result<(), Error> {
+        self.reseed_internal(additional, 0)
     }
 
-    ret = func_exit(&mut tmp, &mut MBEDTLS_CTR_DRBG_SEEDLEN, ret);
-    return ret;
+    /* CTR_DRBG_Instantiate with derivation function (SP 800-90A &sect;10.2.1.3.2)
+     * seed(f_entropy, custom)
+     * implements
+     * CTR_DRBG_Instantiate(entropy_input, nonce, personalization_string,
+     *                      security_strength) -> initial_working_state
+     * with inputs
+     *   custom = nonce || personalization_string
+     * where entropy_input comes from f_entropy for self.entropy_len bytes
+     * and with outputs
+     *   self = initial_working_state
+     */
 
-}
+    /// This function seeds and sets up the CTR_DRBG entropy source for future reseeds.
+    pub fn seed<E: EntropySource + Send + 'a>(&mut self, f_entropy: E, custom: &[u8]) -> Result<(), Error> {
+        let key = [0u8; MBEDTLS_CTR_DRBG_KEYSIZE];
 
-/* CTR_DRBG_Instantiate with derivation function (SP 800-90A &sect;10.2.1.3.2)
- * mbedtls_ctr_drbg_update(ctx, additional, add_len)
- * implements
- * CTR_DRBG_Instantiate(entropy_input, nonce, personalization_string,
- *                      security_strength) -> initial_working_state
- * with inputs
- *   ctx->counter = all-bits-0
- *   ctx->aes_ctx = context from all-bits-0 key
- *   additional[:add_len] = entropy_input || nonce || personalization_string
- * and with outputs
- *   ctx = initial_working_state
- */
+        self.aes_ctx = AesContext::new();
 
-pub fn funct_exit( add_input: &mut [u8], add_input_size: usize, ret: i32 ) -> i32{
-    mbedtls_platform_zeroize(add_input, add_input_size );
-    return ret ;
-}
+        self.f_entropy = Some(Box::new(f_entropy));
 
-//line 323
-// This function updates the state of the CTR_DRBG context. Returns 0 on success
-pub fn mbedtls_ctr_drbg_update_ret( ctx: &mut mbedtls_ctr_drbg_context, additional: &u8, add_len: usize ) -> i32{
-    let mut add_input: [u8; MBEDTLS_CTR_DRBG_SEEDLEN];
-    let mut ret: i32 = MBEDTLS_ERR_ERROR_CORRUPTION_DETECTED;
+        if self.entropy_len == 0 {
+            self.entropy_len = if self.use_df {
+                MBEDTLS_CTR_DRBG_ENTROPY_LEN
+            } else {
+                MBEDTLS_CTR_DRBG_SEEDLEN
+            };
+        }
 
-    if add_len == 0 {
-        return 0 ;
+        /* self.reseed_counter contains the desired amount of entropy to
+         * grab for a nonce (see set_nonce_len()).
+         * If it's -1, indicating that the entropy nonce length was not set
+         * explicitly, use a sufficiently large nonce for security.
+         * Without the derivation function there is no nonce. */
+        let nonce_len = if self.reseed_counter >= 0 {
+            self.reseed_counter as usize
+        } else if self.use_df {
+            good_nonce_len(self.entropy_len)
+        } else {
+            0
+        };
+
+        /* Initialize with an empty key. */
+        self.aes_ctx.setkey_enc(&key, MBEDTLS_CTR_DRBG_KEYBITS)?;
+
+        /* Do the initial seeding. */
+        self.reseed_internal(custom, nonce_len)
     }
 
-    if ( ret = block_cipher_df( add_input, additional, add_len ) ) != 0 {
-        ret = func_exit(add_input: &mut [u8], add_input_size: usize, ret: i32 );
-        return ret ;
+    /// This function seeds the CTR_DRBG from the platform entropy source,
+    /// which is then also used for future reseeds.
+    pub fn seed_default(&mut self, custom: &[u8]) -> Result<(), Error> {
+        self.seed(PlatformEntropy, custom)
     }
-    if ( ret = ctr_drbg_update_internal( ctx, add_input ) ) != 0 {
-        ret = func_exit(add_input: &mut [u8], add_input_size: usize, ret: i32 );
-        return ret ;
-    }
-
-    ret = func_exit(add_input: &mut [u8], add_input_size: usize, ret: i32 );
-    return ret ;
-}
 
+    /* CTR_DRBG_Generate with derivation function (SP 800-90A &sect;10.2.1.5.2)
+     * generate_with_additional(output, additional)
+     * implements
+     * CTR_DRBG_Reseed(working_state, entropy_input, additional)
+     *                -> working_state_after_reseed
+     *                if required, then
+     * CTR_DRBG_Generate(working_state_after_reseed,
+     *                   requested_number_of_bits, additional_input)
+     *                -> status, returned_bits, new_working_state
+     * with inputs
+     *   self contains working_state
+     *   requested_number_of_bits = 8 * output.len()
+     *   additional = additional_input
+     * and entropy_input comes from calling self.f_entropy
+     * and with outputs
+     *   status = SUCCESS (this function does the reseed internally)
+     *
+     * A reseed is also forced in a child process after fork(), which
+     * would otherwise produce the same output as its parent.
+     *   returned_bits = output
+     *   self contains new_working_state
+     */
 
-//line 343
-// This function updates the state of the CTR_DRBG context.
-pub fn mbedtls_ctr_drbg_update( ctx: &mut mbedtls_ctr_drbg_context , additional : &u8, add_len: usize) -> (){
-    /* MAX_INPUT would be more logical here, but we have to match
-     * block_cipher_df()'s limits since we can't propagate errors */
-    if add_len > MBEDTLS_CTR_DRBG_MAX_SEED_INPUT {
-        add_len = MBEDTLS_CTR_DRBG_MAX_SEED_INPUT;
-    }
-    mbedtls_ctr_drbg_update_ret( ctx, additional, add_len ) as ();
-}
+    /// This function updates a CTR_DRBG instance with additional data and uses it to generate random data.
+    pub fn generate_with_additional(&mut self, output: &mut [u8], additional: &[u8]) -> Result<(), Error> {
+        let mut add_input = Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_SEEDLEN]);
+        let mut tmp = Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_BLOCKSIZE]);
+        let mut additional = additional;
 
-/* CTR_DRBG_Reseed with derivation function (SP 800-90A &sect;10.2.1.4.2)
- * mbedtls_ctr_drbg_reseed(ctx, additional, len, nonce_len)
- * implements
- * CTR_DRBG_Reseed(working_state, entropy_input, additional_input)
- *                -> new_working_state
- * with inputs
- *   ctx contains working_state
- *   additional[:len] = additional_input
- * and entropy_input comes from calling ctx->f_entropy
- *                              for (ctx->entropy_len + nonce_len) bytes
- * and with output
- *   ctx contains new_working_state
- */
+        if output.len() > MBEDTLS_CTR_DRBG_MAX_REQUEST {
+            return Err(Error::CtrDrbgRequestTooBig);
+        }
 
-pub fn functi_exit(seed: &mut [u8], seed_size: usize, ret: i32) -> i32{
-    mbedtls_platform_zeroize( seed, seed_size );
-    return ret ;
-}
+        if additional.len() > MBEDTLS_CTR_DRBG_MAX_INPUT {
+            return Err(Error::CtrDrbgInputTooBig);
+        }
 
-//line 369
-pub fn mbedtls_ctr_drbg_reseed_internal( ctx: &mut mbedtls_ctr_drbg_context , additional: &u8 , len: usize, nonce_len: usize ) -> i32{
-    let mut seed: [u8; MBEDTLS_CTR_DRBG_MAX_SEED_INPUT];
-    let mut seedlen: usize = 0;
-    let mut ret: i32 = MBEDTLS_ERR_ERROR_CORRUPTION_DETECTED;
+        if self.reseed_counter > self.reseed_interval || self.prediction_resistance ||
+            self.pid != process::id() {
+            self.reseed(additional)?;
+            additional = &[];
+        }
 
-    if (*ctx).entropy_len > MBEDTLS_CTR_DRBG_MAX_SEED_INPUT {
-        return MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG ;
-    }
-    if nonce_len > MBEDTLS_CTR_DRBG_MAX_SEED_INPUT - (*ctx).entropy_len {
-        return MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG ;
-    }
-    if len > MBEDTLS_CTR_DRBG_MAX_SEED_INPUT - (*ctx).entropy_len - nonce_len {
-        return MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG ;
-    }
+        if !additional.is_empty() {
+            self.derive(&mut add_input, additional)?;
+            self.update_internal(&add_input)?;
+        }
 
-    //memset( seed, 0, MBEDTLS_CTR_DRBG_MAX_SEED_INPUT );
+        for chunk in output.chunks_mut(MBEDTLS_CTR_DRBG_BLOCKSIZE) {
+            /*
+             * Increase counter
+             */
+            self.increment_counter();
+
+            /*
+             * Crypt counter block
+             */
+            self.aes_ctx.encrypt_ecb(&self.counter, &mut tmp);
+
+            /*
+             * Copy random block to destination
+             */
+            chunk.copy_from_slice(&tmp[..chunk.len()]);
+        }
 
-    for i in 0..MBEDTLS_CTR_DRBG_MAX_SEED_INPUT {
-        seed[i] = 0;
-    }
+        self.update_internal(&add_input)?;
 
-    /* Gather entropy_len bytes of entropy to seed state. */
-    if 0 != (*ctx).f_entropy( (*ctx).p_entropy, seed, (*ctx).entropy_len ) {
-        return MBEDTLS_ERR_CTR_DRBG_ENTROPY_SOURCE_FAILED ;
+        self.reseed_counter += 1;
+        Ok(())
     }
-    seedlen += (*ctx).entropy_len;
 
-    /* Gather entropy for a nonce if requested. */
-    if nonce_len != 0 {
-        if 0 != (*ctx).f_entropy( (*ctx).p_entropy, seed, nonce_len ) {
-            return MBEDTLS_ERR_CTR_DRBG_ENTROPY_SOURCE_FAILED ;
-        }
-        seedlen += nonce_len;
+    /// This function uses CTR_DRBG to generate random data.
+    pub fn generate(&mut self, output: &mut [u8]) -> Result<(), Error> {
+        self.generate_with_additional(output, &[])
     }
 
-    /* Add additional data if provided. */
-    if additional != NULL && len != 0 {
+    /// This function fills a buffer of any length with random data, as a
+    /// sequence of generate requests of at most MBEDTLS_CTR_DRBG_MAX_REQUEST
+    /// bytes each. Every request is subject to the reseed interval and to
+    /// prediction resistance; the additional data only goes into the first.
+    pub fn fill_bytes_with_additional(&mut self, output: &mut [u8], additional: &[u8]) -> Result<(), Error> {
+        let mut additional = additional;
 
-        //memcpy( seed + seedlen, additional, len );
-        for i in 0..len {
-            seed[seedlen + i] = additional[i];
+        if output.is_empty() {
+            return self.generate_with_additional(output, additional);
         }
 
-        seedlen += len;
-    }
+        for chunk in output.chunks_mut(MBEDTLS_CTR_DRBG_MAX_REQUEST) {
+            self.generate_with_additional(chunk, additional)?;
+            additional = &[];
+        }
 
-    /* Reduce to 384 bits. */
-    if ( ret = block_cipher_df( seed, seed, seedlen ) ) != 0 {
-        ret = functi_exit(seed, MBEDTLS_CTR_DRBG_MAX_SEED_INPUT, ret);
-        return ret;
+        Ok(())
     }
 
-    /* Update state. */
-    if ( ret = ctr_drbg_update_internal( ctx, seed ) ) != 0 {
-        ret = functi_exit(seed, MBEDTLS_CTR_DRBG_MAX_SEED_INPUT, ret);
-        return ret;
+    /// This function fills a buffer of any length with random data.
+    pub fn fill_bytes(&mut self, output: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes_with_additional(output, &[])
     }
-    (*ctx).reseed_counter = 1;
 
-    ret = functi_exit(seed, MBEDTLS_CTR_DRBG_MAX_SEED_INPUT, ret);
-    return ret;
-}
+    /// This function writes a seed of MBEDTLS_CTR_DRBG_MAX_INPUT bytes of
+    /// output to `writer`, with a format header and an integrity tag.
+    ///
+    /// The record can be kept anywhere, such as in a database or a sealed
+    /// buffer, and given back to update_seed_from() later.
+    pub fn write_seed_to<W: Write>(&mut self, mut writer: W) -> Result<(), Error> {
+        let mut buf = Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_MAX_INPUT]);
+        let mut record = Zeroizing::new([0u8; SEED_FILE_MAX_LEN]);
 
+        self.generate(&mut buf[..])?;
+        let len = seed_file_encode(&buf[..], &mut record);
 
-//line 425
-// This function reseeds the CTR_DRBG context, that is extracts data from the entropy source. Returns 0 on success.
-pub fn mbedtls_ctr_drbg_reseed( ctx: &mut mbedtls_ctr_drbg_context , additional: &u8, len: usize ) -> i32{
-    return mbedtls_ctr_drbg_reseed_internal( ctx, additional, len, 0 ) ;
-}
+        writer.write_all(&record[..len]).map_err(|_| Error::CtrDrbgFileIoError)
+    }
 
+    /// This function reads a seed from `reader` until end of input and
+    /// adds it to this instance.
+    ///
+    /// Seeds written by write_seed_to() are checked against their
+    /// integrity tag; a truncated, corrupted or wrong-version record is
+    /// rejected with its own error. Input that does not start with
+    /// MBEDTLS_CTR_DRBG_SEED_FILE_MAGIC is read as a legacy raw seed of at
+    /// most MBEDTLS_CTR_DRBG_MAX_INPUT bytes.
+    ///
+    /// Unlike update_seed_file(), this function does not write a fresh
+    /// seed back; call write_seed_to() for that.
+    pub fn update_seed_from<R: Read>(&mut self, reader: R) -> Result<(), Error> {
+        let mut buf = Zeroizing::new([0u8; SEED_FILE_MAX_LEN + 1]);
+        let n = read_file(reader, &mut buf[..], Error::CtrDrbgFileIoError)?;
+
+        let seed = seed_file_decode(&buf[..n])?;
+        self.update(seed)
+    }
 
-//line 436 
-/* Return a "good" nonce length for CTR_DRBG. The chosen nonce length
- * is sufficient to achieve the maximum security strength given the key
- * size and entropy length. If there is enough entropy in the initial
- * call to the entropy function to serve as both the entropy input and
- * the nonce, don't make a second call to get a nonce. */
- 
-pub fn good_nonce_len( entropy_len:usize ) -> usize{
-    if entropy_len >= MBEDTLS_CTR_DRBG_KEYSIZE * 3 / 2 {
-        return 0 ;
+    /// This function writes a seed file, in the format of write_seed_to().
+    ///
+    /// The file is written next to `path` under a temporary name, readable
+    /// and writable by its owner only, then renamed over `path`, so that a
+    /// crash never leaves a partially written seed file behind.
+    pub fn write_seed_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        write_file_atomic(path.as_ref(), Error::CtrDrbgFileIoError, |f| self.write_seed_to(f))
     }
-    else{
-        return ( entropy_len + 1 ) / 2 ;
+
+    /// This function reads and updates a seed file. The seed is added to
+    /// this instance as by update_seed_from(), then a fresh seed file is
+    /// written. A file that is rejected is left untouched.
+    pub fn update_seed_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let f = File::open(path).map_err(|_| Error::CtrDrbgFileIoError)?;
+
+        self.update_seed_from(f)?;
+        self.write_seed_file(path)
     }
 }
 
-/* CTR_DRBG_Instantiate with derivation function (SP 800-90A &sect;10.2.1.3.2)
- * mbedtls_ctr_drbg_seed(ctx, f_entropy, p_entropy, custom, len)
- * implements
- * CTR_DRBG_Instantiate(entropy_input, nonce, personalization_string,
- *                      security_strength) -> initial_working_state
- * with inputs
- *   custom[:len] = nonce || personalization_string
- * where entropy_input comes from f_entropy for ctx->entropy_len bytes
- * and with outputs
- *   ctx = initial_working_state
+/*
+ * Seed file format, version 1:
+ *   magic (4) || version (1) || seed length (2, big endian) || seed ||
+ *   tag (32)
+ * where tag = SHA-256(magic || version || seed length || seed).
+ * The tag detects truncation and corruption; it is not a MAC.
  */
+const SEED_FILE_MAX_LEN: usize =
+    MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN + MBEDTLS_CTR_DRBG_MAX_INPUT + MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN;
 
-//line 455 function pointer
-// This function seeds and sets up the CTR_DRBG entropy source for future reseeds. Returns 0 on success.
-pub fn mbedtls_ctr_drbg_seed( ctx: &mut mbedtls_ctr_drbg_context , fptr : f_ptr(data: Option<*mut c_void>, output: &mut [u8], len: usize, olen: usize), p_entropy: Option<*mut c_void>, custom: &u8, len: usize ) -> i32{
-    let mut ret: i32 = MBEDTLS_ERR_ERROR_CORRUPTION_DETECTED;
-    let mut key: [u8; MBEDTLS_CTR_DRBG_KEYSIZE];
-    let mut nonce_len: usize;
+/*
+ * Encodes seed as a seed file and returns the length used in output.
+ */
+fn seed_file_encode(seed: &[u8], output: &mut [u8; SEED_FILE_MAX_LEN]) -> usize {
+    let body_len = MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN + seed.len();
+    let mut tag = [0u8; MBEDTLS_SHA256_OUTPUT_SIZE];
 
-    //memset( key, 0, MBEDTLS_CTR_DRBG_KEYSIZE );
-    for i in 0..MBEDTLS_CTR_DRBG_KEYSIZE {
-        key[i] = 0;
-    }
+    output[..4].copy_from_slice(&MBEDTLS_CTR_DRBG_SEED_FILE_MAGIC);
+    output[4] = MBEDTLS_CTR_DRBG_SEED_FILE_VERSION;
+    output[5..7].copy_from_slice(&(seed.len() as u16).to_be_bytes());
+    output[MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN..body_len].copy_from_slice(seed);
 
-    mbedtls_aes_init( &mut ctx.aes_ctx );
+    sha256(&output[..body_len], &mut tag, false);
+    output[body_len..body_len + MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN].copy_from_slice(&tag);
 
-    (*ctx).fptr = fptr;//(*ctx).f_entropy = f_entropy;
-    (*ctx).p_entropy = p_entropy;
+    body_len + MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN
+}
 
-    if (*ctx).entropy_len == 0 {
-        ctx->entropy_len = MBEDTLS_CTR_DRBG_ENTROPY_LEN;
+/// This function returns the seed held in the contents of a seed file,
+/// either in the versioned format or as a legacy raw seed, with the
+/// checks of update_seed_file() but without touching any context.
+///
+/// It returns the same errors as update_seed_file() on data it would
+/// refuse, so a seed file can be validated without seeding a DRBG.
+pub fn seed_file_decode(data: &[u8]) -> Result<&[u8], Error> {
+    let mut tag = [0u8; MBEDTLS_SHA256_OUTPUT_SIZE];
+
+    if !data.starts_with(&MBEDTLS_CTR_DRBG_SEED_FILE_MAGIC) {
+        return if data.is_empty() {
+            Err(Error::CtrDrbgFileIoError)
+        } else if data.len() > MBEDTLS_CTR_DRBG_MAX_INPUT {
+            Err(Error::CtrDrbgInputTooBig)
+        } else {
+            Ok(data)
+        };
     }
-    /* ctx->reseed_counter contains the desired amount of entropy to
-     * grab for a nonce (see mbedtls_ctr_drbg_set_nonce_len()).
-     * If it's -1, indicating that the entropy nonce length was not set
-     * explicitly, use a sufficiently large nonce for security. */
-
-    nonce_len = if (*ctx).reseed_counter >= 0 { (size_t) (*ctx).reseed_counter } else {good_nonce_len( (*ctx).entropy_len ) };
 
-    /* Initialize with an empty key. */
-    if ( ret = mbedtls_aes_setkey_enc( &mut ctx.aes_ctx, key, MBEDTLS_CTR_DRBG_KEYBITS ) ) != 0 {
-        return ret ;
+    if data.len() < MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN {
+        return Err(Error::CtrDrbgSeedFileTruncated);
     }
-
-    /* Do the initial seeding. */
-    if ( ret = mbedtls_ctr_drbg_reseed_internal( ctx, custom, len, nonce_len ) ) != 0 {
-        return ret ;
+    if data[4] != MBEDTLS_CTR_DRBG_SEED_FILE_VERSION {
+        return Err(Error::CtrDrbgSeedFileBadVersion);
     }
-    return 0 ;
-}
-
-/* CTR_DRBG_Generate with derivation function (SP 800-90A &sect;10.2.1.5.2)
- * mbedtls_ctr_drbg_random_with_add(ctx, output, output_len, additional, add_len)
- * implements
- * CTR_DRBG_Reseed(working_state, entropy_input, additional[:add_len])
- *                -> working_state_after_reseed
- *                if required, then
- * CTR_DRBG_Generate(working_state_after_reseed,
- *                   requested_number_of_bits, additional_input)
- *                -> status, returned_bits, new_working_state
- * with inputs
- *   ctx contains working_state
- *   requested_number_of_bits = 8 * output_len
- *   additional[:add_len] = additional_input
- * and entropy_input comes from calling ctx->f_entropy
- * and with outputs
- *   status = SUCCESS (this function does the reseed internally)
- *   returned_bits = output[:output_len]
- *   ctx contains new_working_state
- */
 
-pub fn funtio_exit(add_input: &mut [u8], add_input_size: usize, ret: i32, tmp: &mut [u8], tmp_size: usize) -> i32 {
-    mbedtls_platform_zeroize( add_input, add_input_size ); // MBEDTLS_CTR_DRBG_SEEDLEN
-    mbedtls_platform_zeroize( tmp, tmp_size ); // MBEDTLS_CTR_DRBG_BLOCKSIZE
-    return ret ;
-}
-
-// line 517
-// This function updates a CTR_DRBG instance with additional data and uses it to generate random data. Returns 0 on success.
-pub fn mbedtls_ctr_drbg_random_with_add( p_rng: Option<*mut c_void>, output: &u8, output_len: usize, additional: &u8, add_len: usize ){
-    let mut ret:u8 = 0;
-    let &mut ctx: mbedtls_ctr_drbg_context =  p_rng: &mut mbedtls_ctr_drbg_context;
-    let mut add_input: [u8; MBEDTLS_CTR_DRBG_SEEDLEN];
-    let &mut p:u8 = output;
-    let mut tmp: [u8; MBEDTLS_CTR_DRBG_BLOCKSIZE];
-    let mut i: i32;
-    let mut use_len: usize;
-
-    if output_len > MBEDTLS_CTR_DRBG_MAX_REQUEST {
-        return MBEDTLS_ERR_CTR_DRBG_REQUEST_TOO_BIG ;
+    let seed_len = u16::from_be_bytes([data[5], data[6]]) as usize;
+    if seed_len > MBEDTLS_CTR_DRBG_MAX_INPUT {
+        return Err(Error::CtrDrbgInputTooBig);
     }
 
-    if add_len > MBEDTLS_CTR_DRBG_MAX_INPUT {
-        return MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG ;
+    let body_len = MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN + seed_len;
+    if data.len() < body_len + MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN {
+        return Err(Error::CtrDrbgSeedFileTruncated);
     }
-
-    // memset( add_input, 0, MBEDTLS_CTR_DRBG_SEEDLEN );
-    for i in 0..MBEDTLS_CTR_DRBG_SEEDLEN {
-        add_input[i] = 0;
+    if data.len() > body_len + MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN {
+        return Err(Error::CtrDrbgSeedFileCorrupted);
     }
 
-    if (*ctx).reseed_counter > (*ctx).reseed_interval || (*ctx).prediction_resistance {
-        if ( ret = mbedtls_ctr_drbg_reseed( ctx, additional, add_len ) ) != 0 {
-            return ret ;
-        }
-        add_len = 0;
+    sha256(&data[..body_len], &mut tag, false);
+    if data[body_len..] != tag[..] {
+        return Err(Error::CtrDrbgSeedFileCorrupted);
     }
-
-    if add_len > 0 {
-        if ( ret = block_cipher_df( add_input, additional, add_len ) ) != 0 {
-            ret = funtio_exit(add_input, MBEDTLS_CTR_DRBG_SEEDLEN, tmp, MBEDTLS_CTR_DRBG_BLOCKSIZE, ret);
-            return ret;
-        }
-        if ( ret = ctr_drbg_update_internal( ctx, add_input ) ) != 0 {
-            ret = funtio_exit(add_input, MBEDTLS_CTR_DRBG_SEEDLEN, tmp, MBEDTLS_CTR_DRBG_BLOCKSIZE, ret);
-            return ret;
-        }
+    if seed_len == 0 {
+        return Err(Error::CtrDrbgFileIoError);
     }
 
-    while output_len > 0 {
-        /*
-         * Increase counter
-         */
-        i = MBEDTLS_CTR_DRBG_BLOCKSIZE;
-        while i > 0 {
-            (*(ctx).counter)[i - 1] += 1;
-            if (*(ctx).counter)[i - 1] != 0 {
-                break;
-            }
-            i -= 1 ;
-        }
-
-        /*
-         * Crypt counter block
-         */
-        if ( ret = mbedtls_aes_crypt_ecb( &mut ctx.aes_ctx, MBEDTLS_AES_ENCRYPT, (*ctx).counter, tmp ) ) != 0  {
-            ret = funtio_exit(add_input, MBEDTLS_CTR_DRBG_SEEDLEN, tmp, MBEDTLS_CTR_DRBG_BLOCKSIZE, ret);
-            return ret;
-        }
-
-        use_len = if output_len > MBEDTLS_CTR_DRBG_BLOCKSIZE { MBEDTLS_CTR_DRBG_BLOCKSIZE } else { output_len };
-        /*
-         * Copy random block to destination
-         */
+    Ok(&data[MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN..body_len])
+}
 
-        // memcpy( p, tmp, use_len );
-        for i in 0..use_len {
-            p[i] = tmp[i];
+impl SharedCtrDrbg {
+    /// This function moves a CTR_DRBG context behind a mutex so that it
+    /// can be shared between threads.
+    pub fn new(ctx: CtrDrbg<'static>) -> Self {
+        SharedCtrDrbg {
+            inner: Arc::new(mutex_init(ctx)),
         }
-
-        p += use_len;
-        output_len -= use_len;
     }
 
-    if ( ret = ctr_drbg_update_internal( ctx, add_input ) ) != 0 {
-        ret = funtio_exit(add_input, MBEDTLS_CTR_DRBG_SEEDLEN, tmp, MBEDTLS_CTR_DRBG_BLOCKSIZE, ret);
-        return ret;
+    /// This function locks the context for a sequence of operations. The
+    /// context is unlocked when the guard is dropped.
+    pub fn lock(&self) -> Result<MutexGuard<'_, CtrDrbg<'static>>, Error> {
+        mutex_lock(&self.inner)
     }
 
-    (*ctx).reseed_counter += 1; 
-
-    ret = funtio_exit(add_input, MBEDTLS_CTR_DRBG_SEEDLEN, tmp, MBEDTLS_CTR_DRBG_BLOCKSIZE, ret);
-    return ret;
+    /// This function uses CTR_DRBG to generate random data, holding the
+    /// lock for the whole request. It is the counterpart of
+    /// mbedtls_ctr_drbg_random().
+    pub fn random(&self, output: &mut [u8]) -> Result<(), Error> {
+        self.random_with_add(output, &[])
+    }
 
-}
+    /// This function updates the shared instance with additional data and
+    /// uses it to generate random data, holding the lock for the whole
+    /// request.
+    pub fn random_with_add(&self, output: &mut [u8], additional: &[u8]) -> Result<(), Error> {
+        let mut ctx = self.lock()?;
 
+        ctx.generate_with_additional(output, additional)
+    }
 
-// line 594
-// This function uses CTR_DRBG to generate random data. Returns 0 on success.
-pub fn mbedtls_ctr_drbg_random( p_rng: Option<*mut c_void>, output: & u8, output_len: usize ) -> i32 {
-    let mut ret: i32 = MBEDTLS_ERR_ERROR_CORRUPTION_DETECTED;
-    let &mut ctx: mbedtls_ctr_drbg_context = p_rng:&mbedtls_ctr_drbg_context; // doubt
+    /// This function fills a buffer of any length with random data,
+    /// holding the lock until the whole buffer is filled.
+    pub fn fill_bytes(&self, output: &mut [u8]) -> Result<(), Error> {
+        let mut ctx = self.lock()?;
 
-    if ( ret = mbedtls_mutex_lock( &mut ctx.mutex ) ) != 0 {
-        return ret ;
+        ctx.fill_bytes(output)
     }
 
-    ret = mbedtls_ctr_drbg_random_with_add( ctx, output, output_len, NULL, 0 );
+    /// This function reseeds the shared instance.
+    pub fn reseed(&self, additional: &[u8]) -> Result<(), Error> {
+        let mut ctx = self.lock()?;
 
-    if mbedtls_mutex_unlock( &mut ctx.mutex ) != 0 {
-        return MBEDTLS_ERR_THREADING_MUTEX_ERROR ;
+        ctx.reseed(additional)
     }
+}
 
-    return ret ;
+impl From<CtrDrbg<'static>> for SharedCtrDrbg {
+    fn from(ctx: CtrDrbg<'static>) -> Self {
+        SharedCtrDrbg::new(ctx)
+    }
 }
 
+impl<'a> CtrDrbgReader<'a> {
+    /// This function wraps a seeded CTR_DRBG context as a reader.
+    pub fn new(ctx: CtrDrbg<'a>) -> Self {
+        CtrDrbgReader {
+            ctx,
+            buf: Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_MAX_REQUEST]),
+            pos: MBEDTLS_CTR_DRBG_MAX_REQUEST,
+        }
+    }
 
-// line 615
-// This function writes a seed file. Returns 0 on success.
-pub fn mbedtls_ctr_drbg_write_seed_file( ctx: &mut mbedtls_ctr_drbg_context , path: & u8 ) -> i32 {
-    let mut ret: i32 = MBEDTLS_ERR_CTR_DRBG_FILE_IO_ERROR;
-    let mut f = fs::File::open(path)?;
-    let mut buf: [u8; MBEDTLS_CTR_DRBG_MAX_INPUT ];
+    /// This function gives access to the context, for instance to reseed
+    /// it. Output already buffered is still returned first.
+    pub fn get_mut(&mut self) -> &mut CtrDrbg<'a> {
+        &mut self.ctx
+    }
 
-    if f == None {
-        return( MBEDTLS_ERR_CTR_DRBG_FILE_IO_ERROR );
+    /// This function returns the context. Output buffered but not read is
+    /// wiped.
+    pub fn into_inner(self) -> CtrDrbg<'a> {
+        self.ctx
     }
+}
 
-    ret = mbedtls_ctr_drbg_random( ctx, buf, MBEDTLS_CTR_DRBG_MAX_INPUT );
+impl<'a> From<CtrDrbg<'a>> for CtrDrbgReader<'a> {
+    fn from(ctx: CtrDrbg<'a>) -> Self {
+        CtrDrbgReader::new(ctx)
+    }
+}
 
-    if ret == 0 {
-        if fwrite( buf, 1, MBEDTLS_CTR_DRBG_MAX_INPUT, f ) != MBEDTLS_CTR_DRBG_MAX_INPUT {
-            ret = MBEDTLS_ERR_CTR_DRBG_FILE_IO_ERROR;
+impl Read for CtrDrbgReader<'_> {
+    /// This function fills `buf` with random data. Buffered output is
+    /// returned first; once it is used up, whole requests are generated
+    /// straight into `buf`. Either way the stream is the same sequence of
+    /// MBEDTLS_CTR_DRBG_MAX_REQUEST-byte requests, whatever the read sizes.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        /* An empty read must not generate a request, which would count
+         * towards the reseed interval. */
+        if buf.is_empty() {
+            return Ok(0);
         }
-        else{
-            ret = 0;
+
+        let whole = buf.len() - buf.len() % MBEDTLS_CTR_DRBG_MAX_REQUEST;
+
+        if self.pos == MBEDTLS_CTR_DRBG_MAX_REQUEST && whole > 0 {
+            self.ctx.fill_bytes(&mut buf[..whole])?;
+            return Ok(whole);
         }
-    }
 
-    mbedtls_platform_zeroize(buf, MBEDTLS_CTR_DRBG_MAX_INPUT);
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
 
-    fclose( f );
-    return ret ;
+        Ok(n)
+    }
 }
 
+impl BufRead for CtrDrbgReader<'_> {
+    /// This function returns the unread output, generating a new request
+    /// of MBEDTLS_CTR_DRBG_MAX_REQUEST bytes when it is all read.
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos == MBEDTLS_CTR_DRBG_MAX_REQUEST {
+            self.ctx.generate(&mut self.buf[..])?;
+            self.pos = 0;
+        }
 
-// line 647 file return
-// This function reads and updates a seed file. The seed is added to this instance. Returns 0 on success.
-pub fn mbedtls_ctr_drbg_update_seed_file(ctx: &mut mbedtls_ctr_drbg_context, path: &u8 ) ->i32 {
-    let mut ret: i32 = 0;
-    let mut f = fs::File::open(path)?; // doubt
-    let mut n: usize;
-    let mut buf: [u8; MBEDTLS_CTR_DRBG_MAX_INPUT ];
-    let mut c: u8;
+        Ok(&self.buf[self.pos..])
+    }
 
-    let mut tmp: i32;
+    /// This function marks `amt` bytes as read and wipes them from the
+    /// buffer.
+    fn consume(&mut self, amt: usize) {
+        let end = (self.pos + amt).min(MBEDTLS_CTR_DRBG_MAX_REQUEST);
 
-    if f == None {
-        return MBEDTLS_ERR_CTR_DRBG_FILE_IO_ERROR ;
+        platform_zeroize(&mut self.buf[self.pos..end]);
+        self.pos = end;
     }
+}
 
-    n = fread( buf, 1, sizeof( buf ), f );
-    if fread( &c, 1, 1, f ) != 0 {
-        ret = MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG;
-    }
-    else if n == 0 || ferror( f ) {
-        ret = MBEDTLS_ERR_CTR_DRBG_FILE_IO_ERROR;
-    }
-    else {
-        fclose f ;
-        f = NULL;
-        ret = mbedtls_ctr_drbg_update_ret( ctx, buf, n );
+impl<F> EntropySource for F
+where
+    F: FnMut(&mut [u8]) -> Result<(), Error>,
+{
+    fn fill(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        self(buf)
     }
+}
 
-    mbedtls_platform_zeroize( buf, buf_size );
-    if f != None {
-        fclose f ;
+impl EntropySource for Box<dyn EntropySource + Send + '_> {
+    fn fill(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        (**self).fill(buf)
     }
-    if ret != 0 {
-        return ret ;
+}
+
+impl LegacyEntropySource {
+    /// This function wraps a C-style entropy callback and its context,
+    /// the f_entropy and p_entropy arguments of mbedtls_ctr_drbg_seed().
+    ///
+    /// # Safety
+    ///
+    /// `f_entropy` must be safe to call with `p_entropy` and any writable
+    /// buffer, from whichever thread ends up owning the DRBG, for as long
+    /// as the source is alive.
+    pub unsafe fn new(f_entropy: EntropyFn, p_entropy: *mut c_void) -> Self {
+        LegacyEntropySource { f_entropy, p_entropy }
     }
-    return mbedtls_ctr_drbg_write_seed_file( ctx, path );
 }
 
+/* The caller of new() vouches for p_entropy being usable from any thread. */
+unsafe impl Send for LegacyEntropySource {}
+
+impl EntropySource for LegacyEntropySource {
+    fn fill(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        let ret = unsafe { (self.f_entropy)(self.p_entropy, buf.as_mut_ptr(), buf.len()) };
 
-// line 687
-pub const entropy_source_pr:[u8;96]=[
+        if ret != 0 {
+            return Err(Error::CtrDrbgEntropySourceFailed);
+        }
+        Ok(())
+    }
+}
+
+/*
+ * Based on NIST CTR_DRBG test vectors (PR = True / False), with
+ * [AES-128 use df] or [AES-256 use df] depending on the key size.
+ *
+ * The AES-192 vectors are a cross-implementation check, not NIST vectors:
+ * no copy of the [AES-192 use df] sections of CTR_DRBG.rsp is bundled.
+ * Their inputs are pseudo-random and their expected outputs were computed
+ * with the OpenSSL 3.5 CTR-DRBG provider; they are COUNT = 0 of the
+ * [AES-192 use df] sections with PersonalizationStringLen = 192 and
+ * AdditionalInputLen = 0 in tests/data/ctr_drbg/openssl_computed
+ * (CTR_DRBG_pr_true.rsp and CTR_DRBG_pr_false.rsp).
+ *
+ * RESULT_DF is the derivation function applied to ENTROPY_SOURCE_NOPR.
+ * RESULT_UPDATE and RESULT_RESEED are the first block generated after
+ * seeding as in the PR = False test, then updating or reseeding with
+ * NONCE_PERS_PR as additional input. No published vector covers them;
+ * for every key size they were computed with the OpenSSL 3.5 CTR-DRBG
+ * provider, as the first block of a generate request with NONCE_PERS_PR
+ * as additional input (which starts with the same update) and after
+ * EVP_RAND_reseed() with NONCE_PERS_PR.
+ */
+#[cfg(feature = "ctr-drbg-128-bit-key")]
+const ENTROPY_SOURCE_PR: [u8; 56] = [
+      0x04, 0xd9, 0x49, 0xa6, 0xdc, 0xe8, 0x6e, 0xbb,
+      0xf1, 0x08, 0x77, 0x2b, 0x9e, 0x08, 0xca, 0x92,
+      0x65, 0x16, 0xda, 0x99, 0xa2, 0x59, 0xf3, 0xe8,
+      0x38, 0x7e, 0x3f, 0x6b, 0x51, 0x70, 0x7b, 0x20,
+      0xec, 0x53, 0xd0, 0x66, 0xc3, 0x0f, 0xe3, 0xb0,
+      0xe0, 0x86, 0xa6, 0xaa, 0x5f, 0x72, 0x2f, 0xad,
+      0xf7, 0xef, 0x06, 0xb8, 0xd6, 0x9c, 0x9d, 0xe8 ];
+
+#[cfg(feature = "ctr-drbg-128-bit-key")]
+const ENTROPY_SOURCE_NOPR: [u8; 40] = [
+      0x07, 0x0d, 0x59, 0x63, 0x98, 0x73, 0xa5, 0x45,
+      0x27, 0x38, 0x22, 0x7b, 0x76, 0x85, 0xd1, 0xa9,
+      0x74, 0x18, 0x1f, 0x3c, 0x22, 0xf6, 0x49, 0x20,
+      0x4a, 0x47, 0xc2, 0xf3, 0x85, 0x16, 0xb4, 0x6f,
+      0x00, 0x2e, 0x71, 0xda, 0xed, 0x16, 0x9b, 0x5c ];
+
+#[cfg(feature = "ctr-drbg-128-bit-key")]
+const NONCE_PERS_PR: [u8; 16] = [
+      0xbf, 0xa4, 0x9a, 0x8f, 0x7b, 0xd8, 0xb1, 0x7a,
+      0x9d, 0xfa, 0x45, 0xed, 0x21, 0x52, 0xb3, 0xad ];
+
+#[cfg(feature = "ctr-drbg-128-bit-key")]
+const NONCE_PERS_NOPR: [u8; 16] = [
+      0x4e, 0x61, 0x79, 0xd4, 0xc2, 0x72, 0xa1, 0x4c,
+      0xf1, 0x3d, 0xf6, 0x5e, 0xa3, 0xa6, 0xe5, 0x0f ];
+
+#[cfg(feature = "ctr-drbg-128-bit-key")]
+const RESULT_PR: [u8; 64] = [
+      0xc9, 0x0a, 0xaf, 0x85, 0x89, 0x71, 0x44, 0x66,
+      0x4f, 0x25, 0x0b, 0x2b, 0xde, 0xd8, 0xfa, 0xff,
+      0x52, 0x5a, 0x1b, 0x32, 0x5e, 0x41, 0x7a, 0x10,
+      0x1f, 0xef, 0x1e, 0x62, 0x23, 0xe9, 0x20, 0x30,
+      0xc9, 0x0d, 0xad, 0x69, 0xb4, 0x9c, 0x5b, 0xf4,
+      0x87, 0x42, 0xd5, 0xae, 0x5e, 0x5e, 0x43, 0xcc,
+      0xd9, 0xfd, 0x0b, 0x93, 0x4a, 0xe3, 0xd4, 0x06,
+      0x37, 0x36, 0x0f, 0x3f, 0x72, 0x82, 0x0c, 0xcf ];
+
+#[cfg(feature = "ctr-drbg-128-bit-key")]
+const RESULT_NOPR: [u8; 64] = [
+      0x31, 0xc9, 0x91, 0x09, 0xf8, 0xc5, 0x10, 0x13,
+      0x3c, 0xd3, 0x96, 0xf9, 0xbc, 0x2c, 0x12, 0xc0,
+      0x7c, 0xc1, 0x61, 0x5f, 0xa3, 0x09, 0x99, 0xaf,
+      0xd7, 0xf2, 0x36, 0xfd, 0x40, 0x1a, 0x8b, 0xf2,
+      0x33, 0x38, 0xee, 0x1d, 0x03, 0x5f, 0x83, 0xb7,
+      0xa2, 0x53, 0xdc, 0xee, 0x18, 0xfc, 0xa7, 0xf2,
+      0xee, 0x96, 0xc6, 0xc2, 0xcd, 0x0c, 0xff, 0x02,
+      0x76, 0x70, 0x69, 0xaa, 0x69, 0xd1, 0x3b, 0xe8 ];
+
+#[cfg(feature = "ctr-drbg-128-bit-key")]
+const RESULT_DF: [u8; 32] = [
+      0xaf, 0x44, 0x2f, 0xea, 0xe5, 0x04, 0xa1, 0xc1,
+      0x76, 0xff, 0xbf, 0x90, 0x94, 0x90, 0x22, 0xee,
+      0xa7, 0xce, 0xbe, 0x25, 0xd5, 0x8f, 0x7a, 0x37,
+      0xd6, 0xd5, 0x4b, 0x50, 0x2d, 0xe9, 0xea, 0x87 ];
+
+#[cfg(feature = "ctr-drbg-128-bit-key")]
+const RESULT_UPDATE: [u8; 16] = [
+      0x47, 0x89, 0xcb, 0x2a, 0x6a, 0x3b, 0x50, 0x6b,
+      0x7c, 0x1b, 0x34, 0xa0, 0xb9, 0x42, 0x44, 0x43 ];
+
+#[cfg(feature = "ctr-drbg-128-bit-key")]
+const RESULT_RESEED: [u8; 16] = [
+      0x42, 0x9e, 0xa9, 0x73, 0xb7, 0xa0, 0x07, 0xa2,
+      0x6e, 0x26, 0x85, 0xf6, 0x8b, 0xee, 0xc3, 0xa5 ];
+
+#[cfg(all(feature = "ctr-drbg-192-bit-key", not(feature = "ctr-drbg-128-bit-key")))]
+const ENTROPY_SOURCE_PR: [u8; 84] = [
+      0x34, 0xfd, 0x00, 0x3f, 0x14, 0x98, 0xb2, 0xe8,
+      0xb3, 0x9c, 0x26, 0x32, 0x82, 0xcc, 0x8f, 0x8f,
+      0xba, 0x8a, 0x63, 0x16, 0xe7, 0x98, 0xb0, 0x81,
+      0x79, 0x49, 0xb0, 0x61, 0xf3, 0x71, 0x97, 0x47,
+      0x2c, 0xf2, 0x4f, 0xae, 0xe5, 0x99, 0x01, 0xd0,
+      0x54, 0xe3, 0xb6, 0x9c, 0x48, 0x00, 0xae, 0x53,
+      0x28, 0xf8, 0xfe, 0x98, 0x6e, 0x88, 0xea, 0x6c,
+      0x9e, 0xb7, 0xbd, 0x05, 0x10, 0xc0, 0x08, 0x6f,
+      0x03, 0xf4, 0x26, 0xf3, 0xfb, 0xf7, 0x17, 0xc2,
+      0x1a, 0xad, 0x12, 0xad, 0xf3, 0x5c, 0xc5, 0xcd,
+      0xc7, 0xb5, 0xf9, 0xe7 ];
+
+#[cfg(all(feature = "ctr-drbg-192-bit-key", not(feature = "ctr-drbg-128-bit-key")))]
+const ENTROPY_SOURCE_NOPR: [u8; 60] = [
+      0x14, 0x3d, 0xdc, 0xd8, 0xe0, 0xe7, 0x61, 0x14,
+      0x9f, 0x4a, 0x2c, 0xa6, 0x5f, 0xac, 0x72, 0xab,
+      0x16, 0x7d, 0x9d, 0x9b, 0xb5, 0x80, 0x71, 0x65,
+      0xa9, 0x65, 0xf1, 0x89, 0x67, 0xec, 0x6b, 0x91,
+      0xc0, 0xb2, 0x97, 0x75, 0xc4, 0x78, 0xbe, 0x80,
+      0x53, 0x7d, 0x34, 0x0a, 0x02, 0xc9, 0x5a, 0xad,
+      0x38, 0x58, 0x25, 0x00, 0x25, 0x06, 0x69, 0x2e,
+      0xb8, 0x7d, 0x40, 0x28 ];
+
+#[cfg(all(feature = "ctr-drbg-192-bit-key", not(feature = "ctr-drbg-128-bit-key")))]
+const NONCE_PERS_PR: [u8; 24] = [
+      0x74, 0x0b, 0x99, 0x72, 0x16, 0xd3, 0x38, 0x03,
+      0x7e, 0x33, 0xea, 0xae, 0xf3, 0x1a, 0x8c, 0xd8,
+      0x62, 0xc8, 0x83, 0xfc, 0xdb, 0x8e, 0x14, 0x87 ];
+
+#[cfg(all(feature = "ctr-drbg-192-bit-key", not(feature = "ctr-drbg-128-bit-key")))]
+const NONCE_PERS_NOPR: [u8; 24] = [
+      0x93, 0xe4, 0x3f, 0xf6, 0xbe, 0x40, 0x56, 0x62,
+      0x25, 0x79, 0xa8, 0x59, 0xe5, 0x49, 0xf8, 0xd6,
+      0x3a, 0xa0, 0x98, 0x7e, 0x0d, 0x52, 0xc1, 0xd8 ];
+
+#[cfg(all(feature = "ctr-drbg-192-bit-key", not(feature = "ctr-drbg-128-bit-key")))]
+const RESULT_PR: [u8; 64] = [
+      0xc6, 0xb0, 0xb5, 0xe9, 0x76, 0x50, 0xb0, 0xec,
+      0x24, 0x35, 0x4f, 0x3b, 0xf0, 0x84, 0xd8, 0x54,
+      0x37, 0x9a, 0x27, 0x2d, 0x57, 0xab, 0x66, 0xfe,
+      0x0f, 0xeb, 0xbd, 0xa7, 0x46, 0x69, 0x44, 0x99,
+      0x7e, 0xb6, 0xf9, 0x5e, 0x40, 0x84, 0xd9, 0x60,
+      0x09, 0x43, 0x3f, 0x26, 0x05, 0x0b, 0xf0, 0x52,
+      0x2c, 0xe6, 0x22, 0xeb, 0xf0, 0x41, 0x30, 0x80,
+      0x2c, 0x61, 0x16, 0x57, 0x86, 0x50, 0x2a, 0x9c ];
+
+#[cfg(all(feature = "ctr-drbg-192-bit-key", not(feature = "ctr-drbg-128-bit-key")))]
+const RESULT_NOPR: [u8; 64] = [
+      0xd6, 0xec, 0x81, 0x1a, 0xf8, 0x88, 0x9a, 0x85,
+      0x00, 0xa7, 0xd6, 0x1f, 0x5b, 0x40, 0x40, 0x37,
+      0xa0, 0x99, 0xb9, 0xf1, 0xcd, 0x9c, 0x77, 0x81,
+      0xea, 0x9d, 0x6d, 0x1a, 0x85, 0x24, 0x1b, 0xed,
+      0x0a, 0x04, 0x97, 0x33, 0xfd, 0xd3, 0xb5, 0xe3,
+      0xe3, 0x6d, 0x3e, 0xf5, 0xea, 0x26, 0xb5, 0x39,
+      0xc5, 0x5f, 0xa7, 0xc1, 0x52, 0x97, 0xfb, 0x96,
+      0xa0, 0x9c, 0x24, 0x29, 0xb2, 0xc9, 0xbd, 0xad ];
+
+#[cfg(all(feature = "ctr-drbg-192-bit-key", not(feature = "ctr-drbg-128-bit-key")))]
+const RESULT_DF: [u8; 40] = [
+      0xcc, 0xba, 0x15, 0x7b, 0xe7, 0x68, 0x84, 0x3a,
+      0x0c, 0xb5, 0xed, 0x9e, 0xe2, 0xea, 0x29, 0x0a,
+      0x5a, 0x77, 0xa6, 0x27, 0xdf, 0xcd, 0xad, 0xc2,
+      0xf3, 0x07, 0x9b, 0x3f, 0x77, 0x75, 0xc4, 0x38,
+      0x94, 0x8e, 0x76, 0x50, 0x52, 0x93, 0xa3, 0xd1 ];
+
+#[cfg(all(feature = "ctr-drbg-192-bit-key", not(feature = "ctr-drbg-128-bit-key")))]
+const RESULT_UPDATE: [u8; 16] = [
+      0x09, 0x28, 0x20, 0x24, 0x02, 0x2d, 0x2b, 0xf1,
+      0x26, 0x76, 0xec, 0x33, 0xaa, 0x79, 0xc8, 0xb1 ];
+
+#[cfg(all(feature = "ctr-drbg-192-bit-key", not(feature = "ctr-drbg-128-bit-key")))]
+const RESULT_RESEED: [u8; 16] = [
+      0xad, 0x79, 0x2b, 0x02, 0xad, 0xbe, 0xb7, 0x04,
+      0x96, 0x57, 0x71, 0x38, 0x28, 0xe8, 0x4e, 0xf0 ];
+
+#[cfg(not(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key")))]
+const ENTROPY_SOURCE_PR: [u8; 96] = [
       0xc1, 0x80, 0x81, 0xa6, 0x5d, 0x44, 0x02, 0x16,
       0x19, 0xb3, 0xf1, 0x80, 0xb1, 0xc9, 0x20, 0x02,
       0x6a, 0x54, 0x6f, 0x0c, 0x70, 0x81, 0x49, 0x8b,
@@ -802,7 +1074,8 @@ pub const entropy_source_pr:[u8;96]=[
       0x93, 0x92, 0xcf, 0xc5, 0x23, 0x12, 0xd5, 0x56,
       0x2c, 0x4a, 0x6e, 0xff, 0xdc, 0x10, 0xd0, 0x68 ];
 
-pub const entropy_source_nopr:[u8;64]=[
+#[cfg(not(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key")))]
+const ENTROPY_SOURCE_NOPR: [u8; 64] = [
       0x5a, 0x19, 0x4d, 0x5e, 0x2b, 0x31, 0x58, 0x14,
       0x54, 0xde, 0xf6, 0x75, 0xfb, 0x79, 0x58, 0xfe,
       0xc7, 0xdb, 0x87, 0x3e, 0x56, 0x89, 0xfc, 0x9d,
@@ -812,106 +1085,218 @@ pub const entropy_source_nopr:[u8;64]=[
       0xf5, 0x98, 0x3d, 0x77, 0x1c, 0x1b, 0x13, 0x7e,
       0x4e, 0x0f, 0x9d, 0x8e, 0xf4, 0x09, 0xf9, 0x2e ];
 
-pub const nonce_pers_pr:[u8;16]=[
+#[cfg(not(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key")))]
+const NONCE_PERS_PR: [u8; 16] = [
       0xd2, 0x54, 0xfc, 0xff, 0x02, 0x1e, 0x69, 0xd2,
       0x29, 0xc9, 0xcf, 0xad, 0x85, 0xfa, 0x48, 0x6c ];
 
-pub const nonce_pers_nopr:[u8;16]=[
+#[cfg(not(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key")))]
+const NONCE_PERS_NOPR: [u8; 16] = [
       0x1b, 0x54, 0xb8, 0xff, 0x06, 0x42, 0xbf, 0xf5,
       0x21, 0xf1, 0x5c, 0x1c, 0x0b, 0x66, 0x5f, 0x3f ];
 
-//#else    // MBEDTLS_CTR_DRBG_USE_128_BIT_KEY
-pub const result_pr:[u8;16]=[
+#[cfg(not(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key")))]
+const RESULT_PR: [u8; 16] = [
       0x34, 0x01, 0x16, 0x56, 0xb4, 0x29, 0x00, 0x8f,
       0x35, 0x63, 0xec, 0xb5, 0xf2, 0x59, 0x07, 0x23 ];
 
-pub const result_nopr:[u8;16]=[
+#[cfg(not(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key")))]
+const RESULT_NOPR: [u8; 16] = [
       0xa0, 0x54, 0x30, 0x3d, 0x8a, 0x7e, 0xa9, 0x88,
       0x9d, 0x90, 0x3e, 0x07, 0x7c, 0x6f, 0x21, 0x8f ];
 
-// line 737 both static
-static test_offset: usize;
-pub fn ctr_drbg_self_test_entropy( data: Option<*mut c_void>, buf: &u8, len: usize ) -> i32{
-    let p:&u8 = data; //const unsigned char *p = data;
+#[cfg(not(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key")))]
+const RESULT_DF: [u8; 48] = [
+      0x2b, 0x87, 0x8f, 0xaf, 0x3d, 0x07, 0x7b, 0xfd,
+      0x80, 0x41, 0x8a, 0x32, 0xf0, 0xb2, 0x20, 0xfa,
+      0xd1, 0x76, 0x7a, 0x3f, 0xf3, 0x0e, 0xf6, 0x3c,
+      0xcd, 0xc5, 0x53, 0xc6, 0x61, 0x74, 0x4f, 0x4e,
+      0x6f, 0x9b, 0x0e, 0x47, 0x54, 0xcf, 0x8b, 0x5c,
+      0xe0, 0x0d, 0xe8, 0xca, 0xf2, 0x75, 0x53, 0x03 ];
+
+#[cfg(not(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key")))]
+const RESULT_UPDATE: [u8; 16] = [
+      0x4a, 0x9e, 0x73, 0x63, 0xb4, 0x85, 0xf7, 0xb9,
+      0x94, 0x69, 0xef, 0xcb, 0x77, 0x38, 0xeb, 0xe2 ];
+
+#[cfg(not(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key")))]
+const RESULT_RESEED: [u8; 16] = [
+      0xb9, 0xe8, 0xe1, 0x68, 0xa8, 0x47, 0xac, 0x1c,
+      0x12, 0xee, 0x84, 0xf0, 0xb1, 0x04, 0xac, 0x97 ];
+
+#[cfg(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key"))]
+const SELF_TEST_ENTROPY_LEN: usize = MBEDTLS_CTR_DRBG_KEYSIZE;
+#[cfg(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key"))]
+const SELF_TEST_NONCE_LEN: usize = MBEDTLS_CTR_DRBG_KEYSIZE / 2;
+#[cfg(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key"))]
+const SELF_TEST_OUTPUT_DISCARD_LENGTH: usize = 64;
+
+#[cfg(not(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key")))]
+const SELF_TEST_ENTROPY_LEN: usize = 32;
+#[cfg(not(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key")))]
+const SELF_TEST_NONCE_LEN: usize = 0;
+#[cfg(not(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key")))]
+const SELF_TEST_OUTPUT_DISCARD_LENGTH: usize = MBEDTLS_CTR_DRBG_BLOCKSIZE;
+
+/*
+ * Replays a fixed entropy buffer, one request after the other.
+ */
+fn ctr_drbg_self_test_entropy(data: &'static [u8]) -> EntropyCallback<'static> {
+    let mut test_offset = 0;
+    Box::new(move |buf: &mut [u8]| {
+        buf.copy_from_slice(&data[test_offset..test_offset + buf.len()]);
+        test_offset += buf.len();
+        Ok(())
+    })
+}
+
+/*
+ * Runs one known-answer test. A failing call leaves the actual output
+ * empty and records its error.
+ */
+fn self_test_case<F>(name: &'static str, expected: &[u8], run: F) -> SelfTestCase
+    where F: FnOnce(&mut [u8]) -> Result<(), Error>
+{
+    let mut actual = vec![0u8; expected.len()];
+    let error = run(&mut actual).err();
 
-    // memcpy( buf, p + test_offset, len );
+    if error.is_some() {
+        actual.clear();
+    }
 
-    test_offset += len;
-    return 0 ;
+    SelfTestCase {
+        name,
+        expected: expected.to_vec(),
+        actual,
+        error,
+    }
 }
 
+/*
+ * Seeds a fresh context the way the PR = FALSE test does
+ */
+fn self_test_seed_nopr(ctx: &mut CtrDrbg<'_>) -> Result<(), Error> {
+    ctx.set_entropy_len(SELF_TEST_ENTROPY_LEN);
+    ctx.set_nonce_len(SELF_TEST_NONCE_LEN)?;
+    ctx.seed(ctr_drbg_self_test_entropy(&ENTROPY_SOURCE_NOPR), &NONCE_PERS_NOPR)
+}
+
+impl SelfTestCase {
+    /// This function tells whether the test produced the expected output.
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.actual == self.expected
+    }
+}
+
+impl SelfTestReport {
+    /// This function tells whether every known-answer test passed.
+    pub fn passed(&self) -> bool {
+        self.cases.iter().all(SelfTestCase::passed)
+    }
+}
+
+impl fmt::Display for SelfTestReport {
+    /// This function prints one line per test in the layout of the
+    /// verbose C checkup routine, with the bytes of the failed ones.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for case in &self.cases {
+            let name = format!("({})", case.name);
+            if case.passed() {
+                writeln!(f, "  CTR_DRBG {:<12}: passed", name)?;
+                continue;
+            }
 
-//line 747
-pub fn CHK(c: i32) -> i32 {
-    if(c != 0) {
-        if verbose != 0 {
-            println!( "failed\n" );
+            writeln!(f, "  CTR_DRBG {:<12}: failed", name)?;
+            if let Some(err) = case.error {
+                writeln!(f, "    error:    {}", err)?;
+            }
+            writeln!(f, "    expected: {}", hex(&case.expected))?;
+            writeln!(f, "    actual:   {}", hex(&case.actual))?;
         }
-        return 1;
+
+        Ok(())
     }
 }
 
+fn hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 /*
  * Checkup routine
  */
 
-// line 757
-// The CTR_DRBG checkup routine. Returns 0 on success and 1 on failure.
-pub fn mbedtls_ctr_drbg_self_test( verbose: i32 ) -> i32 {
-    let mut ctx: mbedtls_ctr_drbg_context;
-    let mut buf: [u8; 16];
-
-    mbedtls_ctr_drbg_init( &ctx );
+/// The CTR_DRBG checkup routine. It runs every known-answer test, even
+/// after a failure, and reports the expected and actual output of each.
+///
+/// Each test uses its own context and entropy buffer, so the routine is
+/// reentrant and may run while other threads use their generators.
+pub fn self_test() -> SelfTestReport {
+    let mut report = SelfTestReport::default();
 
     /*
      * Based on a NIST CTR_DRBG test vector (PR = True)
      */
-    if verbose != 0 {
-        mbedtls_printf( "  CTR_DRBG (PR = TRUE) : " );
-    }
-
-    test_offset = 0;
-    mbedtls_ctr_drbg_set_entropy_len( &ctx, 32 );
-    mbedtls_ctr_drbg_set_nonce_len( &ctx, 0 );
-    CHK( mbedtls_ctr_drbg_seed( &ctx, ctr_drbg_self_test_entropy, entropy_source_pr: Option<*mut c_void>, nonce_pers_pr, 16 ) );
-    mbedtls_ctr_drbg_set_prediction_resistance( &ctx, MBEDTLS_CTR_DRBG_PR_ON );
-    CHK( mbedtls_ctr_drbg_random( &ctx, buf, MBEDTLS_CTR_DRBG_BLOCKSIZE ) );
-    CHK( mbedtls_ctr_drbg_random( &ctx, buf, MBEDTLS_CTR_DRBG_BLOCKSIZE ) );
-    CHK( memcmp( buf, result_pr, MBEDTLS_CTR_DRBG_BLOCKSIZE ) );
+    report.cases.push(self_test_case("PR = TRUE", &RESULT_PR, |buf| {
+        let mut ctx = CtrDrbg::new();
+        let mut discard = [0u8; SELF_TEST_OUTPUT_DISCARD_LENGTH];
+
+        ctx.set_entropy_len(SELF_TEST_ENTROPY_LEN);
+        ctx.set_nonce_len(SELF_TEST_NONCE_LEN)?;
+        ctx.seed(ctr_drbg_self_test_entropy(&ENTROPY_SOURCE_PR), &NONCE_PERS_PR)?;
+        ctx.set_prediction_resistance(MBEDTLS_CTR_DRBG_PR_ON);
+        ctx.generate(&mut discard)?;
+        ctx.generate(buf)
+    }));
 
-    mbedtls_ctr_drbg_free( &ctx );
-
-    if verbose != 0 {
-        mbedtls_printf( "passed\n" );
-    }
     /*
      * Based on a NIST CTR_DRBG test vector (PR = FALSE)
      */
-    if verbose != 0 {
-        mbedtls_printf( "  CTR_DRBG (PR = FALSE): " );
-    }
+    report.cases.push(self_test_case("PR = FALSE", &RESULT_NOPR, |buf| {
+        let mut ctx = CtrDrbg::new();
+        let mut discard = [0u8; SELF_TEST_OUTPUT_DISCARD_LENGTH];
+
+        self_test_seed_nopr(&mut ctx)?;
+        #[cfg(not(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key")))]
+        ctx.generate(&mut discard)?;
+        ctx.reseed(&[])?;
+        #[cfg(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key"))]
+        ctx.generate(&mut discard)?;
+        ctx.generate(buf)
+    }));
 
-    mbedtls_ctr_drbg_init( &ctx );
+    /*
+     * Derivation function
+     */
+    report.cases.push(self_test_case("df", &RESULT_DF, |buf| {
+        let mut output = Zeroizing::new([0u8; MBEDTLS_CTR_DRBG_SEEDLEN]);
 
-    test_offset = 0;
-    mbedtls_ctr_drbg_set_entropy_len( &ctx, 32 );
-    mbedtls_ctr_drbg_set_nonce_len( &ctx, 0 );
-    CHK( mbedtls_ctr_drbg_seed( &ctx, ctr_drbg_self_test_entropy, entropy_source_nopr: Option<*mut c_void>, nonce_pers_nopr, 16 ) );
-    CHK( mbedtls_ctr_drbg_random( &ctx, buf, 16 ) );
-    CHK( mbedtls_ctr_drbg_reseed( &ctx, NULL, 0 ) );
-    CHK( mbedtls_ctr_drbg_random( &ctx, buf, 16 ) );
-    CHK( memcmp( buf, result_nopr, 16 ) );
+        block_cipher_df(&mut output, &ENTROPY_SOURCE_NOPR)?;
+        buf.copy_from_slice(&output[..]);
+        Ok(())
+    }));
 
-    mbedtls_ctr_drbg_free( &ctx );
+    /*
+     * Update with additional input
+     */
+    report.cases.push(self_test_case("update", &RESULT_UPDATE, |buf| {
+        let mut ctx = CtrDrbg::new();
 
-    if verbose != 0 {
-        mbedtls_printf( "passed\n" );
-    }
+        self_test_seed_nopr(&mut ctx)?;
+        ctx.update(&NONCE_PERS_PR)?;
+        ctx.generate(buf)
+    }));
 
-    if verbose != 0 {
-            mbedtls_printf( "\n" );
-    }
+    /*
+     * Reseed with additional input
+     */
+    report.cases.push(self_test_case("reseed", &RESULT_RESEED, |buf| {
+        let mut ctx = CtrDrbg::new();
 
-    return 0 ;
-}
+        self_test_seed_nopr(&mut ctx)?;
+        ctx.reseed(&NONCE_PERS_PR)?;
+        ctx.generate(buf)
+    }));
 
+    report
+}
@@ -0,0 +1,310 @@
+use crate::rng::header::ctr_drbg::EntropyCallback; // The entropy callback.
+use crate::rng::header::ctr_drbg::EntropySource; // A source of entropy for seeding a DRBG.
+use crate::rng::header::error::Error;
+use crate::rng::header::sha512::Sha512Context;
+use crate::rng::header::sha512::MBEDTLS_SHA512_OUTPUT_SIZE; // The length of a SHA-512 checksum, in bytes.
+
+use crate::rng::header::entropy::MBEDTLS_ENTROPY_MAX_SOURCES; // Maximum number of sources supported.
+use crate::rng::header::entropy::MBEDTLS_ENTROPY_MAX_GATHER; // Maximum amount requested from entropy sources.
+use crate::rng::header::entropy::MBEDTLS_ENTROPY_BLOCK_SIZE; // Block size of entropy accumulator (SHA-512).
+use crate::rng::header::entropy::MBEDTLS_ENTROPY_SOURCE_MANUAL; // Source id of update_manual().
+use crate::rng::header::entropy::MBEDTLS_ENTROPY_SOURCE_STRONG; // Entropy source is strong.
+use crate::rng::header::entropy::MBEDTLS_ENTROPY_SOURCE_WEAK; // Entropy source is weak.
+use crate::rng::header::entropy::EntropyContext; // Entropy context structure.
+use crate::rng::header::entropy::EntropyPollCallback; // Entropy poll callback.
+use crate::rng::header::entropy::EntropySourceState; // Entropy source state.
+
+use crate::rng::header::platform_util::Zeroizing; // A buffer wiped when it goes out of scope.
+
+use crate::rng::sha512::sha512;
+
+const ENTROPY_MAX_LOOP: usize = 256; /* Maximum number of gathering loops in func(). */
+
+impl EntropyContext {
+    /// This function initializes the context. It starts without any
+    /// source; add them with add_source().
+    pub fn new() -> Self {
+        EntropyContext {
+            accumulator_started: false,
+            accumulator: Sha512Context::new(),
+            source: Vec::with_capacity(MBEDTLS_ENTROPY_MAX_SOURCES),
+        }
+    }
+
+    /// This function adds an entropy source to poll.
+    ///
+    /// `threshold` is the minimum number of bytes the source must deliver
+    /// before func() releases any output, and `strong` tells whether the
+    /// source counts towards the MBEDTLS_ENTROPY_BLOCK_SIZE bytes of strong
+    /// entropy required per call (MBEDTLS_ENTROPY_SOURCE_STRONG or
+    /// MBEDTLS_ENTROPY_SOURCE_WEAK).
+    pub fn add_source(&mut self, f_source: EntropyPollCallback, threshold: usize, strong: bool) -> Result<(), Error> {
+        if self.source.len() >= MBEDTLS_ENTROPY_MAX_SOURCES {
+            return Err(Error::EntropyMaxSources);
+        }
+
+        self.source.push(EntropySourceState {
+            f_source,
+            size: 0,
+            threshold,
+            strong,
+        });
+
+        Ok(())
+    }
+
+    /*
+     * Entropy accumulator update
+     */
+    fn update(&mut self, source_id: u8, data: &[u8]) {
+        let mut tmp = Zeroizing::new([0u8; MBEDTLS_ENTROPY_BLOCK_SIZE]);
+        let mut p = data;
+
+        if p.len() > MBEDTLS_ENTROPY_BLOCK_SIZE {
+            sha512(data, &mut tmp, false);
+            p = &tmp[..];
+        }
+
+        let header = [source_id, p.len() as u8];
+
+        /*
+         * Start the accumulator if this has not already happened. Note that
+         * it is sufficient to start the accumulator here only because all calls to
+         * gather entropy eventually execute this code.
+         */
+        if !self.accumulator_started {
+            self.accumulator.starts(false);
+            self.accumulator_started = true;
+        }
+        self.accumulator.update(&header);
+        self.accumulator.update(p);
+    }
+
+    /// This function adds data to the accumulator manually.
+    pub fn update_manual(&mut self, data: &[u8]) -> Result<(), Error> {
+        self.update(MBEDTLS_ENTROPY_SOURCE_MANUAL as u8, data);
+        Ok(())
+    }
+
+    /// This function triggers an extra gather poll for the accumulator.
+    pub fn gather(&mut self) -> Result<(), Error> {
+        let mut buf = Zeroizing::new([0u8; MBEDTLS_ENTROPY_MAX_GATHER]);
+        self.gather_internal(&mut buf)
+    }
+
+    /*
+     * Run through the different sources to add entropy to our accumulator
+     */
+    fn gather_internal(&mut self, buf: &mut [u8; MBEDTLS_ENTROPY_MAX_GATHER]) -> Result<(), Error> {
+        let mut have_one_strong = false;
+
+        if self.source.is_empty() {
+            return Err(Error::EntropyNoSourcesDefined);
+        }
+
+        /*
+         * Run through our entropy sources
+         */
+        for i in 0..self.source.len() {
+            if self.source[i].strong == MBEDTLS_ENTROPY_SOURCE_STRONG {
+                have_one_strong = true;
+            }
+
+            let olen = (self.source[i].f_source)(&mut buf[..])?.min(MBEDTLS_ENTROPY_MAX_GATHER);
+
+            /*
+             * Add if we actually gathered something
+             */
+            if olen > 0 {
+                self.update(i as u8, &buf[..olen]);
+                self.source[i].size += olen;
+            }
+        }
+
+        if !have_one_strong {
+            return Err(Error::EntropyNoStrongSource);
+        }
+
+        Ok(())
+    }
+
+    /// This function retrieves entropy from the accumulator, at most
+    /// MBEDTLS_ENTROPY_BLOCK_SIZE bytes. It is the counterpart of
+    /// mbedtls_entropy_func().
+    pub fn func(&mut self, output: &mut [u8]) -> Result<(), Error> {
+        let mut buf = Zeroizing::new([0u8; MBEDTLS_SHA512_OUTPUT_SIZE]);
+        let mut gather_buf = Zeroizing::new([0u8; MBEDTLS_ENTROPY_MAX_GATHER]);
+        let mut count = 0;
+
+        if output.len() > MBEDTLS_ENTROPY_BLOCK_SIZE {
+            return Err(Error::EntropySourceFailed);
+        }
+
+        /*
+         * Always gather extra entropy before a call
+         */
+        loop {
+            count += 1;
+            if count > ENTROPY_MAX_LOOP {
+                return Err(Error::EntropySourceFailed);
+            }
+
+            self.gather_internal(&mut gather_buf)?;
+
+            let thresholds_reached = self.source.iter().all(|s| s.size >= s.threshold);
+            let strong_size: usize = self.source.iter()
+                .filter(|s| s.strong == MBEDTLS_ENTROPY_SOURCE_STRONG)
+                .map(|s| s.size)
+                .sum();
+
+            if thresholds_reached && strong_size >= MBEDTLS_ENTROPY_BLOCK_SIZE {
+                break;
+            }
+        }
+
+        /*
+         * Note that at this stage it is assumed that the accumulator was started
+         * in a previous call to update(). If this is not guaranteed, the
+         * code below will fail.
+         */
+        self.accumulator.finish(&mut buf);
+
+        /*
+         * Reset accumulator and counters and recycle existing entropy
+         */
+        self.accumulator = Sha512Context::new();
+        self.accumulator.starts(false);
+        self.accumulator.update(&buf[..]);
+
+        /*
+         * Perform second SHA-512 on entropy
+         */
+        let input = Zeroizing::new(*buf);
+        sha512(&input[..], &mut buf, false);
+
+        for source in self.source.iter_mut() {
+            source.size = 0;
+        }
+
+        output.copy_from_slice(&buf[..output.len()]);
+        Ok(())
+    }
+
+    /// This function turns the accumulator into the entropy callback of a
+    /// CTR_DRBG, the way mbedtls_entropy_func() and its context are passed
+    /// to mbedtls_ctr_drbg_seed() in C.
+    pub fn into_f_entropy(self) -> EntropyCallback<'static> {
+        Box::new(self)
+    }
+}
+
+impl EntropySource for EntropyContext {
+    /// This function fills the buffer from the accumulator, one
+    /// MBEDTLS_ENTROPY_BLOCK_SIZE block at a time.
+    fn fill(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        for chunk in buf.chunks_mut(MBEDTLS_ENTROPY_BLOCK_SIZE) {
+            self.func(chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for EntropyContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/*
+ * Dummy source function
+ */
+fn entropy_dummy_source(output: &mut [u8]) -> Result<usize, Error> {
+    output.fill(0x2a);
+    Ok(output.len())
+}
+
+/*
+ * Deterministic strong source standing in for a real one, so the
+ * checkup routine does not depend on the platform.
+ */
+fn entropy_counter_source() -> EntropyPollCallback {
+    let mut counter: u8 = 0;
+    Box::new(move |output: &mut [u8]| {
+        for b in output.iter_mut() {
+            counter = counter.wrapping_add(1);
+            *b = counter;
+        }
+        Ok(output.len())
+    })
+}
+
+/*
+ * The actual entropy checkup routine
+ */
+fn entropy_self_test_run() -> Result<(), Error> {
+    let mut ctx = EntropyContext::new();
+    let mut buf = [0u8; MBEDTLS_ENTROPY_BLOCK_SIZE];
+    let mut acc = [0u8; MBEDTLS_ENTROPY_BLOCK_SIZE];
+
+    /* Gathering without any source must fail */
+    if ctx.gather() != Err(Error::EntropyNoSourcesDefined) {
+        return Err(Error::EntropySourceFailed);
+    }
+
+    ctx.add_source(Box::new(entropy_dummy_source), 16, MBEDTLS_ENTROPY_SOURCE_WEAK)?;
+
+    /* A weak source alone is not enough */
+    if ctx.gather() != Err(Error::EntropyNoStrongSource) {
+        return Err(Error::EntropySourceFailed);
+    }
+
+    ctx.add_source(entropy_counter_source(), 32, MBEDTLS_ENTROPY_SOURCE_STRONG)?;
+    ctx.gather()?;
+    ctx.update_manual(&buf)?;
+
+    /*
+     * To test that func writes correct number of bytes:
+     * - collect entropy 8 times and OR the result in an accumulator:
+     *   any byte should then be 0 with probably 2^(-64), so requiring
+     *   each of the 64 bytes to be non-zero has a false failure rate
+     *   of at most 2^(-58) which is acceptable.
+     */
+    for _ in 0..8 {
+        ctx.func(&mut buf)?;
+
+        for (a, b) in acc.iter_mut().zip(buf.iter()) {
+            *a |= *b;
+        }
+    }
+
+    if acc.contains(&0) {
+        return Err(Error::EntropySourceFailed);
+    }
+
+    Ok(())
+}
+
+/*
+ * Checkup routine
+ */
+
+/// The entropy checkup routine. Returns 0 on success and 1 on failure.
+pub fn self_test(verbose: bool) -> i32 {
+    if verbose {
+        print!("  ENTROPY test: ");
+    }
+
+    let ret = entropy_self_test_run();
+
+    if verbose {
+        if ret.is_err() {
+            println!("failed");
+        } else {
+            println!("passed");
+        }
+
+        println!();
+    }
+
+    ret.is_err() as i32
+}
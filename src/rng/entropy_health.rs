@@ -0,0 +1,175 @@
+use crate::rng::header::ctr_drbg::EntropySource; // A source of entropy for seeding a DRBG.
+use crate::rng::header::error::Error;
+
+use crate::rng::header::entropy_health::MBEDTLS_ENTROPY_HEALTH_ALPHA_LOG2; // The false positive probability, as -log2(alpha).
+use crate::rng::header::entropy_health::MBEDTLS_ENTROPY_HEALTH_APT_WINDOW; // The APT window size for non-binary samples.
+use crate::rng::header::entropy_health::HealthTestConfig; // The cutoffs of the continuous health tests.
+use crate::rng::header::entropy_health::HealthTest; // One of the continuous health tests.
+use crate::rng::header::entropy_health::HealthTestedSource; // An entropy source under continuous health tests.
+
+use crate::rng::platform_util::platform_zeroize;
+
+impl HealthTestConfig {
+    /// This function derives the cutoffs of both tests from the
+    /// min-entropy claimed per sample, in bits, with a false positive
+    /// probability of 2^-20 for each test (SP 800-90B &sect;4.4).
+    ///
+    /// The claim must be greater than 0 and at most 8; other values are
+    /// rejected with Error::CtrDrbgEntropySourceFailed, the error code
+    /// that's closest to making sense.
+    pub fn from_min_entropy(min_entropy: f64) -> Result<Self, Error> {
+        if !(min_entropy > 0.0 && min_entropy <= 8.0) {
+            return Err(Error::CtrDrbgEntropySourceFailed);
+        }
+
+        /* C = 1 + ceil(-log2(alpha) / H) */
+        let rct_cutoff = 1 + (MBEDTLS_ENTROPY_HEALTH_ALPHA_LOG2 as f64 / min_entropy).ceil() as u32;
+
+        Ok(HealthTestConfig {
+            rct_cutoff,
+            apt_cutoff: apt_cutoff(min_entropy, MBEDTLS_ENTROPY_HEALTH_APT_WINDOW),
+            apt_window: MBEDTLS_ENTROPY_HEALTH_APT_WINDOW,
+        })
+    }
+}
+
+/*
+ * C = 1 + CRITBINOM(W, 2^-H, 1 - alpha): one more than the smallest k
+ * such that a sample of probability 2^-H occurs at most k times in a
+ * window of W samples with probability at least 1 - alpha.
+ *
+ * The binomial probabilities are computed in log space, since
+ * (1 - p)^W underflows for low claims.
+ */
+fn apt_cutoff(min_entropy: f64, window: u32) -> u32 {
+    let p = (-min_entropy).exp2();
+    let alpha = (-(MBEDTLS_ENTROPY_HEALTH_ALPHA_LOG2 as f64)).exp2();
+    let w = window as f64;
+    let mut ln_pmf = w * (-p).ln_1p();
+    let mut cdf = 0.0;
+
+    for k in 0..window {
+        cdf += ln_pmf.exp();
+        if cdf >= 1.0 - alpha {
+            return k + 1;
+        }
+        ln_pmf += ((w - k as f64) / (k as f64 + 1.0)).ln() + (p / (1.0 - p)).ln();
+    }
+
+    window
+}
+
+impl<E: EntropySource> HealthTestedSource<E> {
+    /// This function wraps an entropy source in the continuous health
+    /// tests with the given cutoffs.
+    pub fn new(source: E, config: HealthTestConfig) -> Self {
+        HealthTestedSource {
+            source,
+            config,
+            started: false,
+            rct_sample: 0,
+            rct_count: 0,
+            apt_sample: 0,
+            apt_count: 0,
+            apt_seen: 0,
+            failure: None,
+        }
+    }
+
+    /// This function returns the test that failed, if any.
+    pub fn failure(&self) -> Option<HealthTest> {
+        self.failure
+    }
+
+    /// This function clears a failure and restarts both tests, once the
+    /// cause of the failure has been dealt with.
+    pub fn reset(&mut self) {
+        self.started = false;
+        self.rct_count = 0;
+        self.apt_count = 0;
+        self.apt_seen = 0;
+        self.failure = None;
+    }
+
+    /// This function returns the wrapped entropy source.
+    pub fn into_inner(self) -> E {
+        self.source
+    }
+
+    /*
+     * Runs both tests on one sample
+     */
+    fn test_sample(&mut self, sample: u8) -> Result<(), HealthTest> {
+        if !self.started {
+            self.started = true;
+            self.rct_sample = sample;
+            self.rct_count = 1;
+            self.apt_sample = sample;
+            self.apt_count = 1;
+            self.apt_seen = 1;
+            return Ok(());
+        }
+
+        /*
+         * Repetition Count Test
+         */
+        if sample == self.rct_sample {
+            self.rct_count += 1;
+            if self.rct_count >= self.config.rct_cutoff {
+                return Err(HealthTest::RepetitionCount);
+            }
+        } else {
+            self.rct_sample = sample;
+            self.rct_count = 1;
+        }
+
+        /*
+         * Adaptive Proportion Test
+         */
+        if self.apt_seen >= self.config.apt_window {
+            self.apt_sample = sample;
+            self.apt_count = 1;
+            self.apt_seen = 1;
+            return Ok(());
+        }
+
+        self.apt_seen += 1;
+        if sample == self.apt_sample {
+            self.apt_count += 1;
+            if self.apt_count >= self.config.apt_cutoff {
+                return Err(HealthTest::AdaptiveProportion);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<E: EntropySource> EntropySource for HealthTestedSource<E> {
+    /// This function fills the buffer from the wrapped source and runs
+    /// the health tests on every byte. On failure the buffer is wiped.
+    fn fill(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        if self.failure.is_some() {
+            return Err(Error::CtrDrbgEntropySourceFailed);
+        }
+
+        if self.source.fill(buf).is_err() {
+            platform_zeroize(buf);
+            return Err(Error::CtrDrbgEntropySourceFailed);
+        }
+
+        for &sample in buf.iter() {
+            if let Err(test) = self.test_sample(sample) {
+                self.failure = Some(test);
+                break;
+            }
+        }
+
+        if self.failure.is_some() {
+            platform_zeroize(buf);
+            return Err(Error::CtrDrbgEntropySourceFailed);
+        }
+
+        Ok(())
+    }
+}
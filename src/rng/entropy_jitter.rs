@@ -0,0 +1,139 @@
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use crate::rng::header::ctr_drbg::EntropySource; // A source of entropy for seeding a DRBG.
+use crate::rng::header::error::Error;
+use crate::rng::header::sha512::MBEDTLS_SHA512_OUTPUT_SIZE; // The length of a SHA-512 checksum, in bytes.
+
+use crate::rng::header::entropy::EntropyPollCallback; // Entropy poll callback.
+use crate::rng::header::entropy_health::HealthTestConfig; // The cutoffs of the continuous health tests.
+use crate::rng::header::entropy_health::HealthTestedSource; // An entropy source under continuous health tests.
+use crate::rng::header::entropy_jitter::MBEDTLS_JITTER_SAMPLE_MIN_ENTROPY; // The min-entropy credited to one raw sample.
+use crate::rng::header::entropy_jitter::MBEDTLS_JITTER_SAMPLES_PER_BLOCK; // The raw samples per output block.
+use crate::rng::header::entropy_jitter::MBEDTLS_JITTER_MEMORY_SIZE; // The size of the memory of the noise loop.
+use crate::rng::header::entropy_jitter::JitterClock; // The timer of the noise loop.
+use crate::rng::header::entropy_jitter::JitterEntropy; // The CPU-jitter entropy source.
+use crate::rng::header::entropy_jitter::JitterNoise; // The raw noise source.
+
+use crate::rng::header::platform_util::Zeroizing; // A buffer wiped when it goes out of scope.
+
+use crate::rng::platform_util::platform_zeroize;
+use crate::rng::sha512::sha512;
+
+/* Stride of the memory walk: a prime just above the cache line size. */
+const JITTER_MEMORY_STRIDE: usize = 67;
+/* The noise loop runs between 64 and 127 rounds, picked by the previous timing. */
+const JITTER_MIN_ROUNDS: usize = 64;
+
+/*
+ * The default timer: nanoseconds of the monotonic clock since its first use
+ */
+fn monotonic_clock() -> u64 {
+    static START: OnceLock<Instant> = OnceLock::new();
+
+    START.get_or_init(Instant::now).elapsed().as_nanos() as u64
+}
+
+impl JitterNoise {
+    fn new(clock: JitterClock) -> Self {
+        JitterNoise {
+            memory: vec![0; MBEDTLS_JITTER_MEMORY_SIZE],
+            position: 0,
+            last_delta: 0,
+            clock,
+        }
+    }
+
+    /*
+     * Times one pass of the memory-access and compute loop, and folds
+     * the duration into one byte
+     */
+    fn sample(&mut self) -> u8 {
+        let rounds = JITTER_MIN_ROUNDS + (self.last_delta as usize % JITTER_MIN_ROUNDS);
+        let mut acc = self.last_delta;
+        let start = (self.clock)();
+
+        for _ in 0..rounds {
+            self.position = (self.position + JITTER_MEMORY_STRIDE) % MBEDTLS_JITTER_MEMORY_SIZE;
+            let v = self.memory[self.position].wrapping_add(acc as u8);
+            self.memory[self.position] = v;
+            acc = acc.rotate_left(7) ^ v as u64;
+        }
+
+        let delta = (self.clock)().wrapping_sub(start);
+
+        /* Keep the result of the compute loop alive. */
+        self.memory[self.position] ^= acc as u8;
+        self.last_delta = delta;
+
+        delta.to_le_bytes().iter().fold(0, |folded, b| folded ^ b)
+    }
+}
+
+impl EntropySource for JitterNoise {
+    /// This function fills the buffer with raw timing samples.
+    fn fill(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        for b in buf.iter_mut() {
+            *b = self.sample();
+        }
+        Ok(())
+    }
+}
+
+impl JitterEntropy {
+    /// This function sets up the jitter source and runs its start-up
+    /// test: MBEDTLS_JITTER_SAMPLES_PER_BLOCK raw samples go through the
+    /// health tests and are discarded (SP 800-90B &sect;4.3). A timer too
+    /// coarse or too regular to provide entropy fails with
+    /// Error::EntropySourceFailed.
+    pub fn new() -> Result<Self, Error> {
+        Self::with_clock(monotonic_clock)
+    }
+
+    /// This function sets up the jitter source like new(), timed with
+    /// `clock` instead of the monotonic clock: a cycle counter where the
+    /// platform has one, or a controlled timer in tests.
+    pub fn with_clock(clock: JitterClock) -> Result<Self, Error> {
+        let config = HealthTestConfig::from_min_entropy(MBEDTLS_JITTER_SAMPLE_MIN_ENTROPY)?;
+        let mut jitter = JitterEntropy {
+            noise: HealthTestedSource::new(JitterNoise::new(clock), config),
+        };
+        let mut startup = Zeroizing::new([0u8; MBEDTLS_JITTER_SAMPLES_PER_BLOCK]);
+
+        jitter.noise.fill(&mut startup[..]).map_err(|_| Error::EntropySourceFailed)?;
+
+        Ok(jitter)
+    }
+
+    /// This function turns the source into an entropy poll callback, to be
+    /// registered in an entropy accumulator, typically as a weak source
+    /// with a threshold of MBEDTLS_ENTROPY_MIN_JITTER.
+    pub fn into_poll_callback(mut self) -> EntropyPollCallback {
+        Box::new(move |output: &mut [u8]| {
+            self.fill(output).map_err(|_| Error::EntropySourceFailed)?;
+            Ok(output.len())
+        })
+    }
+}
+
+impl EntropySource for JitterEntropy {
+    /// This function fills the buffer one 64-byte block at a time, each
+    /// the SHA-512 of MBEDTLS_JITTER_SAMPLES_PER_BLOCK health-tested raw
+    /// samples. On failure the whole buffer is wiped, blocks already
+    /// filled included.
+    fn fill(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        let mut raw = Zeroizing::new([0u8; MBEDTLS_JITTER_SAMPLES_PER_BLOCK]);
+        let mut block = Zeroizing::new([0u8; MBEDTLS_SHA512_OUTPUT_SIZE]);
+
+        for chunk in buf.chunks_mut(MBEDTLS_SHA512_OUTPUT_SIZE) {
+            if let Err(err) = self.noise.fill(&mut raw[..]) {
+                platform_zeroize(buf);
+                return Err(err);
+            }
+            sha512(&raw[..], &mut block, false);
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+
+        Ok(())
+    }
+}
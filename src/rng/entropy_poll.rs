@@ -0,0 +1,111 @@
+use std::fs::File;
+use std::io::{self, ErrorKind, Read};
+
+use crate::rng::header::ctr_drbg::EntropySource; // A source of entropy for seeding a DRBG.
+use crate::rng::header::error::Error;
+
+use crate::rng::header::entropy_poll::PlatformEntropy; // The platform entropy source.
+
+/*
+ * getrandom(2) is reached through syscall(2) rather than the libc
+ * wrapper, so the crate still links against a libc that predates it and
+ * an old kernel simply answers ENOSYS.
+ */
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+const SYS_GETRANDOM: std::os::raw::c_long = 318;
+#[cfg(all(target_os = "linux", target_arch = "x86"))]
+const SYS_GETRANDOM: std::os::raw::c_long = 355;
+#[cfg(all(target_os = "linux", any(target_arch = "aarch64", target_arch = "riscv64")))]
+const SYS_GETRANDOM: std::os::raw::c_long = 278;
+#[cfg(all(target_os = "linux", target_arch = "arm"))]
+const SYS_GETRANDOM: std::os::raw::c_long = 384;
+#[cfg(all(target_os = "linux", target_arch = "powerpc64"))]
+const SYS_GETRANDOM: std::os::raw::c_long = 359;
+#[cfg(all(target_os = "linux", target_arch = "s390x"))]
+const SYS_GETRANDOM: std::os::raw::c_long = 349;
+
+#[cfg(all(target_os = "linux", any(target_arch = "x86_64", target_arch = "x86",
+                                   target_arch = "aarch64", target_arch = "riscv64",
+                                   target_arch = "arm", target_arch = "powerpc64",
+                                   target_arch = "s390x")))]
+fn getrandom_wrapper(buf: &mut [u8]) -> io::Result<usize> {
+    use std::os::raw::{c_long, c_uint};
+
+    extern "C" {
+        fn syscall(number: c_long, ...) -> c_long;
+    }
+
+    /* flags = 0: read from the urandom pool, blocking only until it has
+     * been initialised once after boot. */
+    let ret = unsafe { syscall(SYS_GETRANDOM, buf.as_mut_ptr(), buf.len(), 0 as c_uint) };
+    if ret < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(ret as usize)
+}
+
+#[cfg(not(all(target_os = "linux", any(target_arch = "x86_64", target_arch = "x86",
+                                       target_arch = "aarch64", target_arch = "riscv64",
+                                       target_arch = "arm", target_arch = "powerpc64",
+                                       target_arch = "s390x"))))]
+fn getrandom_wrapper(_buf: &mut [u8]) -> io::Result<usize> {
+    Err(io::Error::from(ErrorKind::Unsupported))
+}
+
+/*
+ * Read the whole buffer from /dev/urandom
+ */
+fn urandom_poll(output: &mut [u8]) -> Result<usize, Error> {
+    let mut file = File::open("/dev/urandom").map_err(|_| Error::EntropySourceFailed)?;
+    let mut n = 0;
+
+    while n < output.len() {
+        match file.read(&mut output[n..]) {
+            Ok(0) => return Err(Error::EntropySourceFailed),
+            Ok(k) => n += k,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(_) => return Err(Error::EntropySourceFailed),
+        }
+    }
+
+    Ok(n)
+}
+
+/// This function polls the platform entropy source, the counterpart of
+/// mbedtls_platform_entropy_poll(). It returns how many bytes were
+/// written, which may be fewer than requested.
+///
+/// It has the shape of an EntropyPollCallback, so it can be registered
+/// in an entropy accumulator with a threshold of
+/// MBEDTLS_ENTROPY_MIN_PLATFORM as a strong source.
+pub fn platform_entropy_poll(output: &mut [u8]) -> Result<usize, Error> {
+    loop {
+        match getrandom_wrapper(output) {
+            Ok(n) => return Ok(n),
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            /* Kernel without getrandom(2), or a seccomp filter denying it. */
+            Err(e) if e.kind() == ErrorKind::Unsupported
+                   || e.kind() == ErrorKind::PermissionDenied => break,
+            Err(_) => return Err(Error::EntropySourceFailed),
+        }
+    }
+
+    urandom_poll(output)
+}
+
+impl EntropySource for PlatformEntropy {
+    /// This function fills the whole buffer from the platform source,
+    /// polling again after short reads.
+    fn fill(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        let mut n = 0;
+
+        while n < buf.len() {
+            match platform_entropy_poll(&mut buf[n..]) {
+                Ok(0) | Err(_) => return Err(Error::CtrDrbgEntropySourceFailed),
+                Ok(k) => n += k,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use std::fmt;
+use std::io;
+
+use crate::rng::header::error::Error;
+use crate::rng::header::error::MBEDTLS_ERR_ERROR_GENERIC_ERROR; // Generic error
+use crate::rng::header::error::MBEDTLS_ERR_ERROR_CORRUPTION_DETECTED; // This is a bug in the library
+
+use crate::rng::header::aes::MBEDTLS_ERR_AES_INVALID_KEY_LENGTH; // Invalid key length.
+use crate::rng::header::aes::MBEDTLS_ERR_AES_BAD_INPUT_DATA; // Invalid input data.
+
+use crate::rng::header::ctr_drbg::MBEDTLS_ERR_CTR_DRBG_ENTROPY_SOURCE_FAILED; // The entropy source failed.
+use crate::rng::header::ctr_drbg::MBEDTLS_ERR_CTR_DRBG_REQUEST_TOO_BIG; // The requested random buffer length is too big.
+use crate::rng::header::ctr_drbg::MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG; // The input (entropy + additional data) is too large.
+use crate::rng::header::ctr_drbg::MBEDTLS_ERR_CTR_DRBG_FILE_IO_ERROR; // Read or write error in file.
+use crate::rng::header::ctr_drbg::MBEDTLS_ERR_CTR_DRBG_SEED_FILE_TRUNCATED; // The seed file ends before its integrity tag.
+use crate::rng::header::ctr_drbg::MBEDTLS_ERR_CTR_DRBG_SEED_FILE_CORRUPTED; // The seed file integrity tag does not match.
+use crate::rng::header::ctr_drbg::MBEDTLS_ERR_CTR_DRBG_SEED_FILE_BAD_VERSION; // The seed file has an unsupported version.
+
+use crate::rng::header::hmac_drbg::MBEDTLS_ERR_HMAC_DRBG_REQUEST_TOO_BIG; // Too many random requested in single call.
+use crate::rng::header::hmac_drbg::MBEDTLS_ERR_HMAC_DRBG_INPUT_TOO_BIG; // Input too large (Entropy + additional).
+use crate::rng::header::hmac_drbg::MBEDTLS_ERR_HMAC_DRBG_FILE_IO_ERROR; // Read/write error in file.
+use crate::rng::header::hmac_drbg::MBEDTLS_ERR_HMAC_DRBG_ENTROPY_SOURCE_FAILED; // The entropy source failed.
+
+use crate::rng::header::hash_drbg::MBEDTLS_ERR_HASH_DRBG_REQUEST_TOO_BIG; // Too many random requested in single call.
+use crate::rng::header::hash_drbg::MBEDTLS_ERR_HASH_DRBG_INPUT_TOO_BIG; // Input too large (Entropy + additional).
+use crate::rng::header::hash_drbg::MBEDTLS_ERR_HASH_DRBG_FILE_IO_ERROR; // Read/write error in file.
+use crate::rng::header::hash_drbg::MBEDTLS_ERR_HASH_DRBG_ENTROPY_SOURCE_FAILED; // The entropy source failed.
+
+use crate::rng::header::md::MBEDTLS_ERR_MD_BAD_INPUT_DATA; // Bad input parameters to function.
+
+use crate::rng::header::entropy::MBEDTLS_ERR_ENTROPY_SOURCE_FAILED; // Critical entropy source failure.
+use crate::rng::header::entropy::MBEDTLS_ERR_ENTROPY_MAX_SOURCES; // No more sources can be added.
+use crate::rng::header::entropy::MBEDTLS_ERR_ENTROPY_NO_SOURCES_DEFINED; // No sources have been added to poll.
+use crate::rng::header::entropy::MBEDTLS_ERR_ENTROPY_NO_STRONG_SOURCE; // No strong sources have been added to poll.
+use crate::rng::header::entropy::MBEDTLS_ERR_ENTROPY_FILE_IO_ERROR; // Read/write error in file.
+
+use crate::rng::header::threading::MBEDTLS_ERR_THREADING_FEATURE_UNAVAILABLE; // The selected feature is not available.
+use crate::rng::header::threading::MBEDTLS_ERR_THREADING_BAD_INPUT_DATA; // Bad input parameters to function.
+use crate::rng::header::threading::MBEDTLS_ERR_THREADING_MUTEX_ERROR; // Locking / unlocking / free failed with error code.
+
+/*
+ * Every named error, with its mbedTLS code and its mbedtls_strerror() text
+ */
+const ERROR_TABLE: [(Error, i32, &str); 28] = [
+    (Error::CtrDrbgEntropySourceFailed, MBEDTLS_ERR_CTR_DRBG_ENTROPY_SOURCE_FAILED,
+        "CTR_DRBG - The entropy source failed"),
+    (Error::CtrDrbgRequestTooBig, MBEDTLS_ERR_CTR_DRBG_REQUEST_TOO_BIG,
+        "CTR_DRBG - The requested random buffer length is too big"),
+    (Error::CtrDrbgInputTooBig, MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG,
+        "CTR_DRBG - The input (entropy + additional data) is too large"),
+    (Error::CtrDrbgFileIoError, MBEDTLS_ERR_CTR_DRBG_FILE_IO_ERROR,
+        "CTR_DRBG - Read or write error in file"),
+    (Error::CtrDrbgSeedFileTruncated, MBEDTLS_ERR_CTR_DRBG_SEED_FILE_TRUNCATED,
+        "CTR_DRBG - The seed file ends before its integrity tag"),
+    (Error::CtrDrbgSeedFileCorrupted, MBEDTLS_ERR_CTR_DRBG_SEED_FILE_CORRUPTED,
+        "CTR_DRBG - The seed file integrity tag does not match its contents"),
+    (Error::CtrDrbgSeedFileBadVersion, MBEDTLS_ERR_CTR_DRBG_SEED_FILE_BAD_VERSION,
+        "CTR_DRBG - The seed file has an unsupported format version"),
+    (Error::HmacDrbgRequestTooBig, MBEDTLS_ERR_HMAC_DRBG_REQUEST_TOO_BIG,
+        "HMAC_DRBG - Too many random requested in single call"),
+    (Error::HmacDrbgInputTooBig, MBEDTLS_ERR_HMAC_DRBG_INPUT_TOO_BIG,
+        "HMAC_DRBG - Input too large (Entropy + additional)"),
+    (Error::HmacDrbgFileIoError, MBEDTLS_ERR_HMAC_DRBG_FILE_IO_ERROR,
+        "HMAC_DRBG - Read/write error in file"),
+    (Error::HmacDrbgEntropySourceFailed, MBEDTLS_ERR_HMAC_DRBG_ENTROPY_SOURCE_FAILED,
+        "HMAC_DRBG - The entropy source failed"),
+    (Error::HashDrbgRequestTooBig, MBEDTLS_ERR_HASH_DRBG_REQUEST_TOO_BIG,
+        "HASH_DRBG - Too many random requested in single call"),
+    (Error::HashDrbgInputTooBig, MBEDTLS_ERR_HASH_DRBG_INPUT_TOO_BIG,
+        "HASH_DRBG - Input too large (Entropy + additional)"),
+    (Error::HashDrbgFileIoError, MBEDTLS_ERR_HASH_DRBG_FILE_IO_ERROR,
+        "HASH_DRBG - Read/write error in file"),
+    (Error::HashDrbgEntropySourceFailed, MBEDTLS_ERR_HASH_DRBG_ENTROPY_SOURCE_FAILED,
+        "HASH_DRBG - The entropy source failed"),
+    (Error::AesInvalidKeyLength, MBEDTLS_ERR_AES_INVALID_KEY_LENGTH,
+        "AES - Invalid key length"),
+    (Error::AesBadInputData, MBEDTLS_ERR_AES_BAD_INPUT_DATA,
+        "AES - Invalid input data"),
+    (Error::MdBadInputData, MBEDTLS_ERR_MD_BAD_INPUT_DATA,
+        "MD - Bad input parameters to function"),
+    (Error::EntropySourceFailed, MBEDTLS_ERR_ENTROPY_SOURCE_FAILED,
+        "ENTROPY - Critical entropy source failure"),
+    (Error::EntropyMaxSources, MBEDTLS_ERR_ENTROPY_MAX_SOURCES,
+        "ENTROPY - No more sources can be added"),
+    (Error::EntropyNoSourcesDefined, MBEDTLS_ERR_ENTROPY_NO_SOURCES_DEFINED,
+        "ENTROPY - No sources have been added to poll"),
+    (Error::EntropyNoStrongSource, MBEDTLS_ERR_ENTROPY_NO_STRONG_SOURCE,
+        "ENTROPY - No strong sources have been added to poll"),
+    (Error::EntropyFileIoError, MBEDTLS_ERR_ENTROPY_FILE_IO_ERROR,
+        "ENTROPY - Read/write error in file"),
+    (Error::ThreadingFeatureUnavailable, MBEDTLS_ERR_THREADING_FEATURE_UNAVAILABLE,
+        "THREADING - The selected feature is not available"),
+    (Error::ThreadingBadInputData, MBEDTLS_ERR_THREADING_BAD_INPUT_DATA,
+        "THREADING - Bad input parameters to function"),
+    (Error::ThreadingMutexError, MBEDTLS_ERR_THREADING_MUTEX_ERROR,
+        "THREADING - Locking / unlocking / free failed with error code"),
+    (Error::GenericError, MBEDTLS_ERR_ERROR_GENERIC_ERROR,
+        "ERROR - Generic error"),
+    (Error::CorruptionDetected, MBEDTLS_ERR_ERROR_CORRUPTION_DETECTED,
+        "ERROR - This is a bug in the library"),
+];
+
+impl Error {
+    /// This function returns the mbedTLS numeric code of the error.
+    pub fn code(&self) -> i32 {
+        match *self {
+            Error::Other(code) => code,
+            err => ERROR_TABLE.iter()
+                .find(|(e, _, _)| *e == err)
+                .map(|(_, code, _)| *code)
+                .unwrap_or(MBEDTLS_ERR_ERROR_GENERIC_ERROR),
+        }
+    }
+
+    /// This function converts an mbedTLS numeric code to an error. Codes
+    /// without a dedicated variant are kept in `Error::Other`.
+    pub fn from_code(code: i32) -> Error {
+        ERROR_TABLE.iter()
+            .find(|(_, c, _)| *c == code)
+            .map(|(e, _, _)| *e)
+            .unwrap_or(Error::Other(code))
+    }
+
+    /// This function returns the mbedTLS error carried by an io::Error,
+    /// such as one returned by CtrDrbgReader, or None for any other I/O
+    /// error.
+    pub fn from_io(err: &io::Error) -> Option<Error> {
+        err.get_ref()
+            .and_then(|inner| inner.downcast_ref::<Error>())
+            .copied()
+    }
+}
+
+impl From<i32> for Error {
+    fn from(code: i32) -> Error {
+        Error::from_code(code)
+    }
+}
+
+impl From<Error> for i32 {
+    fn from(err: Error) -> i32 {
+        err.code()
+    }
+}
+
+impl From<Error> for io::Error {
+    /// Wraps the error as an io::Error of kind Other; Error::from_io()
+    /// gives it back.
+    fn from(err: Error) -> io::Error {
+        io::Error::other(err)
+    }
+}
+
+impl fmt::Display for Error {
+    /// Translates the error the way mbedtls_strerror() does.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = self.code();
+        match ERROR_TABLE.iter().find(|(_, c, _)| *c == code) {
+            Some((_, _, msg)) => f.write_str(msg),
+            None => write!(f, "UNKNOWN ERROR CODE ({:04X})", code.unsigned_abs()),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
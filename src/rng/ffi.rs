@@ -0,0 +1,373 @@
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_uchar, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::ptr;
+use std::slice;
+
+use crate::rng::header::error::Error;
+
+use crate::rng::header::ctr_drbg::CtrDrbg; // The CTR_DRBG context structure.
+use crate::rng::header::ctr_drbg::LegacyEntropySource; // An entropy source calling a C-style callback.
+use crate::rng::header::ffi::mbedtls_ctr_drbg_context; // The CTR_DRBG context as seen from C.
+
+use crate::rng::ctr_drbg::self_test;
+
+/*
+ * Every function below takes NULL, or a context passed to
+ * mbedtls_ctr_drbg_init(), freed since or not. NULL and a freed context
+ * are reported as MBEDTLS_ERR_ERROR_GENERIC_ERROR where an error can be
+ * returned, and ignored otherwise. A context that never went through
+ * mbedtls_ctr_drbg_init() holds an indeterminate pointer and must not be
+ * passed at all; calling mbedtls_ctr_drbg_init() twice without
+ * mbedtls_ctr_drbg_free() in between leaks the first Rust context.
+ */
+
+/*
+ * Every function body runs under guarded(), or guarded_void() when there
+ * is no return code: a panic must not unwind into C, so it is caught and
+ * reported as MBEDTLS_ERR_ERROR_GENERIC_ERROR, or ignored.
+ */
+fn guarded<F: FnOnce() -> c_int>(body: F) -> c_int {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|_| Error::GenericError.code())
+}
+
+fn guarded_void<F: FnOnce()>(body: F) {
+    let _ = panic::catch_unwind(AssertUnwindSafe(body));
+}
+
+/*
+ * Returns the Rust context behind a C context, if it was initialised
+ */
+unsafe fn context<'a>(ctx: *mut mbedtls_ctr_drbg_context) -> Option<&'a mut CtrDrbg<'static>> {
+    if ctx.is_null() {
+        return None;
+    }
+    (*ctx).ctx.as_mut()
+}
+
+/*
+ * Turns a C buffer into a slice; a NULL buffer is only valid when empty
+ */
+unsafe fn input_slice<'a>(data: *const c_uchar, len: usize) -> Result<&'a [u8], Error> {
+    if len == 0 {
+        Ok(&[])
+    } else if data.is_null() {
+        Err(Error::GenericError)
+    } else {
+        Ok(slice::from_raw_parts(data, len))
+    }
+}
+
+unsafe fn output_slice<'a>(data: *mut c_uchar, len: usize) -> Result<&'a mut [u8], Error> {
+    if len == 0 {
+        Ok(&mut [])
+    } else if data.is_null() {
+        Err(Error::GenericError)
+    } else {
+        Ok(slice::from_raw_parts_mut(data, len))
+    }
+}
+
+unsafe fn c_path(path: *const c_char) -> Result<PathBuf, Error> {
+    if path.is_null() {
+        return Err(Error::CtrDrbgFileIoError);
+    }
+    let path = CStr::from_ptr(path);
+
+    #[cfg(unix)]
+    {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        Ok(PathBuf::from(OsStr::from_bytes(path.to_bytes())))
+    }
+    #[cfg(not(unix))]
+    {
+        path.to_str().map(PathBuf::from).map_err(|_| Error::CtrDrbgFileIoError)
+    }
+}
+
+fn code(ret: Result<(), Error>) -> c_int {
+    match ret {
+        Ok(()) => 0,
+        Err(err) => err.code(),
+    }
+}
+
+/// This function initializes the CTR_DRBG context, and prepares it for
+/// mbedtls_ctr_drbg_seed().
+///
+/// # Safety
+///
+/// `ctx` must be NULL or point to writable memory for a context. A
+/// context that was initialised must be freed before it is initialised
+/// again, or its memory leaks.
+#[no_mangle]
+pub unsafe extern "C" fn mbedtls_ctr_drbg_init(ctx: *mut mbedtls_ctr_drbg_context) {
+    guarded_void(|| {
+        if ctx.is_null() {
+            return;
+        }
+        ptr::write(ctx, mbedtls_ctr_drbg_context {
+            ctx: Box::into_raw(Box::new(CtrDrbg::new())),
+        });
+    })
+}
+
+/// This function seeds and sets up the CTR_DRBG entropy source for
+/// future reseeds.
+///
+/// # Safety
+///
+/// `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+/// `f_entropy` must be safe to call with `p_entropy` until the context
+/// is freed. `custom` must point to `len` readable bytes.
+/*
+ * f_entropy has the type of EntropyFn spelled out: cbindgen does not see
+ * through a type alias inside Option.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn mbedtls_ctr_drbg_seed(ctx: *mut mbedtls_ctr_drbg_context,
+                                               f_entropy: Option<unsafe extern "C" fn(p_entropy: *mut c_void, output: *mut c_uchar, len: usize) -> c_int>,
+                                               p_entropy: *mut c_void,
+                                               custom: *const c_uchar,
+                                               len: usize) -> c_int {
+    guarded(|| {
+        let ctx = match context(ctx) {
+            Some(ctx) => ctx,
+            None => return Error::GenericError.code(),
+        };
+        let f_entropy = match f_entropy {
+            Some(f) => f,
+            None => return Error::CtrDrbgEntropySourceFailed.code(),
+        };
+
+        code(input_slice(custom, len).and_then(|custom| {
+            ctx.seed(LegacyEntropySource::new(f_entropy, p_entropy), custom)
+        }))
+    })
+}
+
+/// This function clears CTR_DRBG context data and releases it.
+///
+/// # Safety
+///
+/// `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init(),
+/// possibly freed already.
+#[no_mangle]
+pub unsafe extern "C" fn mbedtls_ctr_drbg_free(ctx: *mut mbedtls_ctr_drbg_context) {
+    guarded_void(|| {
+        if ctx.is_null() || (*ctx).ctx.is_null() {
+            return;
+        }
+        drop(Box::from_raw((*ctx).ctx));
+        (*ctx).ctx = ptr::null_mut();
+    })
+}
+
+/// This function turns prediction resistance on or off.
+///
+/// # Safety
+///
+/// `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+#[no_mangle]
+pub unsafe extern "C" fn mbedtls_ctr_drbg_set_prediction_resistance(ctx: *mut mbedtls_ctr_drbg_context,
+                                                                    resistance: c_int) {
+    guarded_void(|| {
+        if let Some(ctx) = context(ctx) {
+            ctx.set_prediction_resistance(resistance != 0);
+        }
+    })
+}
+
+/// This function sets the amount of entropy grabbed on each seed or reseed.
+///
+/// # Safety
+///
+/// `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+#[no_mangle]
+pub unsafe extern "C" fn mbedtls_ctr_drbg_set_entropy_len(ctx: *mut mbedtls_ctr_drbg_context, len: usize) {
+    guarded_void(|| {
+        if let Some(ctx) = context(ctx) {
+            ctx.set_entropy_len(len);
+        }
+    })
+}
+
+/// This function sets the amount of entropy grabbed as a nonce for the
+/// initial seeding.
+///
+/// # Safety
+///
+/// `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+#[no_mangle]
+pub unsafe extern "C" fn mbedtls_ctr_drbg_set_nonce_len(ctx: *mut mbedtls_ctr_drbg_context, len: usize) -> c_int {
+    guarded(|| {
+        match context(ctx) {
+            Some(ctx) => code(ctx.set_nonce_len(len)),
+            None => Error::GenericError.code(),
+        }
+    })
+}
+
+/// This function sets the reseed interval. As in mbedTLS, the value is
+/// not checked: an interval below 1 makes every request reseed first.
+///
+/// # Safety
+///
+/// `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+#[no_mangle]
+pub unsafe extern "C" fn mbedtls_ctr_drbg_set_reseed_interval(ctx: *mut mbedtls_ctr_drbg_context,
+                                                              interval: c_int) {
+    guarded_void(|| {
+        if let Some(ctx) = context(ctx) {
+            ctx.set_reseed_interval(interval);
+        }
+    })
+}
+
+/// This function reseeds the CTR_DRBG context, that is extracts data from
+/// the entropy source.
+///
+/// # Safety
+///
+/// `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+/// `additional` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn mbedtls_ctr_drbg_reseed(ctx: *mut mbedtls_ctr_drbg_context,
+                                                 additional: *const c_uchar,
+                                                 len: usize) -> c_int {
+    guarded(|| {
+        match context(ctx) {
+            Some(ctx) => code(input_slice(additional, len).and_then(|add| ctx.reseed(add))),
+            None => Error::GenericError.code(),
+        }
+    })
+}
+
+/// This function updates the state of the CTR_DRBG context.
+///
+/// # Safety
+///
+/// `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+/// `additional` must point to `add_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn mbedtls_ctr_drbg_update_ret(ctx: *mut mbedtls_ctr_drbg_context,
+                                                     additional: *const c_uchar,
+                                                     add_len: usize) -> c_int {
+    guarded(|| {
+        match context(ctx) {
+            Some(ctx) => code(input_slice(additional, add_len).and_then(|add| ctx.update(add))),
+            None => Error::GenericError.code(),
+        }
+    })
+}
+
+/// This function updates the state of the CTR_DRBG context, ignoring
+/// errors. It is deprecated in mbedTLS in favour of
+/// mbedtls_ctr_drbg_update_ret().
+///
+/// # Safety
+///
+/// As for mbedtls_ctr_drbg_update_ret().
+#[no_mangle]
+pub unsafe extern "C" fn mbedtls_ctr_drbg_update(ctx: *mut mbedtls_ctr_drbg_context,
+                                                 additional: *const c_uchar,
+                                                 add_len: usize) {
+    guarded_void(|| {
+        mbedtls_ctr_drbg_update_ret(ctx, additional, add_len);
+    })
+}
+
+/// This function updates a CTR_DRBG instance with additional data and
+/// uses it to generate random data. It has the f_rng shape with
+/// additional data, `p_rng` being the context.
+///
+/// # Safety
+///
+/// `p_rng` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+/// `output` must point to `output_len` writable bytes and `additional`
+/// to `add_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn mbedtls_ctr_drbg_random_with_add(p_rng: *mut c_void,
+                                                          output: *mut c_uchar,
+                                                          output_len: usize,
+                                                          additional: *const c_uchar,
+                                                          add_len: usize) -> c_int {
+    guarded(|| {
+        let ctx = match context(p_rng as *mut mbedtls_ctr_drbg_context) {
+            Some(ctx) => ctx,
+            None => return Error::GenericError.code(),
+        };
+
+        code(output_slice(output, output_len).and_then(|out| {
+            input_slice(additional, add_len).and_then(|add| ctx.generate_with_additional(out, add))
+        }))
+    })
+}
+
+/// This function uses CTR_DRBG to generate random data. It is the f_rng
+/// callback of the module, `p_rng` being the context.
+///
+/// # Safety
+///
+/// `p_rng` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+/// `output` must point to `output_len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn mbedtls_ctr_drbg_random(p_rng: *mut c_void,
+                                                 output: *mut c_uchar,
+                                                 output_len: usize) -> c_int {
+    guarded(|| {
+        mbedtls_ctr_drbg_random_with_add(p_rng, output, output_len, ptr::null(), 0)
+    })
+}
+
+/// This function writes a seed file.
+///
+/// # Safety
+///
+/// `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+/// `path` must be NULL or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn mbedtls_ctr_drbg_write_seed_file(ctx: *mut mbedtls_ctr_drbg_context,
+                                                          path: *const c_char) -> c_int {
+    guarded(|| {
+        match context(ctx) {
+            Some(ctx) => code(c_path(path).and_then(|p| ctx.write_seed_file(p))),
+            None => Error::GenericError.code(),
+        }
+    })
+}
+
+/// This function reads and updates a seed file. The seed is added to
+/// this instance.
+///
+/// # Safety
+///
+/// `ctx` must be NULL or a context passed to mbedtls_ctr_drbg_init().
+/// `path` must be NULL or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn mbedtls_ctr_drbg_update_seed_file(ctx: *mut mbedtls_ctr_drbg_context,
+                                                           path: *const c_char) -> c_int {
+    guarded(|| {
+        match context(ctx) {
+            Some(ctx) => code(c_path(path).and_then(|p| ctx.update_seed_file(p))),
+            None => Error::GenericError.code(),
+        }
+    })
+}
+
+/// The CTR_DRBG checkup routine. Returns 0 on success and 1 on failure;
+/// with `verbose`, the report is printed to standard output.
+#[no_mangle]
+pub extern "C" fn mbedtls_ctr_drbg_self_test(verbose: c_int) -> c_int {
+    guarded(|| {
+        let report = self_test();
+
+        if verbose != 0 {
+            println!("{}", report);
+        }
+
+        !report.passed() as c_int
+    })
+}
@@ -0,0 +1,594 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use crate::rng::header::ctr_drbg::EntropyCallback; // The entropy callback.
+use crate::rng::header::ctr_drbg::EntropySource; // A source of entropy for seeding a DRBG.
+use crate::rng::header::entropy_poll::PlatformEntropy; // The platform entropy source.
+use crate::rng::header::error::Error;
+use crate::rng::header::md::MdContext; // The generic message-digest context.
+use crate::rng::header::md::MdType; // Supported message digests.
+use crate::rng::header::md::MBEDTLS_MD_MAX_SIZE; // The longest message digest supported, in bytes.
+
+use crate::rng::header::hash_drbg::MBEDTLS_HASH_DRBG_RESEED_INTERVAL; // Interval before reseed is performed by default.
+use crate::rng::header::hash_drbg::MBEDTLS_HASH_DRBG_MAX_INPUT; // Maximum number of additional input bytes.
+use crate::rng::header::hash_drbg::MBEDTLS_HASH_DRBG_MAX_REQUEST; // Maximum number of requested bytes per call.
+use crate::rng::header::hash_drbg::MBEDTLS_HASH_DRBG_MAX_SEED_INPUT; // Maximum size of (re)seed buffer.
+use crate::rng::header::hash_drbg::MBEDTLS_HASH_DRBG_SEEDLEN_256; // The seed length for SHA-224 and SHA-256, in bytes.
+use crate::rng::header::hash_drbg::MBEDTLS_HASH_DRBG_SEEDLEN_512; // The seed length for SHA-384 and SHA-512, in bytes.
+use crate::rng::header::hash_drbg::MBEDTLS_HASH_DRBG_MAX_SEEDLEN; // The largest seed length, in bytes.
+use crate::rng::header::hash_drbg::MBEDTLS_HASH_DRBG_PR_ON; // Prediction resistance enabled.
+use crate::rng::header::hash_drbg::HashDrbg; // The Hash_DRBG context structure.
+
+use crate::rng::header::platform_util::Zeroizing; // A buffer wiped when it goes out of scope.
+
+use crate::rng::platform_util::{platform_zeroize, read_file, write_file_atomic};
+
+/*
+ * Hash_DRBG context initialization
+ */
+
+impl<'a> HashDrbg<'a> {
+    /// This function initializes the Hash_DRBG context, and prepares it for seed().
+    pub fn new() -> Self {
+        HashDrbg {
+            md_ctx: None,
+            v: [0; MBEDTLS_HASH_DRBG_MAX_SEEDLEN],
+            c: [0; MBEDTLS_HASH_DRBG_MAX_SEEDLEN],
+            seedlen: 0,
+            reseed_counter: 0,
+            entropy_len: 0,
+            prediction_resistance: false,
+            reseed_interval: MBEDTLS_HASH_DRBG_RESEED_INTERVAL,
+            f_entropy: None,
+        }
+    }
+
+    /*
+     *  This function resets Hash_DRBG context to the state immediately
+     *  after initial call of new().
+     */
+
+    /// This function clears Hash_DRBG context data.
+    pub fn free(&mut self) {
+        *self = HashDrbg::new();
+    }
+
+    /// This function turns prediction resistance on or off. The default value is off.
+    pub fn set_prediction_resistance(&mut self, resistance: bool) {
+        self.prediction_resistance = resistance;
+    }
+
+    /// This function sets the amount of entropy grabbed on each seed or reseed.
+    /// The default value is given by the security strength of the hash
+    /// function: 24 bytes for SHA-224, 32 bytes for the larger digests.
+    pub fn set_entropy_len(&mut self, len: usize) {
+        self.entropy_len = len;
+    }
+
+    /// This function sets the reseed interval: the number of generate
+    /// requests after which the entropy source is called again. The default
+    /// value is MBEDTLS_HASH_DRBG_RESEED_INTERVAL.
+    ///
+    /// As in mbedTLS, the value is not checked. A request reseeds when more
+    /// than `interval` requests were made since the last (re)seeding, so an
+    /// interval below 1 makes every request reseed first, like
+    /// prediction resistance.
+    pub fn set_reseed_interval(&mut self, interval: i32) {
+        self.reseed_interval = interval;
+    }
+}
+
+impl Default for HashDrbg<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for HashDrbg<'_> {
+    /// This function wipes the working state of the Hash_DRBG context.
+    fn drop(&mut self) {
+        platform_zeroize(&mut self.v);
+        platform_zeroize(&mut self.c);
+    }
+}
+
+/*
+ * Hash(input) where input is the concatenation of the given parts
+ */
+fn hash(md_ctx: &mut MdContext, input: &[&[u8]], output: &mut [u8; MBEDTLS_MD_MAX_SIZE]) {
+    md_ctx.starts();
+    for part in input {
+        md_ctx.update(part);
+    }
+    md_ctx.finish(output);
+}
+
+/*
+ * Hash_df (SP 800-90A &sect;10.3.1)
+ * hash_df(md_ctx, input, output)
+ * implements
+ * Hash_df(input_string, no_of_bits_to_return) -> requested_bits
+ * with
+ *   input_string = the concatenation of input
+ *   no_of_bits_to_return = 8 * output.len()
+ */
+fn hash_df(md_ctx: &mut MdContext, input: &[&[u8]], output: &mut [u8]) {
+    let md_len = md_ctx.md_type().size();
+    let no_of_bits = ((output.len() * 8) as u32).to_be_bytes();
+    let mut tmp = Zeroizing::new([0u8; MBEDTLS_MD_MAX_SIZE]);
+
+    for (counter, chunk) in output.chunks_mut(md_len).enumerate() {
+        /* temp = temp || Hash(counter || no_of_bits_to_return || input_string) */
+        md_ctx.starts();
+        md_ctx.update(&[counter as u8 + 1]);
+        md_ctx.update(&no_of_bits);
+        for part in input {
+            md_ctx.update(part);
+        }
+        md_ctx.finish(&mut tmp);
+
+        chunk.copy_from_slice(&tmp[..chunk.len()]);
+    }
+
+}
+
+/*
+ * v = (v + x) mod 2^(8 * v.len()), both big endian, x no longer than v
+ */
+fn add_mod(v: &mut [u8], x: &[u8]) {
+    let mut carry: u16 = 0;
+    let mut x_bytes = x.iter().rev();
+
+    for b in v.iter_mut().rev() {
+        let sum = *b as u16 + *x_bytes.next().unwrap_or(&0) as u16 + carry;
+        *b = sum as u8;
+        carry = sum >> 8;
+    }
+}
+
+impl<'a> HashDrbg<'a> {
+    /*
+     * Select the hash function and its seed length
+     */
+    fn setup(&mut self, md_type: MdType) {
+        self.seedlen = match md_type {
+            MdType::Sha224 | MdType::Sha256 => MBEDTLS_HASH_DRBG_SEEDLEN_256,
+            MdType::Sha384 | MdType::Sha512 => MBEDTLS_HASH_DRBG_SEEDLEN_512,
+        };
+        self.v = [0; MBEDTLS_HASH_DRBG_MAX_SEEDLEN];
+        self.c = [0; MBEDTLS_HASH_DRBG_MAX_SEEDLEN];
+        self.md_ctx = Some(MdContext::new(md_type));
+    }
+
+    /*
+     * Derive V from the seed material, then C from V
+     *   V = Hash_df(seed_material, seedlen)
+     *   C = Hash_df((0x00 || V), seedlen)
+     */
+    fn derive_state(&mut self, seed_material: &[&[u8]]) -> Result<(), Error> {
+        let md_ctx = self.md_ctx.as_mut().ok_or(Error::MdBadInputData)?;
+        let seedlen = self.seedlen;
+        let mut seed = Zeroizing::new([0u8; MBEDTLS_HASH_DRBG_MAX_SEEDLEN]);
+
+        hash_df(md_ctx, seed_material, &mut seed[..seedlen]);
+        self.v = *seed;
+        hash_df(md_ctx, &[&[0x00], &self.v[..seedlen]], &mut self.c[..seedlen]);
+
+        Ok(())
+    }
+
+    /// This function updates the state of the Hash_DRBG context with
+    /// additional data, by running the reseed algorithm with the data in
+    /// place of fresh entropy. The reseed counter is left untouched.
+    pub fn update(&mut self, additional: &[u8]) -> Result<(), Error> {
+        let mut v = Zeroizing::new([0u8; MBEDTLS_HASH_DRBG_MAX_SEEDLEN]);
+
+        if additional.is_empty() {
+            return Ok(());
+        }
+
+        v.copy_from_slice(&self.v);
+        self.derive_state(&[&[0x01], &v[..self.seedlen], additional])
+    }
+
+    /*
+     * Internal function used both for seeding and reseeding the DRBG.
+     * Hash_DRBG_Instantiate_algorithm (10.1.1.2) if use_nonce is set,
+     * Hash_DRBG_Reseed_algorithm (10.1.1.3) otherwise.
+     */
+    fn reseed_core(&mut self, additional: &[u8], use_nonce: bool) -> Result<(), Error> {
+        let mut seed = Zeroizing::new([0u8; MBEDTLS_HASH_DRBG_MAX_SEED_INPUT]);
+        let mut v = Zeroizing::new([0u8; MBEDTLS_HASH_DRBG_MAX_SEEDLEN]);
+        let mut seedlen: usize = 0;
+        let len = additional.len();
+
+        {
+            let total_entropy_len = if !use_nonce {
+                self.entropy_len
+            } else {
+                self.entropy_len * 3 / 2
+            };
+
+            /* Check input length */
+            if len > MBEDTLS_HASH_DRBG_MAX_INPUT ||
+                total_entropy_len + len > MBEDTLS_HASH_DRBG_MAX_SEED_INPUT
+            {
+                return Err(Error::HashDrbgInputTooBig);
+            }
+        }
+
+        let entropy_len = self.entropy_len;
+        let f_entropy = self.f_entropy.as_mut()
+            .ok_or(Error::HashDrbgEntropySourceFailed)?;
+
+        /* Gather entropy_len bytes of entropy for the seed */
+        if f_entropy.fill(&mut seed[..entropy_len]).is_err() {
+            return Err(Error::HashDrbgEntropySourceFailed);
+        }
+        seedlen += entropy_len;
+
+        /* For initial seeding, the nonce is also taken from the
+         * entropy source. See Sect 8.6.7 in SP800-90A. */
+        if use_nonce {
+            if f_entropy.fill(&mut seed[seedlen..seedlen + entropy_len / 2]).is_err() {
+                return Err(Error::HashDrbgEntropySourceFailed);
+            }
+            seedlen += entropy_len / 2;
+        }
+
+        /* Concatenate entropy and additional data if any */
+        if len != 0 {
+            seed[seedlen..seedlen + len].copy_from_slice(additional);
+            seedlen += len;
+        }
+
+        if use_nonce {
+            /* seed_material = entropy_input || nonce || personalization_string */
+            self.derive_state(&[&seed[..seedlen]])?;
+        } else {
+            /* seed_material = 0x01 || V || entropy_input || additional_input */
+            v.copy_from_slice(&self.v);
+            self.derive_state(&[&[0x01], &v[..self.seedlen], &seed[..seedlen]])?;
+        }
+
+        self.reseed_counter = 1;
+        Ok(())
+    }
+
+    /*
+     * Hash_DRBG reseeding: 10.1.1.3 + 9.2
+     */
+
+    /// This function reseeds the Hash_DRBG context, that is extracts data from the entropy source.
+    pub fn reseed(&mut self, additional: &[u8]) -> Result<(), Error> {
+        self.reseed_core(additional, false)
+    }
+
+    /*
+     * Hash_DRBG initialisation (10.1.1.2 + 9.1)
+     *
+     * The nonce is not passed as a separate parameter but extracted
+     * from the entropy source as suggested in 8.6.7.
+     */
+
+    /// This function seeds and sets up the Hash_DRBG entropy source for future reseeds.
+    pub fn seed<E: EntropySource + Send + 'a>(&mut self, md_type: MdType, f_entropy: E, custom: &[u8]) -> Result<(), Error> {
+        self.setup(md_type);
+
+        self.f_entropy = Some(Box::new(f_entropy));
+
+        if self.entropy_len == 0 {
+            /*
+             * The minimum entropy is the security strength of the hash
+             * function (SP800-57 5.6.1, SP800-90A rev1 10.1 table 2).
+             */
+            self.entropy_len = if md_type.size() <= 28 {
+                24 /* 224-bits hash -> 192 bits */
+            } else {
+                32 /* better (256+) -> 256 bits */
+            };
+        }
+
+        self.reseed_core(custom, true /* add nonce */)
+    }
+
+    /// This function seeds the Hash_DRBG from the platform entropy source,
+    /// which is then also used for future reseeds.
+    pub fn seed_default(&mut self, md_type: MdType, custom: &[u8]) -> Result<(), Error> {
+        self.seed(md_type, PlatformEntropy, custom)
+    }
+
+    /*
+     * Hash_DRBG random function with optional additional data:
+     * 10.1.1.4 (arabic) + 9.3 (Roman)
+     */
+
+    /// This function updates a Hash_DRBG instance with additional data and uses it to generate random data.
+    pub fn generate_with_additional(&mut self, output: &mut [u8], additional: &[u8]) -> Result<(), Error> {
+        let mut additional = additional;
+
+        /* Check request length */
+        if output.len() > MBEDTLS_HASH_DRBG_MAX_REQUEST {
+            return Err(Error::HashDrbgRequestTooBig);
+        }
+
+        /* Check input length */
+        if additional.len() > MBEDTLS_HASH_DRBG_MAX_INPUT {
+            return Err(Error::HashDrbgInputTooBig);
+        }
+
+        /* 1. Check reseed counter and PR */
+        if self.prediction_resistance == MBEDTLS_HASH_DRBG_PR_ON ||
+            self.reseed_counter > self.reseed_interval
+        {
+            self.reseed(additional)?;
+
+            additional = &[];
+        }
+
+        let md_ctx = self.md_ctx.as_mut().ok_or(Error::MdBadInputData)?;
+        let md_len = md_ctx.md_type().size();
+        let seedlen = self.seedlen;
+        let mut w = Zeroizing::new([0u8; MBEDTLS_MD_MAX_SIZE]);
+        let mut data = Zeroizing::new([0u8; MBEDTLS_HASH_DRBG_MAX_SEEDLEN]);
+
+        /* 2. If additional_input != Null:
+         *      w = Hash(0x02 || V || additional_input)
+         *      V = (V + w) mod 2^seedlen */
+        if !additional.is_empty() {
+            hash(md_ctx, &[&[0x02], &self.v[..seedlen], additional], &mut w);
+            add_mod(&mut self.v[..seedlen], &w[..md_len]);
+        }
+
+        /* 3. (returned_bits) = Hashgen(requested_number_of_bits, V) */
+        data.copy_from_slice(&self.v);
+        for chunk in output.chunks_mut(md_len) {
+            hash(md_ctx, &[&data[..seedlen]], &mut w);
+            chunk.copy_from_slice(&w[..chunk.len()]);
+            add_mod(&mut data[..seedlen], &[0x01]);
+        }
+
+        /* 4. H = Hash(0x03 || V)
+         * 5. V = (V + H + C + reseed_counter) mod 2^seedlen */
+        hash(md_ctx, &[&[0x03], &self.v[..seedlen]], &mut w);
+        add_mod(&mut self.v[..seedlen], &w[..md_len]);
+        add_mod(&mut self.v[..seedlen], &self.c[..seedlen]);
+        add_mod(&mut self.v[..seedlen], &(self.reseed_counter as u64).to_be_bytes());
+
+        /* 6. Update reseed counter */
+        self.reseed_counter += 1;
+
+        Ok(())
+    }
+
+    /// This function uses Hash_DRBG to generate random data.
+    pub fn generate(&mut self, output: &mut [u8]) -> Result<(), Error> {
+        self.generate_with_additional(output, &[])
+    }
+
+    /// This function writes a seed file of MBEDTLS_HASH_DRBG_MAX_INPUT
+    /// bytes of output.
+    ///
+    /// The file is written next to `path` under a temporary name, readable
+    /// and writable by its owner only, then renamed over `path`, as CTR_DRBG
+    /// seed files are.
+    pub fn write_seed_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        let mut buf = Zeroizing::new([0u8; MBEDTLS_HASH_DRBG_MAX_INPUT]);
+
+        self.generate(&mut buf[..])?;
+        write_file_atomic(path.as_ref(), Error::HashDrbgFileIoError, |f| {
+            f.write_all(&buf[..]).map_err(|_| Error::HashDrbgFileIoError)
+        })
+    }
+
+    /// This function reads and updates a seed file. The seed is added to this instance.
+    pub fn update_seed_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let mut buf = Zeroizing::new([0u8; MBEDTLS_HASH_DRBG_MAX_INPUT + 1]);
+
+        let f = File::open(path).map_err(|_| Error::HashDrbgFileIoError)?;
+        let n = read_file(f, &mut buf[..], Error::HashDrbgFileIoError)?;
+
+        if n > MBEDTLS_HASH_DRBG_MAX_INPUT {
+            return Err(Error::HashDrbgInputTooBig);
+        }
+        if n == 0 {
+            return Err(Error::HashDrbgFileIoError);
+        }
+        self.update(&buf[..n])?;
+
+        self.write_seed_file(path)
+    }
+}
+
+/*
+ * PR = True, SHA-256: NIST CAVP Hash_DRBG.rsp (CAVS 14.3,
+ * drbgvectors_pr_true), [SHA-256] with PersonalizationStringLen = 256 and
+ * AdditionalInputLen = 256, COUNT = 14, as in
+ * tests/data/hash_drbg/Hash_DRBG_pr_true.rsp. ENTROPY_PR is the entropy
+ * input, the nonce and the two prediction resistance inputs in the order
+ * they are consumed.
+ */
+const ENTROPY_PR: [u8; 112] = [
+      0x06, 0x6d, 0xc8, 0xce, 0x75, 0xb2, 0x89, 0x66,
+      0xa6, 0x85, 0x16, 0x3f, 0xe2, 0xa4, 0xd4, 0x27,
+      0xfb, 0xdb, 0x61, 0x66, 0x50, 0x61, 0x6b, 0xa2,
+      0x82, 0xfc, 0x33, 0x2b, 0x4e, 0x6f, 0x12, 0x20,
+      0x55, 0x9f, 0x7c, 0x64, 0x89, 0x70, 0x83, 0xec,
+      0x2d, 0x73, 0x70, 0xd9, 0xf0, 0xe5, 0x07, 0x1f,
+      0xff, 0x80, 0xb7, 0xd2, 0x6a, 0x05, 0xbc, 0x8a,
+      0x7a, 0xbe, 0x53, 0x28, 0x6b, 0x0e, 0xeb, 0x73,
+      0x3b, 0x71, 0x5a, 0x20, 0x5b, 0xfa, 0x4f, 0xf6,
+      0x37, 0x03, 0xde, 0xad, 0xb6, 0xea, 0x0e, 0xf4,
+      0xc7, 0x38, 0x32, 0x53, 0x46, 0x81, 0xed, 0xe3,
+      0x7e, 0x03, 0x84, 0x6d, 0x3c, 0x84, 0x17, 0x67,
+      0x29, 0x7d, 0x24, 0x6c, 0x68, 0x92, 0x41, 0xd2,
+      0xe7, 0x75, 0xbe, 0x7e, 0xc9, 0x96, 0x29, 0x3d ];
+
+const PERS_PR: [u8; 32] = [
+      0x88, 0x6f, 0x54, 0x9a, 0xad, 0x1a, 0xc6, 0x3d,
+      0x18, 0xcb, 0xcc, 0x66, 0x85, 0xda, 0xa2, 0xc2,
+      0xf7, 0x9e, 0xb0, 0x89, 0x4c, 0xb4, 0xae, 0xf1,
+      0xac, 0x54, 0x4f, 0xce, 0x57, 0xf1, 0x5e, 0x11 ];
+
+const ADDITIONAL_PR_0: [u8; 32] = [
+      0xb7, 0x21, 0x5f, 0x14, 0xac, 0x7b, 0xaf, 0xd0,
+      0xa9, 0x17, 0x72, 0xba, 0x22, 0xf7, 0x19, 0xaf,
+      0xbd, 0x20, 0xb3, 0x11, 0x63, 0x6c, 0x2b, 0x1e,
+      0x83, 0xe4, 0xa8, 0x23, 0x35, 0x3f, 0xc6, 0xea ];
+
+const ADDITIONAL_PR_1: [u8; 32] = [
+      0xce, 0xd3, 0x1f, 0x7e, 0x0d, 0xae, 0x5b, 0xb5,
+      0xc0, 0x43, 0xe2, 0x46, 0xb2, 0x94, 0x73, 0xe2,
+      0xfd, 0x39, 0x51, 0x2e, 0xad, 0x45, 0x69, 0xee,
+      0xe3, 0xe3, 0x80, 0x33, 0x14, 0xab, 0xa7, 0xa3 ];
+
+const RESULT_PR: [u8; 128] = [
+      0x60, 0xc2, 0x34, 0xcf, 0xaf, 0xb4, 0x68, 0x03,
+      0x3b, 0xf1, 0x95, 0xe5, 0x78, 0xce, 0x26, 0x6e,
+      0x14, 0x65, 0x32, 0x6a, 0x96, 0xa9, 0xe0, 0x3f,
+      0x8b, 0x89, 0x36, 0x70, 0xef, 0x62, 0x75, 0x4d,
+      0x5e, 0x80, 0xd5, 0x53, 0xa1, 0xf8, 0x49, 0x50,
+      0x20, 0x8b, 0x93, 0x43, 0x07, 0x9f, 0x2e, 0xf8,
+      0x56, 0xe9, 0xc5, 0x70, 0x61, 0x85, 0x97, 0xb5,
+      0xdc, 0x82, 0xa2, 0xda, 0xea, 0xa3, 0xfd, 0x9b,
+      0x2f, 0xd2, 0xa0, 0xd7, 0x1b, 0xc6, 0x29, 0x35,
+      0xcc, 0xb8, 0x3d, 0xa0, 0x67, 0x98, 0x05, 0xa0,
+      0xe3, 0x1e, 0xfe, 0xe4, 0xf0, 0xe5, 0x13, 0xb0,
+      0x83, 0x17, 0xfa, 0xca, 0x93, 0x5e, 0x38, 0x29,
+      0x48, 0xd2, 0x72, 0xdb, 0x76, 0x3e, 0x6d, 0xf3,
+      0x25, 0x10, 0xff, 0x1b, 0x99, 0xff, 0xf8, 0xc6,
+      0x0e, 0xb0, 0xdd, 0x29, 0x2e, 0xbc, 0xbb, 0xc8,
+      0x0a, 0x01, 0x6e, 0xd3, 0xb0, 0x0e, 0x4e, 0xab ];
+
+/*
+ * PR = False, SHA-512: a cross-implementation check, not a NIST vector;
+ * no copy of the [SHA-512] sections of Hash_DRBG.rsp is bundled. The
+ * input is the entropy of the HMAC_DRBG checkup routine (PR = False),
+ * with a reseed between instantiation and the two generate calls;
+ * RESULT_NOPR was computed with OpenSSL's HASH-DRBG.
+ */
+const ENTROPY_NOPR: [u8; 80] = [
+      0x48, 0xc1, 0x21, 0xb1, 0x87, 0x33, 0xaf, 0x15,
+      0xc2, 0x7e, 0x1d, 0xd9, 0xba, 0x66, 0xa9, 0xa8,
+      0x1a, 0x55, 0x79, 0xcd, 0xba, 0x0f, 0x5b, 0x65,
+      0x7e, 0xc5, 0x3c, 0x2b, 0x9e, 0x90, 0xbb, 0xf6,
+      0xbb, 0xb7, 0xc7, 0x77, 0x42, 0x80, 0x68, 0xfa,
+      0xd9, 0x97, 0x08, 0x91, 0xf8, 0x79, 0xb1, 0xaf,
+      0xe0, 0xff, 0xef, 0xda, 0xdb, 0x9c, 0xcf, 0x99,
+      0x05, 0x04, 0xd5, 0x68, 0xbd, 0xb4, 0xd8, 0x62,
+      0xcb, 0xe1, 0x7c, 0xcc, 0xe6, 0xe2, 0x2d, 0xfc,
+      0xab, 0x8b, 0x48, 0x04, 0xfd, 0x21, 0x42, 0x1a ];
+
+const RESULT_NOPR: [u8; 256] = [
+      0xa5, 0xb8, 0xf3, 0x1a, 0xcc, 0x93, 0xda, 0xa7,
+      0x81, 0x5c, 0x25, 0xe4, 0x42, 0xda, 0xe7, 0x78,
+      0x70, 0x9a, 0xad, 0xf9, 0x5d, 0xdb, 0x07, 0x8b,
+      0x79, 0x53, 0x04, 0x9a, 0x2f, 0xc7, 0xd8, 0xf8,
+      0x5a, 0x49, 0x6f, 0xc2, 0xdb, 0x73, 0x1e, 0xee,
+      0x12, 0x6a, 0xc5, 0x0f, 0x81, 0x52, 0xc6, 0xc5,
+      0x9e, 0xe8, 0xa6, 0xcd, 0xaa, 0x07, 0x27, 0xb3,
+      0x6e, 0x61, 0x92, 0x90, 0xc4, 0xc5, 0x18, 0xc8,
+      0xac, 0x0a, 0x72, 0xc9, 0xc0, 0xd2, 0x29, 0x26,
+      0x4f, 0x34, 0x0b, 0x66, 0x53, 0xbc, 0xab, 0x6a,
+      0x10, 0x13, 0x75, 0x79, 0x31, 0x7c, 0x92, 0x19,
+      0x7d, 0xd2, 0x5b, 0xca, 0xea, 0x3d, 0x1b, 0xd0,
+      0xce, 0x14, 0xb0, 0x67, 0x97, 0xa4, 0xe8, 0xd6,
+      0xe7, 0xef, 0xa7, 0x0d, 0x86, 0xad, 0x4a, 0x6e,
+      0x47, 0x9b, 0x3a, 0xbf, 0x9c, 0x12, 0xf7, 0xec,
+      0x8a, 0x33, 0xbe, 0x2e, 0x4e, 0x09, 0x9b, 0x2e,
+      0x04, 0xe8, 0x52, 0x8f, 0x7a, 0xe2, 0xb6, 0x77,
+      0x43, 0x4d, 0xa1, 0x6c, 0x21, 0x5c, 0x2c, 0x21,
+      0x41, 0xc3, 0x6a, 0xff, 0x68, 0x20, 0x72, 0x6c,
+      0x7c, 0x25, 0xde, 0x21, 0xf8, 0x28, 0x84, 0x0f,
+      0x1c, 0x29, 0xc4, 0x82, 0x12, 0x9b, 0x59, 0x34,
+      0x4b, 0xe7, 0x7d, 0xfa, 0x97, 0x06, 0x53, 0x84,
+      0xd8, 0x9c, 0x27, 0xd9, 0x81, 0xff, 0x38, 0x2e,
+      0x89, 0xae, 0xe7, 0x5b, 0xb4, 0x63, 0x92, 0x54,
+      0x1b, 0xca, 0x12, 0x31, 0xc5, 0x89, 0x6d, 0xf5,
+      0xb5, 0x9f, 0x87, 0x4f, 0x21, 0x27, 0x48, 0x66,
+      0x7f, 0xfa, 0x86, 0xa4, 0xa0, 0xfc, 0xaf, 0x75,
+      0x84, 0x57, 0xf8, 0x4c, 0x1d, 0xe7, 0xaa, 0x7d,
+      0xe7, 0x89, 0xc0, 0x39, 0x0c, 0x04, 0x34, 0x18,
+      0x02, 0xc4, 0x24, 0x5a, 0x88, 0x2a, 0xf7, 0x32,
+      0xb8, 0x82, 0x0d, 0xd0, 0x40, 0xb1, 0x64, 0xad,
+      0x4d, 0xb3, 0x0f, 0x95, 0x2d, 0x4f, 0x63, 0xfc ];
+
+/*
+ * "Entropy" from buffer
+ */
+fn hash_drbg_self_test_entropy(data: &'static [u8]) -> EntropyCallback<'static> {
+    let mut test_offset = 0;
+    Box::new(move |buf: &mut [u8]| {
+        buf.copy_from_slice(&data[test_offset..test_offset + buf.len()]);
+        test_offset += buf.len();
+        Ok(())
+    })
+}
+
+macro_rules! chk {
+    ($verbose:expr, $c:expr) => {
+        if $c.is_err() {
+            if $verbose {
+                println!("failed");
+            }
+            return 1;
+        }
+    };
+}
+
+/*
+ * Checkup routine
+ */
+
+/// The Hash_DRBG checkup routine, with SHA-256 (PR = True) and SHA-512
+/// (PR = False). Returns 0 on success and 1 on failure.
+pub fn self_test(verbose: bool) -> i32 {
+    let mut ctx = HashDrbg::new();
+    let mut buf = [0u8; RESULT_NOPR.len()];
+
+    /*
+     * PR = True
+     */
+    if verbose {
+        print!("  Hash_DRBG (SHA-256, PR = True) : ");
+    }
+
+    chk!(verbose, ctx.seed(MdType::Sha256, hash_drbg_self_test_entropy(&ENTROPY_PR), &PERS_PR));
+    ctx.set_prediction_resistance(MBEDTLS_HASH_DRBG_PR_ON);
+    chk!(verbose, ctx.generate_with_additional(&mut buf[..RESULT_PR.len()], &ADDITIONAL_PR_0));
+    chk!(verbose, ctx.generate_with_additional(&mut buf[..RESULT_PR.len()], &ADDITIONAL_PR_1));
+    chk!(verbose, if buf[..RESULT_PR.len()] == RESULT_PR { Ok(()) } else { Err(()) });
+
+    ctx.free();
+
+    if verbose {
+        println!("passed");
+    }
+
+    /*
+     * PR = False
+     */
+    if verbose {
+        print!("  Hash_DRBG (SHA-512, PR = False): ");
+    }
+
+    chk!(verbose, ctx.seed(MdType::Sha512, hash_drbg_self_test_entropy(&ENTROPY_NOPR), &[]));
+    chk!(verbose, ctx.reseed(&[]));
+    chk!(verbose, ctx.generate(&mut buf));
+    chk!(verbose, ctx.generate(&mut buf));
+    chk!(verbose, if buf == RESULT_NOPR { Ok(()) } else { Err(()) });
+
+    ctx.free();
+
+    if verbose {
+        println!("passed");
+    }
+
+    if verbose {
+        println!();
+    }
+
+    0
+}
@@ -0,0 +1,39 @@
+/*
+ * \file aes.h
+ *
+ * \brief   This file contains AES definitions and functions.
+ *
+ *          The Advanced Encryption Standard (AES) specifies a FIPS-approved
+ *          cryptographic algorithm that can be used to protect electronic
+ *          data.
+ *
+ *          The AES algorithm is a symmetric block cipher that can
+ *          encrypt and decrypt information. For more information, see
+ *          <em>FIPS Publication 197: Advanced Encryption Standard</em>.
+ */
+
+/// AES encryption.
+pub const MBEDTLS_AES_ENCRYPT: i32 = 1;
+/// AES decryption.
+pub const MBEDTLS_AES_DECRYPT: i32 = 0;
+
+/// Invalid key length.
+pub const MBEDTLS_ERR_AES_INVALID_KEY_LENGTH: i32 = -0x0020;
+/// Invalid input data.
+pub const MBEDTLS_ERR_AES_BAD_INPUT_DATA: i32 = -0x0021;
+
+/// The block size of AES, in bytes.
+pub const MBEDTLS_AES_BLOCKSIZE: usize = 16;
+
+/// The number of rounds for a 256-bit key.
+pub const MBEDTLS_AES_MAX_ROUNDS: usize = 14;
+
+/// The AES context-type definition.
+///
+/// The round keys are wiped when the context is dropped.
+#[derive(Clone)]
+pub struct AesContext {
+    pub(crate) nr: usize,          /* The number of rounds. */
+    pub(crate) rk: [u8; MBEDTLS_AES_BLOCKSIZE * (MBEDTLS_AES_MAX_ROUNDS + 1)],
+                                   /* The expanded round keys. */
+}
@@ -9,74 +9,146 @@
  * Recommendation for Random Number Generation Using Deterministic Random
  * Bit Generators</em>.
  *
- * The Mbed TLS implementation of CTR_DRBG uses AES-256 (default) or AES-128
- * (if \c MBEDTLS_CTR_DRBG_USE_128_BIT_KEY is enabled at compile time)
- * as the underlying block cipher, with a derivation function.
+ * The Mbed TLS implementation of CTR_DRBG uses AES-256 (default), AES-128
+ * (if \c MBEDTLS_CTR_DRBG_USE_128_BIT_KEY is enabled at compile time) or
+ * AES-192 (if the `ctr-drbg-192-bit-key` feature is enabled) as the
+ * underlying block cipher, with a derivation function.
  *
  * The security strength as defined in NIST SP 800-90A is
- * 128 bits when AES-128 is used (\c MBEDTLS_CTR_DRBG_USE_128_BIT_KEY enabled)
- * and 256 bits otherwise, provided that #MBEDTLS_CTR_DRBG_ENTROPY_LEN is
+ * 128 bits when AES-128 is used (\c MBEDTLS_CTR_DRBG_USE_128_BIT_KEY enabled),
+ * 192 bits when AES-192 is used and 256 bits otherwise, provided that #MBEDTLS_CTR_DRBG_ENTROPY_LEN is
  * kept at its default value (and not overridden in config.h) and that the
  * DRBG instance is set up with default parameters.
  * See the documentation of mbedtls_ctr_drbg_seed() for more
  * information.
- */
-
-use std::ffi::c_void;
-
-
-pub const MBEDTLS_ERR_CTR_DRBG_ENTROPY_SOURCE_FAILED:i32 = -0x0034;  /**< The entropy source failed. */
-pub const MBEDTLS_ERR_CTR_DRBG_REQUEST_TOO_BIG:i32 = -0x0036;  /**< The requested random buffer length is too big. */
-pub const MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG:i32 = -0x0038;  /**< The input (entropy + additional data) is too large. */
-pub const MBEDTLS_ERR_CTR_DRBG_FILE_IO_ERROR:i32 = -0x003A;  /**< Read or write error in file. */
-
-pub const MBEDTLS_CTR_DRBG_BLOCKSIZE:i32 = 16; /**< The block size used by the cipher. */
-
-pub const MBEDTLS_CTR_DRBG_KEYSIZE:i32 = 32;
-/**< The key size in bytes used by the cipher.
  *
- * Compile-time choice: 32 bytes (256 bits)
- * because \c MBEDTLS_CTR_DRBG_USE_128_BIT_KEY is disabled.
- */
-
-pub const MBEDTLS_CTR_DRBG_KEYBITS:i32 = ( MBEDTLS_CTR_DRBG_KEYSIZE * 8 ); /**< The key size for the DRBG operation, in bits. */
-pub const MBEDTLS_CTR_DRBG_SEEDLEN:i32 = ( MBEDTLS_CTR_DRBG_KEYSIZE + MBEDTLS_CTR_DRBG_BLOCKSIZE ); /**< The seed length, calculated as (counter + AES key). */
-
-
-pub const MBEDTLS_CTR_DRBG_ENTROPY_LEN: i32 = 48;
-/** This is 48 bytes because the entropy module uses SHA-512
- * (\c MBEDTLS_ENTROPY_FORCE_SHA256 is disabled).
+ * The seed file error codes have no Mbed TLS counterpart; they use
+ * low-level codes left unassigned by error.h (0x003B, 0x007B, 0x007D).
  */
 
+use std::os::raw::{c_int, c_uchar, c_void};
+use std::sync::Arc;
+
+use crate::rng::header::aes::AesContext;
+use crate::rng::header::error::Error;
+use crate::rng::header::platform_util::Zeroizing;
+use crate::rng::header::threading::ThreadingMutex;
+
+
+/// The entropy source failed.
+pub const MBEDTLS_ERR_CTR_DRBG_ENTROPY_SOURCE_FAILED:i32 = -0x0034;
+/// The requested random buffer length is too big.
+pub const MBEDTLS_ERR_CTR_DRBG_REQUEST_TOO_BIG:i32 = -0x0036;
+/// The input (entropy + additional data) is too large.
+pub const MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG:i32 = -0x0038;
+/// Read or write error in file.
+pub const MBEDTLS_ERR_CTR_DRBG_FILE_IO_ERROR:i32 = -0x003A;
+/// The seed file ends before its integrity tag.
+pub const MBEDTLS_ERR_CTR_DRBG_SEED_FILE_TRUNCATED:i32 = -0x003B;
+/// The seed file integrity tag does not match its contents.
+pub const MBEDTLS_ERR_CTR_DRBG_SEED_FILE_CORRUPTED:i32 = -0x007B;
+/// The seed file has an unsupported format version.
+pub const MBEDTLS_ERR_CTR_DRBG_SEED_FILE_BAD_VERSION:i32 = -0x007D;
+
+/// The block size used by the cipher.
+pub const MBEDTLS_CTR_DRBG_BLOCKSIZE:usize = 16;
+
+/// The key size in bytes used by the cipher.
+///
+/// Compile-time choice: 16 bytes (128 bits)
+/// because the `ctr-drbg-128-bit-key` feature
+/// (\c MBEDTLS_CTR_DRBG_USE_128_BIT_KEY) is enabled.
+/// It takes precedence over `ctr-drbg-192-bit-key`.
+#[cfg(feature = "ctr-drbg-128-bit-key")]
+pub const MBEDTLS_CTR_DRBG_KEYSIZE:usize = 16;
+
+/// The key size in bytes used by the cipher.
+///
+/// Compile-time choice: 24 bytes (192 bits)
+/// because the `ctr-drbg-192-bit-key` feature is enabled
+/// and the `ctr-drbg-128-bit-key` feature is disabled.
+#[cfg(all(feature = "ctr-drbg-192-bit-key", not(feature = "ctr-drbg-128-bit-key")))]
+pub const MBEDTLS_CTR_DRBG_KEYSIZE:usize = 24;
+
+/// The key size in bytes used by the cipher.
+///
+/// Compile-time choice: 32 bytes (256 bits)
+/// because neither the `ctr-drbg-128-bit-key` feature
+/// (\c MBEDTLS_CTR_DRBG_USE_128_BIT_KEY) nor the
+/// `ctr-drbg-192-bit-key` feature is enabled.
+#[cfg(not(any(feature = "ctr-drbg-128-bit-key", feature = "ctr-drbg-192-bit-key")))]
+pub const MBEDTLS_CTR_DRBG_KEYSIZE:usize = 32;
+
+/// The key size for the DRBG operation, in bits.
+pub const MBEDTLS_CTR_DRBG_KEYBITS:usize = MBEDTLS_CTR_DRBG_KEYSIZE * 8;
+/// The seed length, calculated as (counter + AES key).
+pub const MBEDTLS_CTR_DRBG_SEEDLEN:usize = MBEDTLS_CTR_DRBG_KEYSIZE + MBEDTLS_CTR_DRBG_BLOCKSIZE;
+
+
+/// The amount of entropy used per seed by default, in bytes.
+///
+/// This is 48 bytes because the entropy module uses SHA-512
+/// (\c MBEDTLS_ENTROPY_FORCE_SHA256 is disabled).
+pub const MBEDTLS_CTR_DRBG_ENTROPY_LEN: usize = 48;
+
+/// The interval before reseed is performed by default.
 pub const MBEDTLS_CTR_DRBG_RESEED_INTERVAL:i32 = 10000;
-/**< The interval before reseed is performed by default. */
-
-pub const MBEDTLS_CTR_DRBG_MAX_INPUT:i32 = 256;
-/**< The maximum number of additional input Bytes. */
 
-pub const MBEDTLS_CTR_DRBG_MAX_REQUEST:i32 = 1024;
-/**< The maximum number of requested Bytes per call. */
-
-pub const MBEDTLS_CTR_DRBG_MAX_SEED_INPUT:i32 = 384;
-/**< The maximum size of seed or reseed buffer in bytes. */
-
-pub const MBEDTLS_CTR_DRBG_PR_OFF:i32 = 0;
-/**< Prediction resistance is disabled. */
-pub const MBEDTLS_CTR_DRBG_PR_ON:i32 = 1;
-/**< Prediction resistance is enabled. */
-
-pub const MBEDTLS_CTR_DRBG_ENTROPY_NONCE_LEN:i32 = 0;
-/**< Prediction resistance is disabled. */
-
-pub fn f_entropy(data: Option<*mut c_void>, output: &mut [u8], len: usize, olen: usize) -> i32; 
-pub type mbedtls_entropy_f_source_ptr = fn(data: Option<*mut c_void>, output: &mut [u8], len: usize, olen: usize)->i32;
-pub f_ptr:mbedtls_entropy_f_source_ptr = f_entropy;
-
-pub struct mbedtls_ctr_drbg_context{
-pub struct {
-
-    pub counter[u8, 16];  /*!< The counter (V). */
-    pub reseed_counter:i32 ;         /*!< The reseed counter.
+/// The maximum number of additional input Bytes.
+pub const MBEDTLS_CTR_DRBG_MAX_INPUT:usize = 256;
+
+/// The maximum number of requested Bytes per call.
+pub const MBEDTLS_CTR_DRBG_MAX_REQUEST:usize = 1024;
+
+/// The maximum size of seed or reseed buffer in bytes.
+pub const MBEDTLS_CTR_DRBG_MAX_SEED_INPUT:usize = 384;
+
+/// The magic bytes a seed file starts with.
+pub const MBEDTLS_CTR_DRBG_SEED_FILE_MAGIC: [u8; 4] = *b"CDSF";
+/// The seed file format version written by write_seed_file().
+pub const MBEDTLS_CTR_DRBG_SEED_FILE_VERSION: u8 = 1;
+/// The length of the seed file header: magic, version and seed length.
+pub const MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN: usize = 7;
+/// The length of the seed file integrity tag (SHA-256).
+pub const MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN: usize = 32;
+
+/// Prediction resistance is disabled.
+pub const MBEDTLS_CTR_DRBG_PR_OFF:bool = false;
+/// Prediction resistance is enabled.
+pub const MBEDTLS_CTR_DRBG_PR_ON:bool = true;
+
+/// The amount of entropy used per seed as a nonce by default.
+pub const MBEDTLS_CTR_DRBG_ENTROPY_NONCE_LEN:usize = 0;
+
+/// A source of entropy for seeding and reseeding a DRBG.
+///
+/// It is implemented for every closure of the same shape and for boxed
+/// sources, so a DRBG can own a source outright or borrow one through a
+/// closure such as `|buf| source.fill(buf)`. LegacyEntropySource adapts
+/// a C-style f_entropy callback.
+pub trait EntropySource {
+    /// This function fills the whole buffer with entropy.
+    fn fill(&mut self, buf: &mut [u8]) -> Result<(), Error>;
+}
+
+/// The entropy callback, as stored by a DRBG context. The lifetime is
+/// that of anything the source borrows.
+pub type EntropyCallback<'a> = Box<dyn EntropySource + Send + 'a>;
+
+/// The C-style entropy callback, as passed to mbedtls_ctr_drbg_seed().
+/// It fills `len` bytes at `output` and returns 0 on success.
+pub type EntropyFn = unsafe extern "C" fn(p_entropy: *mut c_void, output: *mut c_uchar, len: usize) -> c_int;
+
+/// An entropy source that calls a C-style callback with its context.
+pub struct LegacyEntropySource {
+    pub(crate) f_entropy: EntropyFn,       /* The entropy callback function. */
+    pub(crate) p_entropy: *mut c_void,     /* The context passed to the entropy callback. */
+}
+
+/// The CTR_DRBG context structure.
+pub struct CtrDrbg<'a> {
+    pub(crate) counter: [u8; MBEDTLS_CTR_DRBG_BLOCKSIZE],  /* The counter (V). */
+    pub(crate) reseed_counter: i32,         /* The reseed counter.
                                  * This is the number of requests that have
                                  * been made since the last (re)seeding,
                                  * minus one.
@@ -84,31 +156,74 @@ pub struct {
                                  * contains the amount of entropy in bytes
                                  * to use as a nonce for the initial seeding,
                                  * or -1 if no nonce length has been explicitly
-                                 * set (see mbedtls_ctr_drbg_set_nonce_len()).
+                                 * set (see set_nonce_len()).
                                  */
-    pub prediction_resistance:i32;  /*!< This determines whether prediction
+    pub(crate) prediction_resistance: bool,  /* This determines whether prediction
                                      resistance is enabled, that is
                                      whether to systematically reseed before
                                      each random generation. */
-    pub entropy_len: usize;         /*!< The amount of entropy grabbed on each
+    pub(crate) entropy_len: usize,         /* The amount of entropy grabbed on each
                                      seed or reseed operation, in bytes. */
-    pub reseed_interval: i32;        /*!< The reseed interval.
+    pub(crate) reseed_interval: i32,        /* The reseed interval.
                                  * This is the maximum number of requests
                                  * that can be made between reseedings. */
-
-    pub aes_ctx: mbedtls_aes_context;        /*!< The AES context. */
+    pub(crate) use_df: bool,                /* This determines whether seed material
+                                     goes through the derivation function.
+                                     It is fixed when the context is created
+                                     (see new_no_df()). */
+    pub(crate) pid: u32,                    /* The process that last seeded or
+                                     reseeded the context. Any other value
+                                     means the process has forked since,
+                                     and forces a reseed before the next
+                                     generation. */
+
+    pub(crate) aes_ctx: AesContext,        /* The AES context. */
 
     /*
      * Callbacks (Entropy)
      */
-    //int (*f_entropy)(void *, unsigned char *, size_t);   
-    pub fptr = f_ptr(data: Option<*mut c_void>, output: &mut [u8], len: usize, olen: usize)->i32;
-
-    fn (*f_entropy)(void *, unsigned char *, usize) -> i32;  /*!< The entropy callback function. */
-
-    pub p_entropy: *mut c_void;    /*!< The context for the entropy function. */
-   
-};
-}mbedtls_ctr_drbg_context;
-
-
+    pub(crate) f_entropy: Option<EntropyCallback<'a>>,  /* The entropy callback function. */
+}
+
+/// A CTR_DRBG context shared between threads.
+///
+/// This is the counterpart of a context used through mbedtls_ctr_drbg_random()
+/// with MBEDTLS_THREADING_C: every operation locks the context for its whole
+/// duration. Cloning the handle shares the same generator.
+#[derive(Clone)]
+pub struct SharedCtrDrbg {
+    pub(crate) inner: Arc<ThreadingMutex<CtrDrbg<'static>>>,  /* The protected context. */
+}
+
+/// A CTR_DRBG context read as an endless stream of random data.
+///
+/// It implements io::Read and io::BufRead, so the output can go through
+/// io::copy() into a file or a socket. Output is generated at most
+/// MBEDTLS_CTR_DRBG_MAX_REQUEST bytes per request, each request being
+/// subject to the reseed interval and to prediction resistance. Errors of
+/// the generator come out as io::Error; Error::from_io() gives back the
+/// mbedTLS error and its code.
+pub struct CtrDrbgReader<'a> {
+    pub(crate) ctx: CtrDrbg<'a>,  /* The generator. */
+    pub(crate) buf: Zeroizing<[u8; MBEDTLS_CTR_DRBG_MAX_REQUEST]>,  /* Output of the last
+                                     request. */
+    pub(crate) pos: usize,        /* The offset of the unread output in buf,
+                                     MBEDTLS_CTR_DRBG_MAX_REQUEST when it is
+                                     all read. */
+}
+
+/// The outcome of one known-answer test of the CTR_DRBG checkup routine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfTestCase {
+    pub name: &'static str,     /* The known-answer test, such as "PR = TRUE". */
+    pub expected: Vec<u8>,      /* The output the test vector expects. */
+    pub actual: Vec<u8>,        /* The output produced, empty if a call failed. */
+    pub error: Option<Error>,   /* The error of the call that failed, if any. */
+}
+
+/// The report of the CTR_DRBG checkup routine: one case per known-answer
+/// test, in the order they ran.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelfTestReport {
+    pub cases: Vec<SelfTestCase>,  /* The known-answer tests. */
+}
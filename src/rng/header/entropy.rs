@@ -0,0 +1,56 @@
+/*
+ * \file entropy.h
+ *
+ * \brief Entropy accumulator implementation
+ */
+
+use crate::rng::header::error::Error;
+use crate::rng::header::sha512::Sha512Context;
+
+/// Critical entropy source failure.
+pub const MBEDTLS_ERR_ENTROPY_SOURCE_FAILED:i32 = -0x003C;
+/// No more sources can be added.
+pub const MBEDTLS_ERR_ENTROPY_MAX_SOURCES:i32 = -0x003E;
+/// No sources have been added to poll.
+pub const MBEDTLS_ERR_ENTROPY_NO_SOURCES_DEFINED:i32 = -0x0040;
+/// No strong sources have been added to poll.
+pub const MBEDTLS_ERR_ENTROPY_NO_STRONG_SOURCE:i32 = -0x003D;
+/// Read/write error in file.
+pub const MBEDTLS_ERR_ENTROPY_FILE_IO_ERROR:i32 = -0x003F;
+
+/// Maximum number of sources supported.
+pub const MBEDTLS_ENTROPY_MAX_SOURCES: usize = 20;
+
+/// Maximum amount requested from entropy sources.
+pub const MBEDTLS_ENTROPY_MAX_GATHER: usize = 128;
+
+/// Block size of entropy accumulator (SHA-512).
+pub const MBEDTLS_ENTROPY_BLOCK_SIZE: usize = 64;
+
+/// Source id used for entropy added through update_manual().
+pub const MBEDTLS_ENTROPY_SOURCE_MANUAL: usize = MBEDTLS_ENTROPY_MAX_SOURCES;
+
+/// Entropy source is strong.
+pub const MBEDTLS_ENTROPY_SOURCE_STRONG: bool = true;
+/// Entropy source is weak.
+pub const MBEDTLS_ENTROPY_SOURCE_WEAK: bool = false;
+
+/// Entropy poll callback. It writes at most `output.len()` bytes and
+/// returns how many it actually wrote.
+pub type EntropyPollCallback = Box<dyn FnMut(&mut [u8]) -> Result<usize, Error> + Send>;
+
+/// Entropy source state.
+pub struct EntropySourceState {
+    pub(crate) f_source: EntropyPollCallback,  /* The entropy source callback. */
+    pub(crate) size: usize,                    /* Amount received in bytes. */
+    pub(crate) threshold: usize,               /* Minimum bytes required before release. */
+    pub(crate) strong: bool,                   /* Is the source strong? */
+}
+
+/// Entropy context structure.
+pub struct EntropyContext {
+    pub(crate) accumulator_started: bool,      /* false after init.
+                                                  true after the first update. */
+    pub(crate) accumulator: Sha512Context,
+    pub(crate) source: Vec<EntropySourceState>,
+}
@@ -0,0 +1,59 @@
+/*
+ * \file entropy_health.h
+ *
+ * \brief Continuous health tests for entropy sources
+ *
+ * This module implements the Repetition Count Test and the Adaptive
+ * Proportion Test of <em>NIST SP 800-90B: Recommendation for the Entropy
+ * Sources Used for Random Bit Generation</em>, section 4.4, on the bytes
+ * an entropy source delivers. Each byte is one sample.
+ *
+ * Mbed TLS has no counterpart to this module.
+ */
+
+/// The false positive probability of each test, alpha = 2^-20.
+pub const MBEDTLS_ENTROPY_HEALTH_ALPHA_LOG2: u32 = 20;
+
+/// The Adaptive Proportion Test window size for non-binary samples.
+pub const MBEDTLS_ENTROPY_HEALTH_APT_WINDOW: u32 = 512;
+
+/// The cutoffs of the continuous health tests.
+///
+/// from_min_entropy() derives them from the min-entropy claimed per
+/// sample; they can also be set directly, for example from the values an
+/// entropy assessment produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HealthTestConfig {
+    pub rct_cutoff: u32,   /* A run of this many identical samples fails
+                              the Repetition Count Test. */
+    pub apt_cutoff: u32,   /* This many occurrences of the first sample of
+                              a window fail the Adaptive Proportion Test. */
+    pub apt_window: u32,   /* The Adaptive Proportion Test window size. */
+}
+
+/// One of the continuous health tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthTest {
+    /// The Repetition Count Test (SP 800-90B &sect;4.4.1).
+    RepetitionCount,
+    /// The Adaptive Proportion Test (SP 800-90B &sect;4.4.2).
+    AdaptiveProportion,
+}
+
+/// An entropy source whose output goes through the continuous health
+/// tests.
+///
+/// Once a test has failed, every further request fails with
+/// Error::CtrDrbgEntropySourceFailed, so that a DRBG seeded or reseeded
+/// from it refuses to proceed, until reset() is called.
+pub struct HealthTestedSource<E> {
+    pub(crate) source: E,                  /* The wrapped entropy source. */
+    pub(crate) config: HealthTestConfig,   /* The test cutoffs. */
+    pub(crate) started: bool,              /* Whether a sample has been seen. */
+    pub(crate) rct_sample: u8,             /* The sample of the current run. */
+    pub(crate) rct_count: u32,             /* The length of the current run. */
+    pub(crate) apt_sample: u8,             /* The first sample of the window. */
+    pub(crate) apt_count: u32,             /* Its occurrences in the window. */
+    pub(crate) apt_seen: u32,              /* Samples seen in the window. */
+    pub(crate) failure: Option<HealthTest>,  /* The test that failed, if any. */
+}
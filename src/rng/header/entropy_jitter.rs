@@ -0,0 +1,51 @@
+/*
+ * \file entropy_jitter.h
+ *
+ * \brief CPU-jitter entropy source
+ *
+ * A software entropy source for platforms where neither getrandom(2) nor
+ * /dev/urandom is reachable. It times a memory-access and compute loop
+ * with the monotonic clock: caches, pipelines, interrupts and frequency
+ * scaling make the execution time vary. Every raw timing sample goes
+ * through the continuous health tests, and the samples are conditioned
+ * with SHA-512.
+ *
+ * Mbed TLS has no counterpart to this module.
+ */
+
+use crate::rng::header::entropy_health::HealthTestedSource;
+
+/// Minimum for the jitter source in an entropy accumulator, in bytes.
+pub const MBEDTLS_ENTROPY_MIN_JITTER: usize = 32;
+
+/// The min-entropy credited to one raw timing sample, in bits. The
+/// health test cutoffs are derived from it.
+pub const MBEDTLS_JITTER_SAMPLE_MIN_ENTROPY: f64 = 0.5;
+
+/// The number of raw timing samples conditioned into one 64-byte output
+/// block: 512 bits at MBEDTLS_JITTER_SAMPLE_MIN_ENTROPY bits per sample.
+pub const MBEDTLS_JITTER_SAMPLES_PER_BLOCK: usize = 1024;
+
+/// The size of the memory the noise loop walks through, in bytes.
+pub const MBEDTLS_JITTER_MEMORY_SIZE: usize = 64 * 1024;
+
+/// The timer the noise loop is timed with. It returns a monotonic count
+/// of nanoseconds, cycles or any finer unit.
+pub type JitterClock = fn() -> u64;
+
+/// The raw noise source: one sample per timing of the noise loop.
+pub(crate) struct JitterNoise {
+    pub(crate) memory: Vec<u8>,       /* The memory the loop reads and writes. */
+    pub(crate) position: usize,       /* The next memory location to access. */
+    pub(crate) last_delta: u64,       /* The previous timing, in clock units. */
+    pub(crate) clock: JitterClock,    /* The timer. */
+}
+
+/// The CPU-jitter entropy source.
+///
+/// It can seed a DRBG directly, like any other EntropySource, or be
+/// registered in an entropy accumulator through into_poll_callback().
+/// A health test failure makes every further request fail.
+pub struct JitterEntropy {
+    pub(crate) noise: HealthTestedSource<JitterNoise>,  /* The health-tested raw samples. */
+}
@@ -0,0 +1,17 @@
+/*
+ * \file entropy_poll.h
+ *
+ * \brief Platform-specific and custom entropy polling functions
+ */
+
+/// Minimum for the platform source, in bytes.
+pub const MBEDTLS_ENTROPY_MIN_PLATFORM: usize = 32;
+
+/// The platform entropy source, the counterpart of
+/// mbedtls_platform_entropy_poll().
+///
+/// It uses the getrandom(2) system call on Linux and falls back to
+/// /dev/urandom where the call is unavailable. It is the source a DRBG
+/// uses when it is seeded through seed_default().
+#[derive(Clone, Copy, Default)]
+pub struct PlatformEntropy;
@@ -4,6 +4,82 @@
  * \brief Error to string translation
  */
 
-pub const MBEDTLS_ERR_ERROR_GENERIC_ERROR:i32 = -0x0001;  /**< Generic error */
-pub const MBEDTLS_ERR_ERROR_CORRUPTION_DETECTED:i32 = -0x006E; /**< This is a bug in the library */
+/// Generic error
+pub const MBEDTLS_ERR_ERROR_GENERIC_ERROR:i32 = -0x0001;
+/// This is a bug in the library
+pub const MBEDTLS_ERR_ERROR_CORRUPTION_DETECTED:i32 = -0x006E;
 
+/// The error type returned by every fallible function of the crate.
+///
+/// Each variant corresponds to one `MBEDTLS_ERR_*` code; codes that have
+/// no variant of their own are carried verbatim in `Other`, so that
+/// converting a code to an `Error` and back always yields the same code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// MBEDTLS_ERR_CTR_DRBG_ENTROPY_SOURCE_FAILED
+    CtrDrbgEntropySourceFailed,
+    /// MBEDTLS_ERR_CTR_DRBG_REQUEST_TOO_BIG
+    CtrDrbgRequestTooBig,
+    /// MBEDTLS_ERR_CTR_DRBG_INPUT_TOO_BIG
+    CtrDrbgInputTooBig,
+    /// MBEDTLS_ERR_CTR_DRBG_FILE_IO_ERROR
+    CtrDrbgFileIoError,
+    /// MBEDTLS_ERR_CTR_DRBG_SEED_FILE_TRUNCATED
+    CtrDrbgSeedFileTruncated,
+    /// MBEDTLS_ERR_CTR_DRBG_SEED_FILE_CORRUPTED
+    CtrDrbgSeedFileCorrupted,
+    /// MBEDTLS_ERR_CTR_DRBG_SEED_FILE_BAD_VERSION
+    CtrDrbgSeedFileBadVersion,
+
+    /// MBEDTLS_ERR_HMAC_DRBG_REQUEST_TOO_BIG
+    HmacDrbgRequestTooBig,
+    /// MBEDTLS_ERR_HMAC_DRBG_INPUT_TOO_BIG
+    HmacDrbgInputTooBig,
+    /// MBEDTLS_ERR_HMAC_DRBG_FILE_IO_ERROR
+    HmacDrbgFileIoError,
+    /// MBEDTLS_ERR_HMAC_DRBG_ENTROPY_SOURCE_FAILED
+    HmacDrbgEntropySourceFailed,
+
+    /// MBEDTLS_ERR_HASH_DRBG_REQUEST_TOO_BIG
+    HashDrbgRequestTooBig,
+    /// MBEDTLS_ERR_HASH_DRBG_INPUT_TOO_BIG
+    HashDrbgInputTooBig,
+    /// MBEDTLS_ERR_HASH_DRBG_FILE_IO_ERROR
+    HashDrbgFileIoError,
+    /// MBEDTLS_ERR_HASH_DRBG_ENTROPY_SOURCE_FAILED
+    HashDrbgEntropySourceFailed,
+
+    /// MBEDTLS_ERR_AES_INVALID_KEY_LENGTH
+    AesInvalidKeyLength,
+    /// MBEDTLS_ERR_AES_BAD_INPUT_DATA
+    AesBadInputData,
+
+    /// MBEDTLS_ERR_MD_BAD_INPUT_DATA
+    MdBadInputData,
+
+    /// MBEDTLS_ERR_ENTROPY_SOURCE_FAILED
+    EntropySourceFailed,
+    /// MBEDTLS_ERR_ENTROPY_MAX_SOURCES
+    EntropyMaxSources,
+    /// MBEDTLS_ERR_ENTROPY_NO_SOURCES_DEFINED
+    EntropyNoSourcesDefined,
+    /// MBEDTLS_ERR_ENTROPY_NO_STRONG_SOURCE
+    EntropyNoStrongSource,
+    /// MBEDTLS_ERR_ENTROPY_FILE_IO_ERROR
+    EntropyFileIoError,
+
+    /// MBEDTLS_ERR_THREADING_FEATURE_UNAVAILABLE
+    ThreadingFeatureUnavailable,
+    /// MBEDTLS_ERR_THREADING_BAD_INPUT_DATA
+    ThreadingBadInputData,
+    /// MBEDTLS_ERR_THREADING_MUTEX_ERROR
+    ThreadingMutexError,
+
+    /// MBEDTLS_ERR_ERROR_GENERIC_ERROR
+    GenericError,
+    /// MBEDTLS_ERR_ERROR_CORRUPTION_DETECTED
+    CorruptionDetected,
+
+    /// Any other mbedTLS error code.
+    Other(i32),
+}
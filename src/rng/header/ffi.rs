@@ -0,0 +1,27 @@
+/*
+ * \file ffi.h
+ *
+ * \brief C interface of the CTR_DRBG module
+ *
+ * The functions of the ffi module have the names and signatures of
+ * mbedTLS's ctr_drbg.h, so that C code written against mbedTLS links
+ * against the cdylib or staticlib of this crate unchanged. The matching
+ * C header, include/mbedtls/ctr_drbg.h, is generated from this module,
+ * src/rng/ffi.rs and the CTR_DRBG header by build.rs.
+ */
+
+use crate::rng::header::ctr_drbg::CtrDrbg;
+
+/// The CTR_DRBG context as seen from C.
+///
+/// Unlike in mbedTLS, the C structure only holds a pointer to the Rust
+/// context, which mbedtls_ctr_drbg_init() allocates and
+/// mbedtls_ctr_drbg_free() releases. C code that only goes through the
+/// functions, and never reads the fields, works unchanged.
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub struct mbedtls_ctr_drbg_context {
+    pub(crate) ctx: *mut CtrDrbg<'static>,  /* The Rust context, or NULL
+                                               after free; indeterminate
+                                               before init. */
+}
@@ -0,0 +1,76 @@
+/*
+ * \file hash_drbg.h
+ *
+ * \brief The Hash_DRBG pseudorandom generator.
+ *
+ * This module implements the Hash_DRBG pseudorandom generator described
+ * in <em>NIST SP 800-90A: Recommendation for Random Number Generation Using
+ * Deterministic Random Bit Generators</em>, section 10.1.1.
+ *
+ * Mbed TLS has no Hash_DRBG module; the error codes below use low-level
+ * codes that neither the 2.x nor the 3.x error.h assigns (0x0077-0x0079
+ * and 0x007E). 0x0076 is taken by MBEDTLS_ERR_SHA3_BAD_INPUT_DATA in 3.x.
+ */
+
+use crate::rng::header::ctr_drbg::EntropyCallback;
+use crate::rng::header::md::MdContext;
+
+/// Too many random requested in single call.
+pub const MBEDTLS_ERR_HASH_DRBG_REQUEST_TOO_BIG:i32 = -0x0077;
+/// Input too large (Entropy + additional).
+pub const MBEDTLS_ERR_HASH_DRBG_INPUT_TOO_BIG:i32 = -0x0078;
+/// Read/write error in file.
+pub const MBEDTLS_ERR_HASH_DRBG_FILE_IO_ERROR:i32 = -0x0079;
+/// The entropy source failed.
+pub const MBEDTLS_ERR_HASH_DRBG_ENTROPY_SOURCE_FAILED:i32 = -0x007E;
+
+/// Interval before reseed is performed by default.
+pub const MBEDTLS_HASH_DRBG_RESEED_INTERVAL:i32 = 10000;
+
+/// Maximum number of additional input bytes.
+pub const MBEDTLS_HASH_DRBG_MAX_INPUT:usize = 256;
+
+/// Maximum number of requested bytes per call.
+pub const MBEDTLS_HASH_DRBG_MAX_REQUEST:usize = 1024;
+
+/// Maximum size of (re)seed buffer.
+pub const MBEDTLS_HASH_DRBG_MAX_SEED_INPUT:usize = 384;
+
+/// The seed length for SHA-224 and SHA-256, in bytes (440 bits).
+pub const MBEDTLS_HASH_DRBG_SEEDLEN_256:usize = 55;
+
+/// The seed length for SHA-384 and SHA-512, in bytes (888 bits).
+pub const MBEDTLS_HASH_DRBG_SEEDLEN_512:usize = 111;
+
+/// The largest seed length, in bytes.
+pub const MBEDTLS_HASH_DRBG_MAX_SEEDLEN:usize = MBEDTLS_HASH_DRBG_SEEDLEN_512;
+
+/// No prediction resistance.
+pub const MBEDTLS_HASH_DRBG_PR_OFF:bool = false;
+/// Prediction resistance enabled.
+pub const MBEDTLS_HASH_DRBG_PR_ON:bool = true;
+
+/// The Hash_DRBG context structure.
+pub struct HashDrbg<'a> {
+    /* Working state */
+    pub(crate) md_ctx: Option<MdContext>,   /* Hash context, set up by seed(). */
+    pub(crate) v: [u8; MBEDTLS_HASH_DRBG_MAX_SEEDLEN],  /* V in the spec. */
+    pub(crate) c: [u8; MBEDTLS_HASH_DRBG_MAX_SEEDLEN],  /* C in the spec. */
+    pub(crate) seedlen: usize,              /* The seed length of the selected
+                                     hash, in bytes. */
+    pub(crate) reseed_counter: i32,         /* The reseed counter. */
+
+    /* Administrative state */
+    pub(crate) entropy_len: usize,         /* The amount of entropy grabbed on each
+                                     (re)seed operation, in bytes. */
+    pub(crate) prediction_resistance: bool,  /* This determines whether prediction
+                                     resistance is enabled, that is
+                                     whether to systematically reseed before
+                                     each random generation. */
+    pub(crate) reseed_interval: i32,        /* The reseed interval. */
+
+    /*
+     * Callbacks (Entropy)
+     */
+    pub(crate) f_entropy: Option<EntropyCallback<'a>>,  /* The entropy callback function. */
+}
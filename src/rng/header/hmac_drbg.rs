@@ -0,0 +1,64 @@
+/*
+ * \file hmac_drbg.h
+ *
+ * \brief The HMAC_DRBG pseudorandom generator.
+ *
+ * This module implements the HMAC_DRBG pseudorandom generator described
+ * in <em>NIST SP 800-90A: Recommendation for Random Number Generation Using
+ * Deterministic Random Bit Generators</em>.
+ */
+
+use crate::rng::header::ctr_drbg::EntropyCallback;
+use crate::rng::header::md::MdContext;
+use crate::rng::header::md::MBEDTLS_MD_MAX_SIZE;
+
+/// Too many random requested in single call.
+pub const MBEDTLS_ERR_HMAC_DRBG_REQUEST_TOO_BIG:i32 = -0x0003;
+/// Input too large (Entropy + additional).
+pub const MBEDTLS_ERR_HMAC_DRBG_INPUT_TOO_BIG:i32 = -0x0005;
+/// Read/write error in file.
+pub const MBEDTLS_ERR_HMAC_DRBG_FILE_IO_ERROR:i32 = -0x0007;
+/// The entropy source failed.
+pub const MBEDTLS_ERR_HMAC_DRBG_ENTROPY_SOURCE_FAILED:i32 = -0x0009;
+
+/// Interval before reseed is performed by default.
+pub const MBEDTLS_HMAC_DRBG_RESEED_INTERVAL:i32 = 10000;
+
+/// Maximum number of additional input bytes.
+pub const MBEDTLS_HMAC_DRBG_MAX_INPUT:usize = 256;
+
+/// Maximum number of requested bytes per call.
+pub const MBEDTLS_HMAC_DRBG_MAX_REQUEST:usize = 1024;
+
+/// Maximum size of (re)seed buffer.
+pub const MBEDTLS_HMAC_DRBG_MAX_SEED_INPUT:usize = 384;
+
+/// No prediction resistance.
+pub const MBEDTLS_HMAC_DRBG_PR_OFF:bool = false;
+/// Prediction resistance enabled.
+pub const MBEDTLS_HMAC_DRBG_PR_ON:bool = true;
+
+/// The HMAC_DRBG context structure.
+pub struct HmacDrbg<'a> {
+    /* Working state: the key K is not stored explicitly,
+     * but is implied by the HMAC context */
+    pub(crate) md_ctx: Option<MdContext>,   /* HMAC context (inc. K), set up by seed(). */
+    pub(crate) v: [u8; MBEDTLS_MD_MAX_SIZE],  /* V in the spec. */
+    pub(crate) reseed_counter: i32,         /* The reseed counter. */
+
+    /* Administrative state */
+    pub(crate) entropy_len: usize,         /* The amount of entropy grabbed on each
+                                     (re)seed operation, in bytes. */
+    pub(crate) prediction_resistance: bool,  /* This determines whether prediction
+                                     resistance is enabled, that is
+                                     whether to systematically reseed before
+                                     each random generation. */
+    pub(crate) reseed_interval: i32,        /* The reseed interval. */
+
+    /*
+     * Callbacks (Entropy)
+     */
+    pub(crate) f_entropy: Option<EntropyCallback<'a>>,  /* The entropy callback function.
+                                     None for instances seeded by seed_buf(),
+                                     which never reseed. */
+}
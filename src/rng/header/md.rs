@@ -0,0 +1,51 @@
+/*
+ * \file md.h
+ *
+ * \brief This file contains the generic message-digest wrapper.
+ *
+ * Only the SHA-2 family is available; the wrapper is what HMAC_DRBG
+ * uses to compute its HMACs.
+ */
+
+use crate::rng::header::sha256::Sha256Context;
+use crate::rng::header::sha512::Sha512Context;
+
+/// Bad input parameters to function.
+pub const MBEDTLS_ERR_MD_BAD_INPUT_DATA:i32 = -0x5100;
+
+/// The longest message digest supported, in bytes (SHA-512).
+pub const MBEDTLS_MD_MAX_SIZE: usize = 64;
+
+/// The largest block size of the supported digests, in bytes (SHA-512).
+pub const MBEDTLS_MD_MAX_BLOCK_SIZE: usize = 128;
+
+/// Supported message digests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MdType {
+    /// The SHA-224 message digest.
+    Sha224,
+    /// The SHA-256 message digest.
+    Sha256,
+    /// The SHA-384 message digest.
+    Sha384,
+    /// The SHA-512 message digest.
+    Sha512,
+}
+
+/// The hash context of the selected digest.
+#[derive(Clone)]
+pub(crate) enum MdDigest {
+    Sha256(Sha256Context),
+    Sha512(Sha512Context),
+}
+
+/// The generic message-digest context.
+#[derive(Clone)]
+pub struct MdContext {
+    pub(crate) md_type: MdType,             /* The type of the message digest. */
+    pub(crate) digest: MdDigest,            /* The digest-specific context. */
+    pub(crate) ipad: [u8; MBEDTLS_MD_MAX_BLOCK_SIZE],
+                                            /* The HMAC inner padding (key XOR 0x36). */
+    pub(crate) opad: [u8; MBEDTLS_MD_MAX_BLOCK_SIZE],
+                                            /* The HMAC outer padding (key XOR 0x5C). */
+}
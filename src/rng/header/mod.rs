@@ -0,0 +1,15 @@
+pub mod aes;
+pub mod ctr_drbg;
+pub mod entropy;
+pub mod entropy_health;
+pub mod entropy_jitter;
+pub mod entropy_poll;
+pub mod error;
+pub mod ffi;
+pub mod hash_drbg;
+pub mod hmac_drbg;
+pub mod md;
+pub mod platform_util;
+pub mod sha256;
+pub mod sha512;
+pub mod threading;
@@ -1,10 +1,13 @@
-/**
+/*
  * \file platform_util.h
  *
  * \brief Common and shared functions used by multiple modules in the Mbed TLS
  *        library.
  */
 
-/* 
- * NO STRUCTURE OR DATA TYPE TO DECLARE
- */
+/// A buffer that is securely wiped with platform_zeroize() when it goes
+/// out of scope, on the success path as well as on every early return.
+///
+/// It dereferences to the wrapped buffer, so a temporary such as
+/// `[0u8; 16]` is used exactly like the plain array.
+pub struct Zeroizing<T: AsMut<[u8]>>(pub(crate) T);
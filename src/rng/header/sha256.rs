@@ -0,0 +1,29 @@
+/*
+ * \file sha256.h
+ * \brief This file contains SHA-224 and SHA-256 definitions and functions.
+ *
+ * The Secure Hash Algorithms 224 and 256 (SHA-224 and SHA-256) cryptographic
+ * hash functions are defined in <em>FIPS 180-4: Secure Hash Standard (SHS)</em>.
+ */
+
+/// The SHA-256 block size, in bytes.
+pub const MBEDTLS_SHA256_BLOCK_SIZE: usize = 64;
+
+/// The length of a SHA-256 checksum, in bytes. SHA-224 checksums are the
+/// first 28 bytes of it.
+pub const MBEDTLS_SHA256_OUTPUT_SIZE: usize = 32;
+
+/// The SHA-256 context structure.
+///
+/// The structure is used both for SHA-256 and for SHA-224
+/// checksum calculations. The choice between these two is
+/// made in the call to starts().
+#[derive(Clone)]
+pub struct Sha256Context {
+    pub(crate) total: [u32; 2],          /* The number of Bytes processed. */
+    pub(crate) state: [u32; 8],          /* The intermediate digest state. */
+    pub(crate) buffer: [u8; MBEDTLS_SHA256_BLOCK_SIZE],
+                                         /* The data block being processed. */
+    pub(crate) is224: bool,              /* Determines which function to use:
+                                            false: Use SHA-256, or true: Use SHA-224. */
+}
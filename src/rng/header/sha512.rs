@@ -0,0 +1,29 @@
+/*
+ * \file sha512.h
+ * \brief This file contains SHA-384 and SHA-512 definitions and functions.
+ *
+ * The Secure Hash Algorithms 384 and 512 (SHA-384 and SHA-512) cryptographic
+ * hash functions are defined in <em>FIPS 180-4: Secure Hash Standard (SHS)</em>.
+ */
+
+/// The SHA-512 block size, in bytes.
+pub const MBEDTLS_SHA512_BLOCK_SIZE: usize = 128;
+
+/// The length of a SHA-512 checksum, in bytes. SHA-384 checksums are the
+/// first 48 bytes of it.
+pub const MBEDTLS_SHA512_OUTPUT_SIZE: usize = 64;
+
+/// The SHA-512 context structure.
+///
+/// The structure is used both for SHA-384 and for SHA-512
+/// checksum calculations. The choice between these two is
+/// made in the call to starts().
+#[derive(Clone)]
+pub struct Sha512Context {
+    pub(crate) total: [u64; 2],          /* The number of Bytes processed. */
+    pub(crate) state: [u64; 8],          /* The intermediate digest state. */
+    pub(crate) buffer: [u8; MBEDTLS_SHA512_BLOCK_SIZE],
+                                         /* The data block being processed. */
+    pub(crate) is384: bool,              /* Determines which function to use:
+                                            false: Use SHA-512, or true: Use SHA-384. */
+}
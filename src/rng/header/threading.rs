@@ -0,0 +1,19 @@
+/*
+ * \file threading.h
+ *
+ * \brief Threading abstraction layer
+ */
+
+use std::sync::Mutex;
+
+/// The selected feature is not available.
+pub const MBEDTLS_ERR_THREADING_FEATURE_UNAVAILABLE:i32 = -0x001A;
+/// Bad input parameters to function.
+pub const MBEDTLS_ERR_THREADING_BAD_INPUT_DATA:i32 = -0x001C;
+/// Locking / unlocking / free failed with error code.
+pub const MBEDTLS_ERR_THREADING_MUTEX_ERROR:i32 = -0x001E;
+
+/// The mutex type used by the library, the counterpart of
+/// mbedtls_threading_mutex_t. The mutex owns the data it protects;
+/// lock it with mutex_lock() and unlock it by dropping the guard.
+pub type ThreadingMutex<T> = Mutex<T>;
@@ -0,0 +1,522 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use crate::rng::header::ctr_drbg::EntropyCallback; // The entropy callback.
+use crate::rng::header::ctr_drbg::EntropySource; // A source of entropy for seeding a DRBG.
+use crate::rng::header::entropy_poll::PlatformEntropy; // The platform entropy source.
+use crate::rng::header::error::Error;
+use crate::rng::header::md::MdContext; // The generic message-digest context.
+use crate::rng::header::md::MdType; // Supported message digests.
+use crate::rng::header::md::MBEDTLS_MD_MAX_SIZE; // The longest message digest supported, in bytes.
+
+use crate::rng::header::hmac_drbg::MBEDTLS_HMAC_DRBG_RESEED_INTERVAL; // Interval before reseed is performed by default.
+use crate::rng::header::hmac_drbg::MBEDTLS_HMAC_DRBG_MAX_INPUT; // Maximum number of additional input bytes.
+use crate::rng::header::hmac_drbg::MBEDTLS_HMAC_DRBG_MAX_REQUEST; // Maximum number of requested bytes per call.
+use crate::rng::header::hmac_drbg::MBEDTLS_HMAC_DRBG_MAX_SEED_INPUT; // Maximum size of (re)seed buffer.
+use crate::rng::header::hmac_drbg::MBEDTLS_HMAC_DRBG_PR_ON; // Prediction resistance enabled.
+use crate::rng::header::hmac_drbg::HmacDrbg; // The HMAC_DRBG context structure.
+
+use crate::rng::header::platform_util::Zeroizing; // A buffer wiped when it goes out of scope.
+
+use crate::rng::platform_util::{platform_zeroize, read_file, write_file_atomic};
+
+/*
+ * HMAC_DRBG context initialization
+ */
+
+impl<'a> HmacDrbg<'a> {
+    /// This function initializes the HMAC_DRBG context, and prepares it for
+    /// seed() or seed_buf().
+    pub fn new() -> Self {
+        HmacDrbg {
+            md_ctx: None,
+            v: [0; MBEDTLS_MD_MAX_SIZE],
+            reseed_counter: 0,
+            entropy_len: 0,
+            prediction_resistance: false,
+            reseed_interval: MBEDTLS_HMAC_DRBG_RESEED_INTERVAL,
+            f_entropy: None,
+        }
+    }
+
+    /*
+     *  This function resets HMAC_DRBG context to the state immediately
+     *  after initial call of new().
+     */
+
+    /// This function clears HMAC_DRBG context data.
+    pub fn free(&mut self) {
+        *self = HmacDrbg::new();
+    }
+
+    /// This function turns prediction resistance on or off. The default value is off.
+    pub fn set_prediction_resistance(&mut self, resistance: bool) {
+        self.prediction_resistance = resistance;
+    }
+
+    /// This function sets the amount of entropy grabbed on each seed or reseed.
+    /// The default value is given by the security strength of the message
+    /// digest: 24 bytes for SHA-224, 32 bytes for the larger digests.
+    pub fn set_entropy_len(&mut self, len: usize) {
+        self.entropy_len = len;
+    }
+
+    /// This function sets the reseed interval: the number of generate
+    /// requests after which the entropy source is called again. The default
+    /// value is MBEDTLS_HMAC_DRBG_RESEED_INTERVAL.
+    ///
+    /// As in mbedTLS, the value is not checked. A request reseeds when more
+    /// than `interval` requests were made since the last (re)seeding, so an
+    /// interval below 1 makes every request reseed first, like
+    /// prediction resistance.
+    pub fn set_reseed_interval(&mut self, interval: i32) {
+        self.reseed_interval = interval;
+    }
+}
+
+impl Default for HmacDrbg<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for HmacDrbg<'_> {
+    /// This function wipes the working state of the HMAC_DRBG context.
+    fn drop(&mut self) {
+        platform_zeroize(&mut self.v);
+    }
+}
+
+impl<'a> HmacDrbg<'a> {
+    /*
+     * HMAC_DRBG update, using optional additional data (10.1.2.2)
+     */
+
+    /// This function updates the state of the HMAC_DRBG context.
+    pub fn update(&mut self, additional: &[u8]) -> Result<(), Error> {
+        let md_ctx = self.md_ctx.as_mut().ok_or(Error::MdBadInputData)?;
+        let md_len = md_ctx.md_type().size();
+        let rounds: u8 = if !additional.is_empty() { 2 } else { 1 };
+        let mut k = Zeroizing::new([0u8; MBEDTLS_MD_MAX_SIZE]);
+
+        for sep in 0..rounds {
+            /* Step 1 or 4 */
+            md_ctx.hmac_reset();
+            md_ctx.hmac_update(&self.v[..md_len]);
+            md_ctx.hmac_update(&[sep]);
+            if rounds == 2 {
+                md_ctx.hmac_update(additional);
+            }
+            md_ctx.hmac_finish(&mut k);
+
+            /* Step 2 or 5 */
+            md_ctx.hmac_starts(&k[..md_len]);
+            md_ctx.hmac_update(&self.v[..md_len]);
+            md_ctx.hmac_finish(&mut self.v);
+        }
+
+        Ok(())
+    }
+
+    /*
+     * Set up the HMAC context with an all-zero key (K = 0x00...00) and
+     * set V to its initial value (V = 0x01...01).
+     */
+    fn setup(&mut self, md_type: MdType) {
+        let md_size = md_type.size();
+        let mut md_ctx = MdContext::new(md_type);
+
+        self.v = [0; MBEDTLS_MD_MAX_SIZE];
+        md_ctx.hmac_starts(&self.v[..md_size]);
+        self.v[..md_size].fill(0x01);
+
+        self.md_ctx = Some(md_ctx);
+    }
+
+    /*
+     * Simplified HMAC_DRBG initialisation (for use with deterministic ECDSA)
+     */
+
+    /// This function initializes the HMAC_DRBG context from a buffer,
+    /// without an entropy source. Such an instance never reseeds; it is
+    /// meant for deterministic uses such as RFC 6979 signatures.
+    pub fn seed_buf(&mut self, md_type: MdType, data: &[u8]) -> Result<(), Error> {
+        self.setup(md_type);
+        self.f_entropy = None;
+
+        self.update(data)
+    }
+
+    /*
+     * Internal function used both for seeding and reseeding the DRBG.
+     * Comments starting with arabic numbers refer to section 10.1.2.4
+     * of SP800-90A, while roman numbers refer to section 9.2.
+     */
+    fn reseed_core(&mut self, additional: &[u8], use_nonce: bool) -> Result<(), Error> {
+        let mut seed = Zeroizing::new([0u8; MBEDTLS_HMAC_DRBG_MAX_SEED_INPUT]);
+        let mut seedlen: usize = 0;
+        let len = additional.len();
+
+        {
+            let total_entropy_len = if !use_nonce {
+                self.entropy_len
+            } else {
+                self.entropy_len * 3 / 2
+            };
+
+            /* III. Check input length */
+            if len > MBEDTLS_HMAC_DRBG_MAX_INPUT ||
+                total_entropy_len + len > MBEDTLS_HMAC_DRBG_MAX_SEED_INPUT
+            {
+                return Err(Error::HmacDrbgInputTooBig);
+            }
+        }
+
+        let entropy_len = self.entropy_len;
+        let f_entropy = self.f_entropy.as_mut()
+            .ok_or(Error::HmacDrbgEntropySourceFailed)?;
+
+        /* IV. Gather entropy_len bytes of entropy for the seed */
+        if f_entropy.fill(&mut seed[..entropy_len]).is_err() {
+            return Err(Error::HmacDrbgEntropySourceFailed);
+        }
+        seedlen += entropy_len;
+
+        /* For initial seeding, allow adding of nonce generated
+         * from the entropy source. See Sect 8.6.7 in SP800-90A. */
+        if use_nonce {
+            /* Note: We don't merge the two calls to f_entropy() in order
+             *       to avoid requesting too much entropy from f_entropy()
+             *       at once. */
+            if f_entropy.fill(&mut seed[seedlen..seedlen + entropy_len / 2]).is_err() {
+                return Err(Error::HmacDrbgEntropySourceFailed);
+            }
+            seedlen += entropy_len / 2;
+        }
+
+        /* 1. Concatenate entropy and additional data if any */
+        if len != 0 {
+            seed[seedlen..seedlen + len].copy_from_slice(additional);
+            seedlen += len;
+        }
+
+        /* 2. Update state */
+        self.update(&seed[..seedlen])?;
+
+        /* 3. Reset reseed_counter */
+        self.reseed_counter = 1;
+
+        /* 4. Done */
+        Ok(())
+    }
+
+    /*
+     * HMAC_DRBG reseeding: 10.1.2.4 + 9.2
+     */
+
+    /// This function reseeds the HMAC_DRBG context, that is extracts data from the entropy source.
+    pub fn reseed(&mut self, additional: &[u8]) -> Result<(), Error> {
+        self.reseed_core(additional, false)
+    }
+
+    /*
+     * HMAC_DRBG initialisation (10.1.2.3 + 9.1)
+     *
+     * The nonce is not passed as a separate parameter but extracted
+     * from the entropy source as suggested in 8.6.7.
+     */
+
+    /// This function seeds and sets up the HMAC_DRBG entropy source for future reseeds.
+    pub fn seed<E: EntropySource + Send + 'a>(&mut self, md_type: MdType, f_entropy: E, custom: &[u8]) -> Result<(), Error> {
+        let md_size = md_type.size();
+
+        self.setup(md_type);
+
+        self.f_entropy = Some(Box::new(f_entropy));
+
+        if self.entropy_len == 0 {
+            /*
+             * See SP800-57 5.6.1 (p. 65-66) for the security strength provided by
+             * each hash function, then according to SP800-90A rev1 10.1 table 2,
+             * min_entropy_len (in bits) is security_strength.
+             *
+             * (This also matches the sizes used in the NIST test vectors.)
+             */
+            self.entropy_len = if md_size <= 28 {
+                24 /* 224-bits hash -> 192 bits */
+            } else {
+                32 /* better (256+) -> 256 bits */
+            };
+        }
+
+        self.reseed_core(custom, true /* add nonce */)
+    }
+
+    /// This function seeds the HMAC_DRBG from the platform entropy source,
+    /// which is then also used for future reseeds.
+    pub fn seed_default(&mut self, md_type: MdType, custom: &[u8]) -> Result<(), Error> {
+        self.seed(md_type, PlatformEntropy, custom)
+    }
+
+    /*
+     * HMAC_DRBG random function with optional additional data:
+     * 10.1.2.5 (arabic) + 9.3 (Roman)
+     */
+
+    /// This function updates an HMAC_DRBG instance with additional data and uses it to generate random data.
+    pub fn generate_with_additional(&mut self, output: &mut [u8], additional: &[u8]) -> Result<(), Error> {
+        let mut additional = additional;
+
+        /* II. Check request length */
+        if output.len() > MBEDTLS_HMAC_DRBG_MAX_REQUEST {
+            return Err(Error::HmacDrbgRequestTooBig);
+        }
+
+        /* III. Check input length */
+        if additional.len() > MBEDTLS_HMAC_DRBG_MAX_INPUT {
+            return Err(Error::HmacDrbgInputTooBig);
+        }
+
+        /* 1. (aka VII and IX) Check reseed counter and PR */
+        if self.f_entropy.is_some() && /* For no-reseeding instances */
+            (self.prediction_resistance == MBEDTLS_HMAC_DRBG_PR_ON ||
+             self.reseed_counter > self.reseed_interval)
+        {
+            self.reseed(additional)?;
+
+            additional = &[]; /* VII.4 */
+        }
+
+        /* 2. Use additional data if any */
+        if !additional.is_empty() {
+            self.update(additional)?;
+        }
+
+        /* 3, 4, 5. Generate bytes */
+        let md_ctx = self.md_ctx.as_mut().ok_or(Error::MdBadInputData)?;
+        let md_len = md_ctx.md_type().size();
+
+        for chunk in output.chunks_mut(md_len) {
+            md_ctx.hmac_reset();
+            md_ctx.hmac_update(&self.v[..md_len]);
+            md_ctx.hmac_finish(&mut self.v);
+
+            chunk.copy_from_slice(&self.v[..chunk.len()]);
+        }
+
+        /* 6. Update */
+        self.update(additional)?;
+
+        /* 7. Update reseed counter */
+        self.reseed_counter += 1;
+
+        /* 8. Done */
+        Ok(())
+    }
+
+    /// This function uses HMAC_DRBG to generate random data.
+    pub fn generate(&mut self, output: &mut [u8]) -> Result<(), Error> {
+        self.generate_with_additional(output, &[])
+    }
+
+    /// This function writes a seed file of MBEDTLS_HMAC_DRBG_MAX_INPUT
+    /// bytes of output.
+    ///
+    /// The file is written next to `path` under a temporary name, readable
+    /// and writable by its owner only, then renamed over `path`, as CTR_DRBG
+    /// seed files are.
+    pub fn write_seed_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        let mut buf = Zeroizing::new([0u8; MBEDTLS_HMAC_DRBG_MAX_INPUT]);
+
+        self.generate(&mut buf[..])?;
+        write_file_atomic(path.as_ref(), Error::HmacDrbgFileIoError, |f| {
+            f.write_all(&buf[..]).map_err(|_| Error::HmacDrbgFileIoError)
+        })
+    }
+
+    /// This function reads and updates a seed file. The seed is added to this instance.
+    pub fn update_seed_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let mut buf = Zeroizing::new([0u8; MBEDTLS_HMAC_DRBG_MAX_INPUT + 1]);
+
+        let f = File::open(path).map_err(|_| Error::HmacDrbgFileIoError)?;
+        let n = read_file(f, &mut buf[..], Error::HmacDrbgFileIoError)?;
+
+        if n > MBEDTLS_HMAC_DRBG_MAX_INPUT {
+            return Err(Error::HmacDrbgInputTooBig);
+        }
+        if n == 0 {
+            return Err(Error::HmacDrbgFileIoError);
+        }
+        self.update(&buf[..n])?;
+
+        self.write_seed_file(path)
+    }
+}
+
+/*
+ * From a NIST PR=true test vector
+ * (CAVS 14.3, HMAC_DRBG SHA-256, no personalization string)
+ */
+const ENTROPY_PR: [u8; 112] = [
+      0x99, 0x69, 0xe5, 0x4b, 0x47, 0x03, 0xff, 0x31,
+      0x78, 0x5b, 0x87, 0x9a, 0x7e, 0x5c, 0x0e, 0xae,
+      0x0d, 0x3e, 0x30, 0x95, 0x59, 0xe9, 0xfe, 0x96,
+      0xb0, 0x67, 0x6d, 0x49, 0xd5, 0x91, 0xea, 0x4d,
+      0x07, 0xd2, 0x0d, 0x46, 0xd0, 0x64, 0x75, 0x7d,
+      0x30, 0x23, 0xca, 0xc2, 0x37, 0x61, 0x27, 0xab,
+      0xc6, 0x0f, 0x29, 0x99, 0x10, 0x0f, 0x73, 0x8c,
+      0x10, 0xf7, 0x47, 0x92, 0x67, 0x6a, 0x3f, 0xc4,
+      0xa2, 0x62, 0xd1, 0x37, 0x21, 0x79, 0x80, 0x46,
+      0xe2, 0x9a, 0x29, 0x51, 0x81, 0x56, 0x9f, 0x54,
+      0xc1, 0x1d, 0x45, 0x24, 0xc9, 0x07, 0x1b, 0xd3,
+      0x09, 0x60, 0x15, 0xfc, 0xf7, 0xbc, 0x24, 0xa6,
+      0x07, 0xf2, 0x2f, 0xa0, 0x65, 0xc9, 0x37, 0x65,
+      0x8a, 0x2a, 0x77, 0xa8, 0x69, 0x90, 0x89, 0xf4 ];
+
+const RESULT_PR: [u8; 128] = [
+      0xab, 0xc0, 0x15, 0x85, 0x60, 0x94, 0x80, 0x3a,
+      0x93, 0x8d, 0xff, 0xd2, 0x0d, 0xa9, 0x48, 0x43,
+      0x87, 0x0e, 0xf9, 0x35, 0xb8, 0x2c, 0xfe, 0xc1,
+      0x77, 0x06, 0xb8, 0xf5, 0x51, 0xb8, 0x38, 0x50,
+      0x44, 0x23, 0x5d, 0xd4, 0x4b, 0x59, 0x9f, 0x94,
+      0xb3, 0x9b, 0xe7, 0x8d, 0xd4, 0x76, 0xe0, 0xcf,
+      0x11, 0x30, 0x9c, 0x99, 0x5a, 0x73, 0x34, 0xe0,
+      0xa7, 0x8b, 0x37, 0xbc, 0x95, 0x86, 0x23, 0x50,
+      0x86, 0xfa, 0x3b, 0x63, 0x7b, 0xa9, 0x1c, 0xf8,
+      0xfb, 0x65, 0xef, 0xa2, 0x2a, 0x58, 0x9c, 0x13,
+      0x75, 0x31, 0xaa, 0x7b, 0x2d, 0x4e, 0x26, 0x07,
+      0xaa, 0xc2, 0x72, 0x92, 0xb0, 0x1c, 0x69, 0x8e,
+      0x6e, 0x01, 0xae, 0x67, 0x9e, 0xb8, 0x7c, 0x01,
+      0xa8, 0x9c, 0x74, 0x22, 0xd4, 0x37, 0x2d, 0x6d,
+      0x75, 0x4a, 0xba, 0xbb, 0x4b, 0xf8, 0x96, 0xfc,
+      0xb1, 0xcd, 0x09, 0xd6, 0x92, 0xd0, 0x28, 0x3f ];
+
+/*
+ * From a NIST PR=false test vector
+ * (CAVS 14.3, HMAC_DRBG SHA-512, no personalization string)
+ */
+const ENTROPY_NOPR: [u8; 80] = [
+      0x48, 0xc1, 0x21, 0xb1, 0x87, 0x33, 0xaf, 0x15,
+      0xc2, 0x7e, 0x1d, 0xd9, 0xba, 0x66, 0xa9, 0xa8,
+      0x1a, 0x55, 0x79, 0xcd, 0xba, 0x0f, 0x5b, 0x65,
+      0x7e, 0xc5, 0x3c, 0x2b, 0x9e, 0x90, 0xbb, 0xf6,
+      0xbb, 0xb7, 0xc7, 0x77, 0x42, 0x80, 0x68, 0xfa,
+      0xd9, 0x97, 0x08, 0x91, 0xf8, 0x79, 0xb1, 0xaf,
+      0xe0, 0xff, 0xef, 0xda, 0xdb, 0x9c, 0xcf, 0x99,
+      0x05, 0x04, 0xd5, 0x68, 0xbd, 0xb4, 0xd8, 0x62,
+      0xcb, 0xe1, 0x7c, 0xcc, 0xe6, 0xe2, 0x2d, 0xfc,
+      0xab, 0x8b, 0x48, 0x04, 0xfd, 0x21, 0x42, 0x1a ];
+
+const RESULT_NOPR: [u8; 256] = [
+      0x05, 0xda, 0x6a, 0xac, 0x7d, 0x98, 0x0d, 0xa0,
+      0x38, 0xf6, 0x5f, 0x39, 0x28, 0x41, 0x47, 0x6d,
+      0x37, 0xfe, 0x70, 0xfb, 0xd3, 0xe3, 0x69, 0xd1,
+      0xf8, 0x01, 0x96, 0xe6, 0x6e, 0x54, 0xb8, 0xfa,
+      0xdb, 0x1d, 0x60, 0xe1, 0xa0, 0xf3, 0xd4, 0xdc,
+      0x17, 0x37, 0x69, 0xd7, 0x5f, 0xc3, 0x41, 0x05,
+      0x49, 0xd7, 0xa8, 0x43, 0x27, 0x0a, 0x54, 0xa0,
+      0x68, 0xb4, 0xfe, 0x76, 0x7d, 0x7d, 0x9a, 0x59,
+      0x60, 0x45, 0x10, 0xa8, 0x75, 0xad, 0x1e, 0x97,
+      0x31, 0xc8, 0xaf, 0xd0, 0xfd, 0x50, 0xb8, 0x25,
+      0xe2, 0xc5, 0x0d, 0x06, 0x25, 0x76, 0x17, 0x51,
+      0x06, 0xa9, 0x98, 0x1b, 0xe3, 0x7e, 0x02, 0xec,
+      0x7c, 0x5c, 0xd0, 0xa6, 0x9a, 0xa0, 0xca, 0x65,
+      0xbd, 0xda, 0xee, 0x1b, 0x0d, 0xe5, 0x32, 0xe1,
+      0x0c, 0xfa, 0x1f, 0x5b, 0xf6, 0xa0, 0x26, 0xe4,
+      0x73, 0x79, 0x73, 0x6a, 0x09, 0x9d, 0x67, 0x50,
+      0xab, 0x12, 0x1d, 0xbe, 0x36, 0x22, 0xb8, 0x41,
+      0xba, 0xf8, 0xbd, 0xcb, 0xe8, 0x75, 0xc8, 0x5b,
+      0xa4, 0xb5, 0x86, 0xb8, 0xb5, 0xb5, 0x7b, 0x0f,
+      0xec, 0xbe, 0xc0, 0x8c, 0x12, 0xff, 0x2a, 0x94,
+      0x53, 0xc4, 0x7c, 0x6e, 0x32, 0xa5, 0x21, 0x03,
+      0xd9, 0x72, 0xc6, 0x2a, 0xb9, 0xaf, 0xfb, 0x8e,
+      0x72, 0x8a, 0x31, 0xfc, 0xef, 0xbb, 0xcc, 0xc5,
+      0x56, 0xc0, 0xf0, 0xa3, 0x5f, 0x4b, 0x10, 0xac,
+      0xe2, 0xd9, 0x6b, 0x90, 0x6e, 0x36, 0xcb, 0xb7,
+      0x22, 0x33, 0x20, 0x1e, 0x53, 0x6d, 0x3e, 0x13,
+      0xb0, 0x45, 0x18, 0x7b, 0x41, 0x7d, 0x24, 0x49,
+      0xca, 0xd1, 0xed, 0xd1, 0x92, 0xe0, 0x61, 0xf1,
+      0x2d, 0x22, 0x14, 0x7b, 0x0a, 0x17, 0x6e, 0xa8,
+      0xd9, 0xc4, 0xc3, 0x54, 0x04, 0x39, 0x5b, 0x65,
+      0x02, 0xef, 0x33, 0x3a, 0x81, 0x3b, 0x65, 0x86,
+      0x03, 0x74, 0x79, 0xe0, 0xfa, 0x3c, 0x6a, 0x23 ];
+
+/*
+ * "Entropy" from buffer
+ */
+fn hmac_drbg_self_test_entropy(data: &'static [u8]) -> EntropyCallback<'static> {
+    let mut test_offset = 0;
+    Box::new(move |buf: &mut [u8]| {
+        buf.copy_from_slice(&data[test_offset..test_offset + buf.len()]);
+        test_offset += buf.len();
+        Ok(())
+    })
+}
+
+macro_rules! chk {
+    ($verbose:expr, $c:expr) => {
+        if $c.is_err() {
+            if $verbose {
+                println!("failed");
+            }
+            return 1;
+        }
+    };
+}
+
+/*
+ * Checkup routine
+ */
+
+/// The HMAC_DRBG checkup routine, with SHA-256 (PR = True) and SHA-512
+/// (PR = False). Returns 0 on success and 1 on failure.
+pub fn self_test(verbose: bool) -> i32 {
+    let mut ctx = HmacDrbg::new();
+    let mut buf = [0u8; RESULT_NOPR.len()];
+
+    /*
+     * PR = True
+     */
+    if verbose {
+        print!("  HMAC_DRBG (SHA-256, PR = True) : ");
+    }
+
+    chk!(verbose, ctx.seed(MdType::Sha256, hmac_drbg_self_test_entropy(&ENTROPY_PR), &[]));
+    ctx.set_prediction_resistance(MBEDTLS_HMAC_DRBG_PR_ON);
+    chk!(verbose, ctx.generate(&mut buf[..RESULT_PR.len()]));
+    chk!(verbose, ctx.generate(&mut buf[..RESULT_PR.len()]));
+    chk!(verbose, if buf[..RESULT_PR.len()] == RESULT_PR { Ok(()) } else { Err(()) });
+
+    ctx.free();
+
+    if verbose {
+        println!("passed");
+    }
+
+    /*
+     * PR = False
+     */
+    if verbose {
+        print!("  HMAC_DRBG (SHA-512, PR = False): ");
+    }
+
+    chk!(verbose, ctx.seed(MdType::Sha512, hmac_drbg_self_test_entropy(&ENTROPY_NOPR), &[]));
+    chk!(verbose, ctx.reseed(&[]));
+    chk!(verbose, ctx.generate(&mut buf));
+    chk!(verbose, ctx.generate(&mut buf));
+    chk!(verbose, if buf == RESULT_NOPR { Ok(()) } else { Err(()) });
+
+    ctx.free();
+
+    if verbose {
+        println!("passed");
+    }
+
+    if verbose {
+        println!();
+    }
+
+    0
+}
@@ -0,0 +1,194 @@
+use crate::rng::header::md::MdContext; // The generic message-digest context.
+use crate::rng::header::md::MdDigest; // The hash context of the selected digest.
+use crate::rng::header::md::MdType; // Supported message digests.
+use crate::rng::header::md::MBEDTLS_MD_MAX_SIZE; // The longest message digest supported, in bytes.
+use crate::rng::header::md::MBEDTLS_MD_MAX_BLOCK_SIZE; // The largest block size of the supported digests, in bytes.
+use crate::rng::header::sha256::Sha256Context;
+use crate::rng::header::sha256::MBEDTLS_SHA256_OUTPUT_SIZE; // The length of a SHA-256 checksum, in bytes.
+use crate::rng::header::sha512::Sha512Context;
+
+use crate::rng::header::platform_util::Zeroizing; // A buffer wiped when it goes out of scope.
+
+use crate::rng::platform_util::platform_zeroize;
+
+impl MdType {
+    /// This function returns the size of the message-digest output, in bytes.
+    pub fn size(self) -> usize {
+        match self {
+            MdType::Sha224 => 28,
+            MdType::Sha256 => 32,
+            MdType::Sha384 => 48,
+            MdType::Sha512 => 64,
+        }
+    }
+
+    /// This function returns the block size of the message digest, in bytes.
+    pub fn block_size(self) -> usize {
+        match self {
+            MdType::Sha224 | MdType::Sha256 => 64,
+            MdType::Sha384 | MdType::Sha512 => 128,
+        }
+    }
+
+    /// This function returns the name of the message digest.
+    pub fn name(self) -> &'static str {
+        match self {
+            MdType::Sha224 => "SHA224",
+            MdType::Sha256 => "SHA256",
+            MdType::Sha384 => "SHA384",
+            MdType::Sha512 => "SHA512",
+        }
+    }
+}
+
+impl MdDigest {
+    fn starts(&mut self, md_type: MdType) {
+        match self {
+            MdDigest::Sha256(ctx) => ctx.starts(md_type == MdType::Sha224),
+            MdDigest::Sha512(ctx) => ctx.starts(md_type == MdType::Sha384),
+        }
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        match self {
+            MdDigest::Sha256(ctx) => ctx.update(input),
+            MdDigest::Sha512(ctx) => ctx.update(input),
+        }
+    }
+
+    fn finish(&mut self, output: &mut [u8; MBEDTLS_MD_MAX_SIZE]) {
+        match self {
+            MdDigest::Sha256(ctx) => {
+                let mut sum = Zeroizing::new([0u8; MBEDTLS_SHA256_OUTPUT_SIZE]);
+                ctx.finish(&mut sum);
+                output[..MBEDTLS_SHA256_OUTPUT_SIZE].copy_from_slice(&sum[..]);
+                output[MBEDTLS_SHA256_OUTPUT_SIZE..].fill(0);
+            }
+            MdDigest::Sha512(ctx) => ctx.finish(output),
+        }
+    }
+}
+
+impl MdContext {
+    /// This function selects the message digest and sets up the context,
+    /// the counterpart of mbedtls_md_setup() with HMAC enabled.
+    pub fn new(md_type: MdType) -> Self {
+        let digest = match md_type {
+            MdType::Sha224 | MdType::Sha256 => MdDigest::Sha256(Sha256Context::new()),
+            MdType::Sha384 | MdType::Sha512 => MdDigest::Sha512(Sha512Context::new()),
+        };
+
+        MdContext {
+            md_type,
+            digest,
+            ipad: [0; MBEDTLS_MD_MAX_BLOCK_SIZE],
+            opad: [0; MBEDTLS_MD_MAX_BLOCK_SIZE],
+        }
+    }
+
+    /// This function returns the type of the message digest.
+    pub fn md_type(&self) -> MdType {
+        self.md_type
+    }
+
+    /// This function starts a message-digest computation.
+    pub fn starts(&mut self) {
+        self.digest.starts(self.md_type);
+    }
+
+    /// This function feeds an input buffer into an ongoing message-digest
+    /// computation.
+    pub fn update(&mut self, input: &[u8]) {
+        self.digest.update(input);
+    }
+
+    /// This function finishes the digest operation, and writes the result
+    /// to the output buffer. Only the first size() bytes are significant.
+    pub fn finish(&mut self, output: &mut [u8; MBEDTLS_MD_MAX_SIZE]) {
+        self.digest.finish(output);
+    }
+
+    /// This function sets the HMAC key and prepares to authenticate a new
+    /// message.
+    pub fn hmac_starts(&mut self, key: &[u8]) {
+        let block_size = self.md_type.block_size();
+        let mut sum = Zeroizing::new([0u8; MBEDTLS_MD_MAX_SIZE]);
+        let mut key = key;
+
+        if key.len() > block_size {
+            self.starts();
+            self.update(key);
+            self.finish(&mut sum);
+            key = &sum[..self.md_type.size()];
+        }
+
+        self.ipad = [0x36; MBEDTLS_MD_MAX_BLOCK_SIZE];
+        self.opad = [0x5C; MBEDTLS_MD_MAX_BLOCK_SIZE];
+
+        for (i, k) in key.iter().enumerate() {
+            self.ipad[i] ^= *k;
+            self.opad[i] ^= *k;
+        }
+
+        self.digest.starts(self.md_type);
+        self.digest.update(&self.ipad[..block_size]);
+    }
+
+    /// This function feeds an input buffer into an ongoing HMAC computation.
+    pub fn hmac_update(&mut self, input: &[u8]) {
+        self.update(input);
+    }
+
+    /// This function finishes the HMAC operation, and writes the result to
+    /// the output buffer. Only the first size() bytes are significant.
+    pub fn hmac_finish(&mut self, output: &mut [u8; MBEDTLS_MD_MAX_SIZE]) {
+        let block_size = self.md_type.block_size();
+        let mut tmp = Zeroizing::new([0u8; MBEDTLS_MD_MAX_SIZE]);
+
+        self.finish(&mut tmp);
+
+        self.digest.starts(self.md_type);
+        self.digest.update(&self.opad[..block_size]);
+        self.digest.update(&tmp[..self.md_type.size()]);
+        self.digest.finish(output);
+    }
+
+    /// This function prepares to authenticate a new message with the same
+    /// key as the previous HMAC operation.
+    pub fn hmac_reset(&mut self) {
+        let block_size = self.md_type.block_size();
+
+        self.digest.starts(self.md_type);
+        self.digest.update(&self.ipad[..block_size]);
+    }
+}
+
+impl Drop for MdContext {
+    /// This function clears the HMAC key material of the context.
+    fn drop(&mut self) {
+        platform_zeroize(&mut self.ipad);
+        platform_zeroize(&mut self.opad);
+    }
+}
+
+/// This function calculates the message digest of a buffer with the
+/// selected digest. Only the first size() bytes of the output are
+/// significant.
+pub fn md(md_type: MdType, input: &[u8], output: &mut [u8; MBEDTLS_MD_MAX_SIZE]) {
+    let mut ctx = MdContext::new(md_type);
+
+    ctx.starts();
+    ctx.update(input);
+    ctx.finish(output);
+}
+
+/// This function calculates the full generic HMAC of a buffer with the
+/// selected digest. Only the first size() bytes of the output are
+/// significant.
+pub fn md_hmac(md_type: MdType, key: &[u8], input: &[u8], output: &mut [u8; MBEDTLS_MD_MAX_SIZE]) {
+    let mut ctx = MdContext::new(md_type);
+
+    ctx.hmac_starts(key);
+    ctx.hmac_update(input);
+    ctx.hmac_finish(output);
+}
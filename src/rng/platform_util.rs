@@ -0,0 +1,132 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Read};
+use std::mem;
+use std::ops::{Deref, DerefMut};
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::process;
+use std::ptr;
+use std::slice;
+use std::sync::atomic::{compiler_fence, AtomicUsize, Ordering};
+
+use crate::rng::header::error::Error;
+use crate::rng::header::platform_util::Zeroizing; // A buffer wiped when it goes out of scope.
+
+/// This function securely zeroizes a buffer, the counterpart of
+/// mbedtls_platform_zeroize().
+///
+/// The stores are volatile and followed by a compiler fence, so the
+/// optimizer cannot remove them even when the buffer is never read again.
+pub fn platform_zeroize(buf: &mut [u8]) {
+    for b in buf.iter_mut() {
+        unsafe { ptr::write_volatile(b, 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/*
+ * Integer types whose every bit pattern, zero included, is a valid value
+ */
+pub(crate) trait Word: Copy {}
+
+impl Word for u32 {}
+impl Word for u64 {}
+
+/// This function securely zeroizes an array of words, such as the
+/// intermediate state of a hash, through platform_zeroize().
+pub(crate) fn platform_zeroize_words<W: Word>(words: &mut [W]) {
+    let len = mem::size_of_val(words);
+    /* Any byte pattern is a valid W, so the words can be wiped as bytes. */
+    platform_zeroize(unsafe { slice::from_raw_parts_mut(words.as_mut_ptr() as *mut u8, len) });
+}
+
+impl<T: AsMut<[u8]>> Zeroizing<T> {
+    /// This function wraps a buffer so that it is zeroized on drop.
+    pub fn new(buf: T) -> Self {
+        Zeroizing(buf)
+    }
+}
+
+impl<T: AsMut<[u8]>> Deref for Zeroizing<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: AsMut<[u8]>> DerefMut for Zeroizing<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: AsMut<[u8]>> Drop for Zeroizing<T> {
+    fn drop(&mut self) {
+        platform_zeroize(self.0.as_mut());
+    }
+}
+
+/*
+ * Seed files of the DRBG modules
+ *
+ * Every DRBG writes its seed file through write_file_atomic() and reads
+ * it back through read_file(), so that they share one policy; each
+ * module reports failures with its own FILE_IO_ERROR code, passed as
+ * `io_error`.
+ */
+
+/*
+ * Writes a file next to path under a temporary name, readable and
+ * writable by its owner only, then renames it over path, so that a crash
+ * never leaves a partially written file behind. The temporary file is
+ * removed if anything fails.
+ */
+pub(crate) fn write_file_atomic<F>(path: &Path, io_error: Error, write: F) -> Result<(), Error>
+    where F: FnOnce(&mut File) -> Result<(), Error>
+{
+    static TMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let mut tmp_name = path.file_name().ok_or(io_error)?.to_os_string();
+    tmp_name.push(format!(".{}.{}.tmp", process::id(), TMP_COUNTER.fetch_add(1, Ordering::Relaxed)));
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let mut f = options.open(&tmp_path).map_err(|_| io_error)?;
+    let ret = write(&mut f).and_then(|()| {
+        f.sync_all()
+            .and_then(|()| fs::rename(&tmp_path, path))
+            .map_err(|_| io_error)
+    });
+
+    if ret.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+
+    ret
+}
+
+/*
+ * Reads from reader until end of input or until buf is full, and returns
+ * the number of bytes read. A reader longer than buf fills it exactly,
+ * so callers detect oversized input with a buffer one byte too long.
+ */
+pub(crate) fn read_file<R: Read>(mut reader: R, buf: &mut [u8], io_error: Error) -> Result<usize, Error> {
+    let mut n = 0;
+
+    while n < buf.len() {
+        match reader.read(&mut buf[n..]) {
+            Ok(0) => break,
+            Ok(k) => n += k,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(_) => return Err(io_error),
+        }
+    }
+
+    Ok(n)
+}
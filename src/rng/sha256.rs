@@ -0,0 +1,361 @@
+use crate::rng::header::sha256::Sha256Context;
+use crate::rng::header::sha256::MBEDTLS_SHA256_BLOCK_SIZE; // The SHA-256 block size, in bytes.
+use crate::rng::header::sha256::MBEDTLS_SHA256_OUTPUT_SIZE; // The length of a SHA-256 checksum, in bytes.
+use crate::rng::header::platform_util::Zeroizing; // A buffer wiped when it goes out of scope.
+use crate::rng::platform_util::{platform_zeroize, platform_zeroize_words};
+
+/*
+ * Round constants
+ */
+const K: [u32; 64] = [
+    0x428A2F98, 0x71374491, 0xB5C0FBCF, 0xE9B5DBA5,
+    0x3956C25B, 0x59F111F1, 0x923F82A4, 0xAB1C5ED5,
+    0xD807AA98, 0x12835B01, 0x243185BE, 0x550C7DC3,
+    0x72BE5D74, 0x80DEB1FE, 0x9BDC06A7, 0xC19BF174,
+    0xE49B69C1, 0xEFBE4786, 0x0FC19DC6, 0x240CA1CC,
+    0x2DE92C6F, 0x4A7484AA, 0x5CB0A9DC, 0x76F988DA,
+    0x983E5152, 0xA831C66D, 0xB00327C8, 0xBF597FC7,
+    0xC6E00BF3, 0xD5A79147, 0x06CA6351, 0x14292967,
+    0x27B70A85, 0x2E1B2138, 0x4D2C6DFC, 0x53380D13,
+    0x650A7354, 0x766A0ABB, 0x81C2C92E, 0x92722C85,
+    0xA2BFE8A1, 0xA81A664B, 0xC24B8B70, 0xC76C51A3,
+    0xD192E819, 0xD6990624, 0xF40E3585, 0x106AA070,
+    0x19A4C116, 0x1E376C08, 0x2748774C, 0x34B0BCB5,
+    0x391C0CB3, 0x4ED8AA4A, 0x5B9CCA4F, 0x682E6FF3,
+    0x748F82EE, 0x78A5636F, 0x84C87814, 0x8CC70208,
+    0x90BEFFFA, 0xA4506CEB, 0xBEF9A3F7, 0xC67178F2 ];
+
+impl Sha256Context {
+    /// This function initializes a SHA-256 context. It must be started
+    /// with starts() before use.
+    pub fn new() -> Self {
+        Sha256Context {
+            total: [0; 2],
+            state: [0; 8],
+            buffer: [0; MBEDTLS_SHA256_BLOCK_SIZE],
+            is224: false,
+        }
+    }
+
+    /// This function starts a SHA-224 or SHA-256 checksum calculation.
+    pub fn starts(&mut self, is224: bool) {
+        self.total = [0; 2];
+
+        self.state = if !is224 {
+            /* SHA-256 */
+            [ 0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A,
+              0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19 ]
+        } else {
+            /* SHA-224 */
+            [ 0xC1059ED8, 0x367CD507, 0x3070DD17, 0xF70E5939,
+              0xFFC00B31, 0x68581511, 0x64F98FA7, 0xBEFA4FA4 ]
+        };
+
+        self.is224 = is224;
+    }
+
+    /// This function processes a single data block within the ongoing
+    /// SHA-256 computation.
+    pub(crate) fn process(&mut self, data: &[u8; MBEDTLS_SHA256_BLOCK_SIZE]) {
+        let mut w = [0u32; 64];
+        let mut a = self.state;
+
+        for (i, chunk) in data.chunks_exact(4).enumerate() {
+            let mut word = [0u8; 4];
+            word.copy_from_slice(chunk);
+            w[i] = u32::from_be_bytes(word);
+        }
+
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = s1.wrapping_add(w[i - 7]).wrapping_add(s0).wrapping_add(w[i - 16]);
+        }
+
+        for i in 0..64 {
+            let s1 = a[4].rotate_right(6) ^ a[4].rotate_right(11) ^ a[4].rotate_right(25);
+            let ch = (a[4] & a[5]) ^ (!a[4] & a[6]);
+            let temp1 = a[7].wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i]).wrapping_add(w[i]);
+            let s0 = a[0].rotate_right(2) ^ a[0].rotate_right(13) ^ a[0].rotate_right(22);
+            let maj = (a[0] & a[1]) ^ (a[0] & a[2]) ^ (a[1] & a[2]);
+            let temp2 = s0.wrapping_add(maj);
+
+            a[7] = a[6];
+            a[6] = a[5];
+            a[5] = a[4];
+            a[4] = a[3].wrapping_add(temp1);
+            a[3] = a[2];
+            a[2] = a[1];
+            a[1] = a[0];
+            a[0] = temp1.wrapping_add(temp2);
+        }
+
+        for (s, v) in self.state.iter_mut().zip(a.iter()) {
+            *s = s.wrapping_add(*v);
+        }
+
+        platform_zeroize_words(&mut w);
+        platform_zeroize_words(&mut a);
+    }
+
+    /// This function feeds an input buffer into an ongoing SHA-256
+    /// checksum calculation.
+    pub fn update(&mut self, input: &[u8]) {
+        if input.is_empty() {
+            return;
+        }
+
+        let left = (self.total[0] & 0x3F) as usize;
+        let fill = MBEDTLS_SHA256_BLOCK_SIZE - left;
+
+        let (total, carry) = self.total[0].overflowing_add(input.len() as u32);
+        self.total[0] = total;
+        self.total[1] = self.total[1]
+            .wrapping_add((input.len() as u64 >> 32) as u32)
+            .wrapping_add(carry as u32);
+
+        let mut input = input;
+
+        if left != 0 && input.len() >= fill {
+            self.buffer[left..].copy_from_slice(&input[..fill]);
+            let block = Zeroizing::new(self.buffer);
+            self.process(&block);
+            input = &input[fill..];
+        } else if left != 0 {
+            self.buffer[left..left + input.len()].copy_from_slice(input);
+            return;
+        }
+
+        let mut blocks = input.chunks_exact(MBEDTLS_SHA256_BLOCK_SIZE);
+        for chunk in &mut blocks {
+            let mut block = Zeroizing::new([0u8; MBEDTLS_SHA256_BLOCK_SIZE]);
+            block.copy_from_slice(chunk);
+            self.process(&block);
+        }
+
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+    }
+
+    /// This function finishes the SHA-256 operation, and writes the result
+    /// to the output buffer. For SHA-224 only the first 28 bytes are
+    /// significant.
+    pub fn finish(&mut self, output: &mut [u8; MBEDTLS_SHA256_OUTPUT_SIZE]) {
+        /*
+         * Add padding: 0x80 then 0x00 until 8 bytes remain for the length
+         */
+        let mut used = (self.total[0] & 0x3F) as usize;
+
+        self.buffer[used] = 0x80;
+        used += 1;
+
+        if used <= 56 {
+            /* Enough room for padding + length in current block */
+            self.buffer[used..56].fill(0);
+        } else {
+            /* We'll need an extra block */
+            self.buffer[used..].fill(0);
+            let block = Zeroizing::new(self.buffer);
+            self.process(&block);
+            self.buffer[..56].fill(0);
+        }
+
+        /*
+         * Add message length
+         */
+        let high = (self.total[0] >> 29) | (self.total[1] << 3);
+        let low = self.total[0] << 3;
+
+        self.buffer[56..60].copy_from_slice(&high.to_be_bytes());
+        self.buffer[60..].copy_from_slice(&low.to_be_bytes());
+
+        let block = Zeroizing::new(self.buffer);
+        self.process(&block);
+
+        /*
+         * Output final state
+         */
+        for (out, s) in output.chunks_exact_mut(4).zip(self.state.iter()) {
+            out.copy_from_slice(&s.to_be_bytes());
+        }
+
+        if self.is224 {
+            output[28..].fill(0);
+        }
+    }
+}
+
+impl Default for Sha256Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Sha256Context {
+    /// This function clears a SHA-256 context.
+    fn drop(&mut self) {
+        platform_zeroize_words(&mut self.total);
+        platform_zeroize_words(&mut self.state);
+        platform_zeroize(&mut self.buffer);
+    }
+}
+
+/// This function calculates the SHA-224 or SHA-256 checksum of a buffer.
+pub fn sha256(input: &[u8], output: &mut [u8; MBEDTLS_SHA256_OUTPUT_SIZE], is224: bool) {
+    let mut ctx = Sha256Context::new();
+
+    ctx.starts(is224);
+    ctx.update(input);
+    ctx.finish(output);
+}
+
+/*
+ * NIST SHAVS byte-oriented short message test vectors
+ * (SHA224ShortMsg.rsp and SHA256ShortMsg.rsp, Len = 0 to 40)
+ */
+const SHA256_TEST_SHAVS: [(&[u8], &[u8]); 12] = [
+    /* SHA-224 */
+    (&[],
+     &[ 0xd1, 0x4a, 0x02, 0x8c, 0x2a, 0x3a, 0x2b, 0xc9,
+        0x47, 0x61, 0x02, 0xbb, 0x28, 0x82, 0x34, 0xc4,
+        0x15, 0xa2, 0xb0, 0x1f, 0x82, 0x8e, 0xa6, 0x2a,
+        0xc5, 0xb3, 0xe4, 0x2f ]),
+    (&[0xff],
+     &[ 0xe3, 0x3f, 0x9d, 0x75, 0xe6, 0xae, 0x13, 0x69,
+        0xdb, 0xab, 0xf8, 0x1b, 0x96, 0xb4, 0x59, 0x1a,
+        0xe4, 0x6b, 0xba, 0x30, 0xb5, 0x91, 0xa6, 0xb6,
+        0xc6, 0x25, 0x42, 0xb5 ]),
+    (&[0x98, 0x4c],
+     &[ 0x2f, 0xa9, 0xdf, 0x91, 0x57, 0xd9, 0xe0, 0x27,
+        0xcf, 0xbc, 0x4c, 0x6a, 0x9d, 0xf3, 0x2e, 0x1a,
+        0xdc, 0x0c, 0xbe, 0x23, 0x28, 0xec, 0x2a, 0x63,
+        0xc5, 0xae, 0x93, 0x4e ]),
+    (&[0x50, 0xef, 0xd0],
+     &[ 0xb5, 0xa9, 0x82, 0x04, 0x13, 0xc2, 0xbf, 0x82,
+        0x11, 0xfb, 0xbf, 0x5d, 0xf1, 0x33, 0x70, 0x43,
+        0xb3, 0x2f, 0xa4, 0xea, 0xfa, 0xf6, 0x1a, 0x0c,
+        0x8e, 0x9c, 0xce, 0xde ]),
+    (&[0xe5, 0xe0, 0x99, 0x24],
+     &[ 0xfd, 0x19, 0xe7, 0x46, 0x90, 0xd2, 0x91, 0x46,
+        0x7c, 0xe5, 0x9f, 0x07, 0x7d, 0xf3, 0x11, 0x63,
+        0x8f, 0x1c, 0x3a, 0x46, 0xe5, 0x10, 0xd0, 0xe4,
+        0x9a, 0x67, 0x06, 0x2d ]),
+    (&[0x21, 0xeb, 0xec, 0xb9, 0x14],
+     &[ 0x78, 0xf4, 0xa7, 0x1c, 0x21, 0xc6, 0x94, 0x49,
+        0x9c, 0xe1, 0xc7, 0x86, 0x66, 0x11, 0xb1, 0x4a,
+        0xce, 0x70, 0xd9, 0x05, 0x01, 0x2c, 0x35, 0x63,
+        0x23, 0xc7, 0xc7, 0x13 ]),
+    /* SHA-256 */
+    (&[],
+     &[ 0xe3, 0xb0, 0xc4, 0x42, 0x98, 0xfc, 0x1c, 0x14,
+        0x9a, 0xfb, 0xf4, 0xc8, 0x99, 0x6f, 0xb9, 0x24,
+        0x27, 0xae, 0x41, 0xe4, 0x64, 0x9b, 0x93, 0x4c,
+        0xa4, 0x95, 0x99, 0x1b, 0x78, 0x52, 0xb8, 0x55 ]),
+    (&[0xbd],
+     &[ 0x68, 0x32, 0x57, 0x20, 0xaa, 0xbd, 0x7c, 0x82,
+        0xf3, 0x0f, 0x55, 0x4b, 0x31, 0x3d, 0x05, 0x70,
+        0xc9, 0x5a, 0xcc, 0xbb, 0x7d, 0xc4, 0xb5, 0xaa,
+        0xe1, 0x12, 0x04, 0xc0, 0x8f, 0xfe, 0x73, 0x2b ]),
+    (&[0x5f, 0xd4],
+     &[ 0x7c, 0x4f, 0xbf, 0x48, 0x44, 0x98, 0xd2, 0x1b,
+        0x48, 0x7b, 0x9d, 0x61, 0xde, 0x89, 0x14, 0xb2,
+        0xea, 0xda, 0xf2, 0x69, 0x87, 0x12, 0x93, 0x6d,
+        0x47, 0xc3, 0xad, 0xa2, 0x55, 0x8f, 0x67, 0x88 ]),
+    (&[0xb0, 0xbd, 0x69],
+     &[ 0x40, 0x96, 0x80, 0x42, 0x21, 0x09, 0x3d, 0xdc,
+        0xcf, 0xbf, 0x46, 0x83, 0x14, 0x90, 0xea, 0x63,
+        0xe9, 0xe9, 0x94, 0x14, 0x85, 0x8f, 0x8d, 0x75,
+        0xff, 0x7f, 0x64, 0x2c, 0x7c, 0xa6, 0x18, 0x03 ]),
+    (&[0xc9, 0x8c, 0x8e, 0x55],
+     &[ 0x7a, 0xbc, 0x22, 0xc0, 0xae, 0x5a, 0xf2, 0x6c,
+        0xe9, 0x3d, 0xbb, 0x94, 0x43, 0x3a, 0x0e, 0x0b,
+        0x2e, 0x11, 0x9d, 0x01, 0x4f, 0x8e, 0x7f, 0x65,
+        0xbd, 0x56, 0xc6, 0x1c, 0xcc, 0xcd, 0x95, 0x04 ]),
+    (&[0x81, 0xa7, 0x23, 0xd9, 0x66],
+     &[ 0x75, 0x16, 0xfb, 0x8b, 0xb1, 0x13, 0x50, 0xdf,
+        0x2b, 0xf3, 0x86, 0xbc, 0x3c, 0x33, 0xbd, 0x0f,
+        0x52, 0xcb, 0x4c, 0x67, 0xc6, 0xe4, 0x74, 0x5e,
+        0x04, 0x88, 0xe6, 0x2c, 0x2a, 0xea, 0x26, 0x05 ]),
+];
+
+/*
+ * FIPS-180 long message: one million repetitions of 'a'
+ */
+const SHA256_TEST_MILLION_A: [&[u8]; 2] = [
+    /* SHA-224 */
+    &[ 0x20, 0x79, 0x46, 0x55, 0x98, 0x0c, 0x91, 0xd8,
+       0xbb, 0xb4, 0xc1, 0xea, 0x97, 0x61, 0x8a, 0x4b,
+       0xf0, 0x3f, 0x42, 0x58, 0x19, 0x48, 0xb2, 0xee,
+       0x4e, 0xe7, 0xad, 0x67 ],
+    /* SHA-256 */
+    &[ 0xcd, 0xc7, 0x6e, 0x5c, 0x99, 0x14, 0xfb, 0x92,
+       0x81, 0xa1, 0xc7, 0xe2, 0x84, 0xd7, 0x3e, 0x67,
+       0xf1, 0x80, 0x9a, 0x48, 0xa4, 0x97, 0x20, 0x0e,
+       0x04, 0x6d, 0x39, 0xcc, 0xc7, 0x11, 0x2c, 0xd0 ] ];
+
+/*
+ * Checkup routine
+ */
+
+/// The SHA-224 and SHA-256 checkup routine. Returns 0 on success and 1
+/// on failure.
+pub fn self_test(verbose: bool) -> i32 {
+    let mut sha256sum = [0u8; MBEDTLS_SHA256_OUTPUT_SIZE];
+
+    for (i, (msg, sum)) in SHA256_TEST_SHAVS.iter().enumerate() {
+        let k = i % 6;
+        let is224 = i < 6;
+
+        if verbose {
+            print!("  SHA-{} SHAVS #{}: ", 256 - is224 as i32 * 32, k + 1);
+        }
+
+        sha256(msg, &mut sha256sum, is224);
+
+        if sha256sum[..sum.len()] != sum[..] {
+            if verbose {
+                println!("failed");
+            }
+            return 1;
+        }
+
+        if verbose {
+            println!("passed");
+        }
+    }
+
+    /* Feed the long message in pieces to exercise the streaming API */
+    let buf = [b'a'; 1000];
+
+    for (i, sum) in SHA256_TEST_MILLION_A.iter().enumerate() {
+        let is224 = i == 0;
+        let mut ctx = Sha256Context::new();
+
+        if verbose {
+            print!("  SHA-{} long message: ", 256 - is224 as i32 * 32);
+        }
+
+        ctx.starts(is224);
+        for _ in 0..1000 {
+            ctx.update(&buf);
+        }
+        ctx.finish(&mut sha256sum);
+
+        if sha256sum[..sum.len()] != sum[..] {
+            if verbose {
+                println!("failed");
+            }
+            return 1;
+        }
+
+        if verbose {
+            println!("passed");
+        }
+    }
+
+    if verbose {
+        println!();
+    }
+
+    0
+}
@@ -0,0 +1,430 @@
+use crate::rng::header::sha512::Sha512Context;
+use crate::rng::header::sha512::MBEDTLS_SHA512_BLOCK_SIZE; // The SHA-512 block size, in bytes.
+use crate::rng::header::sha512::MBEDTLS_SHA512_OUTPUT_SIZE; // The length of a SHA-512 checksum, in bytes.
+use crate::rng::header::platform_util::Zeroizing; // A buffer wiped when it goes out of scope.
+use crate::rng::platform_util::{platform_zeroize, platform_zeroize_words};
+
+/*
+ * Round constants
+ */
+const K: [u64; 80] = [
+    0x428A2F98D728AE22, 0x7137449123EF65CD,
+    0xB5C0FBCFEC4D3B2F, 0xE9B5DBA58189DBBC,
+    0x3956C25BF348B538, 0x59F111F1B605D019,
+    0x923F82A4AF194F9B, 0xAB1C5ED5DA6D8118,
+    0xD807AA98A3030242, 0x12835B0145706FBE,
+    0x243185BE4EE4B28C, 0x550C7DC3D5FFB4E2,
+    0x72BE5D74F27B896F, 0x80DEB1FE3B1696B1,
+    0x9BDC06A725C71235, 0xC19BF174CF692694,
+    0xE49B69C19EF14AD2, 0xEFBE4786384F25E3,
+    0x0FC19DC68B8CD5B5, 0x240CA1CC77AC9C65,
+    0x2DE92C6F592B0275, 0x4A7484AA6EA6E483,
+    0x5CB0A9DCBD41FBD4, 0x76F988DA831153B5,
+    0x983E5152EE66DFAB, 0xA831C66D2DB43210,
+    0xB00327C898FB213F, 0xBF597FC7BEEF0EE4,
+    0xC6E00BF33DA88FC2, 0xD5A79147930AA725,
+    0x06CA6351E003826F, 0x142929670A0E6E70,
+    0x27B70A8546D22FFC, 0x2E1B21385C26C926,
+    0x4D2C6DFC5AC42AED, 0x53380D139D95B3DF,
+    0x650A73548BAF63DE, 0x766A0ABB3C77B2A8,
+    0x81C2C92E47EDAEE6, 0x92722C851482353B,
+    0xA2BFE8A14CF10364, 0xA81A664BBC423001,
+    0xC24B8B70D0F89791, 0xC76C51A30654BE30,
+    0xD192E819D6EF5218, 0xD69906245565A910,
+    0xF40E35855771202A, 0x106AA07032BBD1B8,
+    0x19A4C116B8D2D0C8, 0x1E376C085141AB53,
+    0x2748774CDF8EEB99, 0x34B0BCB5E19B48A8,
+    0x391C0CB3C5C95A63, 0x4ED8AA4AE3418ACB,
+    0x5B9CCA4F7763E373, 0x682E6FF3D6B2B8A3,
+    0x748F82EE5DEFB2FC, 0x78A5636F43172F60,
+    0x84C87814A1F0AB72, 0x8CC702081A6439EC,
+    0x90BEFFFA23631E28, 0xA4506CEBDE82BDE9,
+    0xBEF9A3F7B2C67915, 0xC67178F2E372532B,
+    0xCA273ECEEA26619C, 0xD186B8C721C0C207,
+    0xEADA7DD6CDE0EB1E, 0xF57D4F7FEE6ED178,
+    0x06F067AA72176FBA, 0x0A637DC5A2C898A6,
+    0x113F9804BEF90DAE, 0x1B710B35131C471B,
+    0x28DB77F523047D84, 0x32CAAB7B40C72493,
+    0x3C9EBE0A15C9BEBC, 0x431D67C49C100D4C,
+    0x4CC5D4BECB3E42B6, 0x597F299CFC657E2A,
+    0x5FCB6FAB3AD6FAEC, 0x6C44198C4A475817 ];
+
+impl Sha512Context {
+    /// This function initializes a SHA-512 context. It must be started
+    /// with starts() before use.
+    pub fn new() -> Self {
+        Sha512Context {
+            total: [0; 2],
+            state: [0; 8],
+            buffer: [0; MBEDTLS_SHA512_BLOCK_SIZE],
+            is384: false,
+        }
+    }
+
+    /// This function starts a SHA-384 or SHA-512 checksum calculation.
+    pub fn starts(&mut self, is384: bool) {
+        self.total = [0; 2];
+
+        self.state = if !is384 {
+            /* SHA-512 */
+            [ 0x6A09E667F3BCC908, 0xBB67AE8584CAA73B,
+              0x3C6EF372FE94F82B, 0xA54FF53A5F1D36F1,
+              0x510E527FADE682D1, 0x9B05688C2B3E6C1F,
+              0x1F83D9ABFB41BD6B, 0x5BE0CD19137E2179 ]
+        } else {
+            /* SHA-384 */
+            [ 0xCBBB9D5DC1059ED8, 0x629A292A367CD507,
+              0x9159015A3070DD17, 0x152FECD8F70E5939,
+              0x67332667FFC00B31, 0x8EB44A8768581511,
+              0xDB0C2E0D64F98FA7, 0x47B5481DBEFA4FA4 ]
+        };
+
+        self.is384 = is384;
+    }
+
+    /// This function processes a single data block within the ongoing
+    /// SHA-512 computation.
+    pub(crate) fn process(&mut self, data: &[u8; MBEDTLS_SHA512_BLOCK_SIZE]) {
+        let mut w = [0u64; 80];
+        let mut a = self.state;
+
+        for (i, chunk) in data.chunks_exact(8).enumerate() {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(chunk);
+            w[i] = u64::from_be_bytes(word);
+        }
+
+        for i in 16..80 {
+            let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+            let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+            w[i] = s1.wrapping_add(w[i - 7]).wrapping_add(s0).wrapping_add(w[i - 16]);
+        }
+
+        for i in 0..80 {
+            let s1 = a[4].rotate_right(14) ^ a[4].rotate_right(18) ^ a[4].rotate_right(41);
+            let ch = (a[4] & a[5]) ^ (!a[4] & a[6]);
+            let temp1 = a[7].wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i]).wrapping_add(w[i]);
+            let s0 = a[0].rotate_right(28) ^ a[0].rotate_right(34) ^ a[0].rotate_right(39);
+            let maj = (a[0] & a[1]) ^ (a[0] & a[2]) ^ (a[1] & a[2]);
+            let temp2 = s0.wrapping_add(maj);
+
+            a[7] = a[6];
+            a[6] = a[5];
+            a[5] = a[4];
+            a[4] = a[3].wrapping_add(temp1);
+            a[3] = a[2];
+            a[2] = a[1];
+            a[1] = a[0];
+            a[0] = temp1.wrapping_add(temp2);
+        }
+
+        for (s, v) in self.state.iter_mut().zip(a.iter()) {
+            *s = s.wrapping_add(*v);
+        }
+
+        platform_zeroize_words(&mut w);
+        platform_zeroize_words(&mut a);
+    }
+
+    /// This function feeds an input buffer into an ongoing SHA-512
+    /// checksum calculation.
+    pub fn update(&mut self, input: &[u8]) {
+        if input.is_empty() {
+            return;
+        }
+
+        let left = (self.total[0] & 0x7F) as usize;
+        let fill = MBEDTLS_SHA512_BLOCK_SIZE - left;
+
+        self.total[0] = self.total[0].wrapping_add(input.len() as u64);
+        if self.total[0] < input.len() as u64 {
+            self.total[1] += 1;
+        }
+
+        let mut input = input;
+
+        if left != 0 && input.len() >= fill {
+            self.buffer[left..].copy_from_slice(&input[..fill]);
+            let block = Zeroizing::new(self.buffer);
+            self.process(&block);
+            input = &input[fill..];
+        } else if left != 0 {
+            self.buffer[left..left + input.len()].copy_from_slice(input);
+            return;
+        }
+
+        let mut blocks = input.chunks_exact(MBEDTLS_SHA512_BLOCK_SIZE);
+        for chunk in &mut blocks {
+            let mut block = Zeroizing::new([0u8; MBEDTLS_SHA512_BLOCK_SIZE]);
+            block.copy_from_slice(chunk);
+            self.process(&block);
+        }
+
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+    }
+
+    /// This function finishes the SHA-512 operation, and writes the result
+    /// to the output buffer. For SHA-384 only the first 48 bytes are
+    /// significant.
+    pub fn finish(&mut self, output: &mut [u8; MBEDTLS_SHA512_OUTPUT_SIZE]) {
+        /*
+         * Add padding: 0x80 then 0x00 until 16 bytes remain for the length
+         */
+        let mut used = (self.total[0] & 0x7F) as usize;
+
+        self.buffer[used] = 0x80;
+        used += 1;
+
+        if used <= 112 {
+            /* Enough room for padding + length in current block */
+            self.buffer[used..112].fill(0);
+        } else {
+            /* We'll need an extra block */
+            self.buffer[used..].fill(0);
+            let block = Zeroizing::new(self.buffer);
+            self.process(&block);
+            self.buffer[..112].fill(0);
+        }
+
+        /*
+         * Add message length
+         */
+        let high = (self.total[0] >> 61) | (self.total[1] << 3);
+        let low = self.total[0] << 3;
+
+        self.buffer[112..120].copy_from_slice(&high.to_be_bytes());
+        self.buffer[120..].copy_from_slice(&low.to_be_bytes());
+
+        let block = Zeroizing::new(self.buffer);
+        self.process(&block);
+
+        /*
+         * Output final state
+         */
+        for (out, s) in output.chunks_exact_mut(8).zip(self.state.iter()) {
+            out.copy_from_slice(&s.to_be_bytes());
+        }
+
+        if self.is384 {
+            output[48..].fill(0);
+        }
+    }
+}
+
+impl Default for Sha512Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Sha512Context {
+    /// This function clears a SHA-512 context.
+    fn drop(&mut self) {
+        platform_zeroize_words(&mut self.total);
+        platform_zeroize_words(&mut self.state);
+        platform_zeroize(&mut self.buffer);
+    }
+}
+
+/// This function calculates the SHA-512 or SHA-384 checksum of a buffer.
+pub fn sha512(input: &[u8], output: &mut [u8; MBEDTLS_SHA512_OUTPUT_SIZE], is384: bool) {
+    let mut ctx = Sha512Context::new();
+
+    ctx.starts(is384);
+    ctx.update(input);
+    ctx.finish(output);
+}
+
+/*
+ * NIST SHAVS byte-oriented short message test vectors
+ * (SHA384ShortMsg.rsp and SHA512ShortMsg.rsp, Len = 0 to 40)
+ */
+const SHA512_TEST_SHAVS: [(&[u8], &[u8]); 12] = [
+    /* SHA-384 */
+    (&[],
+     &[ 0x38, 0xb0, 0x60, 0xa7, 0x51, 0xac, 0x96, 0x38,
+        0x4c, 0xd9, 0x32, 0x7e, 0xb1, 0xb1, 0xe3, 0x6a,
+        0x21, 0xfd, 0xb7, 0x11, 0x14, 0xbe, 0x07, 0x43,
+        0x4c, 0x0c, 0xc7, 0xbf, 0x63, 0xf6, 0xe1, 0xda,
+        0x27, 0x4e, 0xde, 0xbf, 0xe7, 0x6f, 0x65, 0xfb,
+        0xd5, 0x1a, 0xd2, 0xf1, 0x48, 0x98, 0xb9, 0x5b ]),
+    (&[0xab],
+     &[ 0xfb, 0x94, 0xd5, 0xbe, 0x11, 0x88, 0x65, 0xf6,
+        0xfc, 0xbc, 0x97, 0x8b, 0x82, 0x5d, 0xa8, 0x2c,
+        0xff, 0x18, 0x8f, 0xae, 0xc2, 0xf6, 0x6c, 0xb8,
+        0x4b, 0x25, 0x37, 0xd7, 0x4b, 0x49, 0x38, 0x46,
+        0x98, 0x54, 0xb0, 0xca, 0x89, 0xe6, 0x6f, 0xa2,
+        0xe1, 0x82, 0x83, 0x47, 0x36, 0x62, 0x9f, 0x3d ]),
+    (&[0x7c, 0x27],
+     &[ 0x3d, 0x80, 0xbe, 0x46, 0x7d, 0xf8, 0x6d, 0x63,
+        0xab, 0xb9, 0xea, 0x1d, 0x3f, 0x9c, 0xb3, 0x9c,
+        0xd1, 0x98, 0x90, 0xe7, 0xf2, 0xc5, 0x3a, 0x62,
+        0x00, 0xbe, 0xdc, 0x50, 0x06, 0x84, 0x2b, 0x35,
+        0xe8, 0x20, 0xdc, 0x4e, 0x0c, 0xa9, 0x0c, 0xa9,
+        0xb9, 0x7a, 0xb2, 0x3e, 0xf0, 0x70, 0x80, 0xfc ]),
+    (&[0x31, 0xf5, 0xca],
+     &[ 0x78, 0xd5, 0x4b, 0x94, 0x34, 0x21, 0xfd, 0xf7,
+        0xba, 0x90, 0xa7, 0xfb, 0x96, 0x37, 0xc2, 0x07,
+        0x3a, 0xa4, 0x80, 0x45, 0x4b, 0xd8, 0x41, 0xd3,
+        0x9f, 0xf7, 0x2f, 0x45, 0x11, 0xfc, 0x21, 0xfb,
+        0x67, 0x79, 0x7b, 0x65, 0x2c, 0x0c, 0x82, 0x32,
+        0x29, 0x34, 0x28, 0x73, 0xd3, 0xbe, 0xf9, 0x55 ]),
+    (&[0x7b, 0xde, 0xe3, 0xf8],
+     &[ 0x8b, 0xda, 0xfb, 0xa0, 0x77, 0x7e, 0xe4, 0x46,
+        0xc3, 0x43, 0x1c, 0x2d, 0x7b, 0x1f, 0xbb, 0x63,
+        0x10, 0x89, 0xf7, 0x1d, 0x2c, 0xa4, 0x17, 0xab,
+        0xc1, 0xd2, 0x30, 0xe1, 0xab, 0xa6, 0x4e, 0xc2,
+        0xf1, 0xc1, 0x87, 0x47, 0x4a, 0x6f, 0x40, 0x77,
+        0xd3, 0x72, 0xc1, 0x4a, 0xd4, 0x07, 0xf9, 0x9a ]),
+    (&[0x8f, 0x05, 0x60, 0x49, 0x15],
+     &[ 0x50, 0x4e, 0x41, 0x4b, 0xf1, 0xdb, 0x10, 0x60,
+        0xf1, 0x4c, 0x8c, 0x79, 0x9e, 0x25, 0xb1, 0xe0,
+        0xc4, 0xdc, 0xf1, 0x50, 0x4e, 0xbb, 0xd1, 0x29,
+        0x99, 0x8f, 0x0a, 0xe2, 0x83, 0xe6, 0xde, 0x86,
+        0xe0, 0xd3, 0xc7, 0xe8, 0x79, 0xc7, 0x3e, 0xc3,
+        0xb1, 0x83, 0x6c, 0x3e, 0xe8, 0x9c, 0x26, 0x49 ]),
+    /* SHA-512 */
+    (&[],
+     &[ 0xcf, 0x83, 0xe1, 0x35, 0x7e, 0xef, 0xb8, 0xbd,
+        0xf1, 0x54, 0x28, 0x50, 0xd6, 0x6d, 0x80, 0x07,
+        0xd6, 0x20, 0xe4, 0x05, 0x0b, 0x57, 0x15, 0xdc,
+        0x83, 0xf4, 0xa9, 0x21, 0xd3, 0x6c, 0xe9, 0xce,
+        0x47, 0xd0, 0xd1, 0x3c, 0x5d, 0x85, 0xf2, 0xb0,
+        0xff, 0x83, 0x18, 0xd2, 0x87, 0x7e, 0xec, 0x2f,
+        0x63, 0xb9, 0x31, 0xbd, 0x47, 0x41, 0x7a, 0x81,
+        0xa5, 0x38, 0x32, 0x7a, 0xf9, 0x27, 0xda, 0x3e ]),
+    (&[0x8f],
+     &[ 0xe4, 0xcd, 0x2d, 0x19, 0x93, 0x1b, 0x5a, 0xad,
+        0x9c, 0x92, 0x0f, 0x45, 0xf5, 0x6f, 0x6c, 0xe3,
+        0x4e, 0x3d, 0x38, 0xc6, 0xd3, 0x19, 0xa6, 0xe1,
+        0x1d, 0x05, 0x88, 0xab, 0x8b, 0x83, 0x85, 0x76,
+        0xd6, 0xce, 0x6d, 0x68, 0xee, 0xa7, 0xc8, 0x30,
+        0xde, 0x66, 0xe2, 0xbd, 0x96, 0x45, 0x8b, 0xfa,
+        0x7a, 0xaf, 0xbc, 0xbe, 0xc9, 0x81, 0xd4, 0xed,
+        0x04, 0x04, 0x98, 0xc3, 0xdd, 0x95, 0xf2, 0x2a ]),
+    (&[0xe7, 0x24],
+     &[ 0x7d, 0xbb, 0x52, 0x02, 0x21, 0xa7, 0x02, 0x87,
+        0xb2, 0x3d, 0xbc, 0xf6, 0x2b, 0xfc, 0x1b, 0x73,
+        0x13, 0x6d, 0x85, 0x8e, 0x86, 0x26, 0x67, 0x32,
+        0xa7, 0xff, 0xfa, 0x87, 0x5e, 0xca, 0xa2, 0xc1,
+        0xb8, 0xf6, 0x73, 0xb5, 0xc0, 0x65, 0xd3, 0x60,
+        0xc5, 0x63, 0xa7, 0xb9, 0x53, 0x93, 0x49, 0xf5,
+        0xf5, 0x9b, 0xef, 0x8c, 0x0c, 0x59, 0x3f, 0x95,
+        0x87, 0xe3, 0xcd, 0x50, 0xbb, 0x26, 0xa2, 0x31 ]),
+    (&[0xde, 0x4c, 0x90],
+     &[ 0x33, 0xce, 0x98, 0x28, 0x10, 0x45, 0xa5, 0xc4,
+        0xc9, 0xdf, 0x03, 0x63, 0xd8, 0x19, 0x6f, 0x1d,
+        0x7d, 0xfc, 0xd5, 0xee, 0x46, 0xac, 0x89, 0x77,
+        0x6f, 0xd8, 0xa4, 0x34, 0x4c, 0x12, 0xf1, 0x23,
+        0xa6, 0x67, 0x88, 0xaf, 0x5b, 0xd4, 0x1c, 0xef,
+        0xf1, 0x94, 0x1a, 0xa5, 0x63, 0x76, 0x54, 0xb4,
+        0x06, 0x4c, 0x88, 0xc1, 0x4e, 0x00, 0x46, 0x5a,
+        0xb7, 0x9a, 0x2f, 0xc6, 0xc9, 0x7e, 0x10, 0x14 ]),
+    (&[0xa8, 0x01, 0xe9, 0x4b],
+     &[ 0xda, 0xdb, 0x1b, 0x5a, 0x27, 0xf9, 0xfe, 0xce,
+        0x8d, 0x86, 0xad, 0xb2, 0xa5, 0x18, 0x79, 0xbe,
+        0xb1, 0x78, 0x7f, 0xf2, 0x8f, 0x4e, 0x8c, 0xe1,
+        0x62, 0xca, 0xd7, 0xfe, 0xe0, 0xf9, 0x42, 0xef,
+        0xca, 0xbb, 0xf7, 0x38, 0xbc, 0x6f, 0x79, 0x7f,
+        0xc7, 0xcc, 0x79, 0xa3, 0xa7, 0x50, 0x48, 0xcd,
+        0x4c, 0x82, 0xca, 0x07, 0x57, 0xa3, 0x24, 0x69,
+        0x5b, 0xfb, 0x19, 0xa5, 0x57, 0xe5, 0x6e, 0x2f ]),
+    (&[0x94, 0x39, 0x0d, 0x35, 0x02],
+     &[ 0xb6, 0x17, 0x5c, 0x4c, 0x4c, 0xcc, 0xf6, 0x9e,
+        0x0c, 0xe5, 0xf0, 0x31, 0x20, 0x10, 0x88, 0x6e,
+        0xa6, 0xb3, 0x4d, 0x43, 0x67, 0x3f, 0x94, 0x2a,
+        0xe4, 0x24, 0x83, 0xf9, 0xcb, 0xb7, 0xda, 0x81,
+        0x7d, 0xe4, 0xe1, 0x1b, 0x5d, 0x58, 0xe2, 0x5a,
+        0x3d, 0x9b, 0xd7, 0x21, 0xa2, 0x2c, 0xdf, 0xfe,
+        0x1c, 0x40, 0x41, 0x1c, 0xc4, 0x5d, 0xf1, 0x91,
+        0x1f, 0xa5, 0x50, 0x61, 0x29, 0xb6, 0x92, 0x97 ]),
+];
+
+/*
+ * FIPS-180 long message: one million repetitions of 'a'
+ */
+const SHA512_TEST_MILLION_A: [&[u8]; 2] = [
+    /* SHA-384 */
+    &[ 0x9d, 0x0e, 0x18, 0x09, 0x71, 0x64, 0x74, 0xcb,
+       0x08, 0x6e, 0x83, 0x4e, 0x31, 0x0a, 0x4a, 0x1c,
+       0xed, 0x14, 0x9e, 0x9c, 0x00, 0xf2, 0x48, 0x52,
+       0x79, 0x72, 0xce, 0xc5, 0x70, 0x4c, 0x2a, 0x5b,
+       0x07, 0xb8, 0xb3, 0xdc, 0x38, 0xec, 0xc4, 0xeb,
+       0xae, 0x97, 0xdd, 0xd8, 0x7f, 0x3d, 0x89, 0x85 ],
+    /* SHA-512 */
+    &[ 0xe7, 0x18, 0x48, 0x3d, 0x0c, 0xe7, 0x69, 0x64,
+       0x4e, 0x2e, 0x42, 0xc7, 0xbc, 0x15, 0xb4, 0x63,
+       0x8e, 0x1f, 0x98, 0xb1, 0x3b, 0x20, 0x44, 0x28,
+       0x56, 0x32, 0xa8, 0x03, 0xaf, 0xa9, 0x73, 0xeb,
+       0xde, 0x0f, 0xf2, 0x44, 0x87, 0x7e, 0xa6, 0x0a,
+       0x4c, 0xb0, 0x43, 0x2c, 0xe5, 0x77, 0xc3, 0x1b,
+       0xeb, 0x00, 0x9c, 0x5c, 0x2c, 0x49, 0xaa, 0x2e,
+       0x4e, 0xad, 0xb2, 0x17, 0xad, 0x8c, 0xc0, 0x9b ] ];
+
+/*
+ * Checkup routine
+ */
+
+/// The SHA-384 and SHA-512 checkup routine. Returns 0 on success and 1
+/// on failure.
+pub fn self_test(verbose: bool) -> i32 {
+    let mut sha512sum = [0u8; MBEDTLS_SHA512_OUTPUT_SIZE];
+
+    for (i, (msg, sum)) in SHA512_TEST_SHAVS.iter().enumerate() {
+        let k = i % 6;
+        let is384 = i < 6;
+
+        if verbose {
+            print!("  SHA-{} SHAVS #{}: ", 512 - is384 as i32 * 128, k + 1);
+        }
+
+        sha512(msg, &mut sha512sum, is384);
+
+        if sha512sum[..sum.len()] != sum[..] {
+            if verbose {
+                println!("failed");
+            }
+            return 1;
+        }
+
+        if verbose {
+            println!("passed");
+        }
+    }
+
+    /* Feed the long message in pieces to exercise the streaming API */
+    let buf = [b'a'; 1000];
+
+    for (i, sum) in SHA512_TEST_MILLION_A.iter().enumerate() {
+        let is384 = i == 0;
+        let mut ctx = Sha512Context::new();
+
+        if verbose {
+            print!("  SHA-{} long message: ", 512 - is384 as i32 * 128);
+        }
+
+        ctx.starts(is384);
+        for _ in 0..1000 {
+            ctx.update(&buf);
+        }
+        ctx.finish(&mut sha512sum);
+
+        if sha512sum[..sum.len()] != sum[..] {
+            if verbose {
+                println!("failed");
+            }
+            return 1;
+        }
+
+        if verbose {
+            println!("passed");
+        }
+    }
+
+    if verbose {
+        println!();
+    }
+
+    0
+}
@@ -0,0 +1,19 @@
+use std::sync::MutexGuard;
+
+use crate::rng::header::error::Error;
+use crate::rng::header::threading::ThreadingMutex; // The mutex type used by the library.
+
+/// This function initializes a mutex around the data it protects.
+pub fn mutex_init<T>(data: T) -> ThreadingMutex<T> {
+    ThreadingMutex::new(data)
+}
+
+/// This function locks a mutex. The mutex is unlocked when the returned
+/// guard is dropped.
+///
+/// A mutex poisoned by a thread that panicked while holding it is
+/// reported as `Error::ThreadingMutexError`, like a failing
+/// pthread_mutex_lock() in the C threading layer.
+pub fn mutex_lock<T>(mutex: &ThreadingMutex<T>) -> Result<MutexGuard<'_, T>, Error> {
+    mutex.lock().map_err(|_| Error::ThreadingMutexError)
+}
@@ -0,0 +1,134 @@
+/*
+ * C test of the mbedtls_ctr_drbg_* interface
+ *
+ * Links against the cdylib of the crate and uses it the way C code
+ * written against mbedTLS does: the checkup routine, then a context
+ * seeded from a C entropy callback and used through the f_rng shape.
+ * The seed file functions write to the path given as the only argument.
+ */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "mbedtls/ctr_drbg.h"
+
+#define CHECK( expr )                                               \
+    do {                                                            \
+        if( !( expr ) ) {                                           \
+            printf( "FAILED at line %d: %s\n", __LINE__, #expr );   \
+            return( 1 );                                            \
+        }                                                           \
+    } while( 0 )
+
+/* Deterministic entropy: a counter, so that the test is reproducible. */
+static int counter_entropy( void *data, unsigned char *output, size_t len )
+{
+    unsigned char *counter = (unsigned char *) data;
+    size_t i;
+
+    for( i = 0; i < len; i++ )
+        output[i] = (*counter)++;
+
+    return( 0 );
+}
+
+static int failing_entropy( void *data, unsigned char *output, size_t len )
+{
+    (void) data;
+    (void) output;
+    (void) len;
+
+    return( -1 );
+}
+
+/* A consumer taking any generator, like the mbedTLS public key API. */
+static int use_rng( int (*f_rng)(void *, unsigned char *, size_t), void *p_rng,
+                    unsigned char *buf, size_t len )
+{
+    return( f_rng( p_rng, buf, len ) );
+}
+
+int main( int argc, char *argv[] )
+{
+    mbedtls_ctr_drbg_context ctx, ctx2;
+    unsigned char counter = 0, counter2 = 0, before;
+    const char *seed_path;
+    unsigned char buf[64], buf2[64], zero[64];
+    const unsigned char pers[] = "ctr_drbg C test";
+
+    if( argc != 2 )
+    {
+        printf( "usage: %s <seed file path>\n", argv[0] );
+        return( 2 );
+    }
+    seed_path = argv[1];
+
+    CHECK( mbedtls_ctr_drbg_self_test( 1 ) == 0 );
+
+    mbedtls_ctr_drbg_init( &ctx );
+    mbedtls_ctr_drbg_init( &ctx2 );
+
+    /* Using a context before seeding or after freeing fails cleanly. */
+    CHECK( mbedtls_ctr_drbg_seed( &ctx, failing_entropy, NULL, NULL, 0 ) ==
+           MBEDTLS_ERR_CTR_DRBG_ENTROPY_SOURCE_FAILED );
+    mbedtls_ctr_drbg_free( &ctx );
+    CHECK( mbedtls_ctr_drbg_random( &ctx, buf, sizeof( buf ) ) != 0 );
+    mbedtls_ctr_drbg_free( &ctx );
+
+    /* Same entropy and personalization, same output. */
+    mbedtls_ctr_drbg_init( &ctx );
+    CHECK( mbedtls_ctr_drbg_seed( &ctx, counter_entropy, &counter,
+                                  pers, sizeof( pers ) - 1 ) == 0 );
+    CHECK( mbedtls_ctr_drbg_seed( &ctx2, counter_entropy, &counter2,
+                                  pers, sizeof( pers ) - 1 ) == 0 );
+
+    memset( zero, 0, sizeof( zero ) );
+    CHECK( use_rng( mbedtls_ctr_drbg_random, &ctx, buf, sizeof( buf ) ) == 0 );
+    CHECK( use_rng( mbedtls_ctr_drbg_random, &ctx2, buf2, sizeof( buf2 ) ) == 0 );
+    CHECK( memcmp( buf, zero, sizeof( buf ) ) != 0 );
+    CHECK( memcmp( buf, buf2, sizeof( buf ) ) == 0 );
+
+    /* Additional data, reseeding and updating change the stream. */
+    CHECK( mbedtls_ctr_drbg_random_with_add( &ctx, buf, sizeof( buf ),
+                                             pers, sizeof( pers ) - 1 ) == 0 );
+    CHECK( mbedtls_ctr_drbg_random( &ctx2, buf2, sizeof( buf2 ) ) == 0 );
+    CHECK( memcmp( buf, buf2, sizeof( buf ) ) != 0 );
+
+    /* A reseed takes the entropy length set last from the callback. */
+    before = counter;
+    CHECK( mbedtls_ctr_drbg_reseed( &ctx, NULL, 0 ) == 0 );
+    CHECK( (unsigned char)( counter - before ) == MBEDTLS_CTR_DRBG_ENTROPY_LEN );
+    mbedtls_ctr_drbg_set_entropy_len( &ctx, 32 );
+    before = counter;
+    CHECK( mbedtls_ctr_drbg_reseed( &ctx, NULL, 0 ) == 0 );
+    CHECK( (unsigned char)( counter - before ) == 32 );
+
+    CHECK( mbedtls_ctr_drbg_update_ret( &ctx, pers, sizeof( pers ) - 1 ) == 0 );
+    mbedtls_ctr_drbg_set_prediction_resistance( &ctx, MBEDTLS_CTR_DRBG_PR_ON );
+    mbedtls_ctr_drbg_set_reseed_interval( &ctx, 1 );
+    CHECK( mbedtls_ctr_drbg_random( &ctx, buf, sizeof( buf ) ) == 0 );
+
+    /* Limits are reported with the mbedTLS codes. */
+    {
+        unsigned char big[MBEDTLS_CTR_DRBG_MAX_REQUEST + 1];
+        CHECK( mbedtls_ctr_drbg_random( &ctx, big, sizeof( big ) ) ==
+               MBEDTLS_ERR_CTR_DRBG_REQUEST_TOO_BIG );
+    }
+    CHECK( mbedtls_ctr_drbg_set_nonce_len( &ctx, 0 ) ==
+           MBEDTLS_ERR_CTR_DRBG_ENTROPY_SOURCE_FAILED );
+
+    /* A seed file written by one context updates another. */
+    CHECK( mbedtls_ctr_drbg_write_seed_file( &ctx, seed_path ) == 0 );
+    CHECK( mbedtls_ctr_drbg_update_seed_file( &ctx2, seed_path ) == 0 );
+    CHECK( mbedtls_ctr_drbg_update_seed_file( &ctx, seed_path ) == 0 );
+    CHECK( mbedtls_ctr_drbg_write_seed_file( &ctx, NULL ) ==
+           MBEDTLS_ERR_CTR_DRBG_FILE_IO_ERROR );
+    CHECK( mbedtls_ctr_drbg_update_seed_file( &ctx, NULL ) ==
+           MBEDTLS_ERR_CTR_DRBG_FILE_IO_ERROR );
+
+    mbedtls_ctr_drbg_free( &ctx );
+    mbedtls_ctr_drbg_free( &ctx2 );
+
+    printf( "C interface test passed\n" );
+    return( 0 );
+}
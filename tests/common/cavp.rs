@@ -0,0 +1,170 @@
+/*
+ * NIST CAVP DRBGVS response files
+ *
+ * The parser and runner shared by the CAVP tests of the DRBGs. A test
+ * supplies two closures: one that maps a section header such as
+ * "AES-256 use df" or "SHA-256" to its parameters (None when the section
+ * does not apply to this build), and one that instantiates the DRBG for a
+ * case and returns the ReturnedBits it generates.
+ */
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use mbed::rng::header::error::Error;
+
+/* The CAVP flow a response file follows. */
+#[derive(Clone, Copy, PartialEq)]
+pub enum Mode {
+    PrTrue,   /* instantiate, generate (PR), generate (PR) */
+    PrFalse,  /* instantiate, reseed, generate, generate */
+    NoReseed, /* instantiate, generate, generate */
+}
+
+impl Mode {
+    /*
+     * The name of the response file for `drbg`, after its drbgvectors
+     * directory: "CTR_DRBG" gives CTR_DRBG_pr_true.rsp, and so on.
+     */
+    pub fn file_name(self, drbg: &str) -> String {
+        match self {
+            Mode::PrTrue => format!("{}_pr_true.rsp", drbg),
+            Mode::PrFalse => format!("{}_pr_false.rsp", drbg),
+            Mode::NoReseed => format!("{}_no_reseed.rsp", drbg),
+        }
+    }
+}
+
+/* One COUNT block: its fields in file order, values hex-decoded. */
+#[derive(Default)]
+pub struct Case {
+    pub count: String,
+    pub fields: Vec<(String, Vec<u8>)>,
+}
+
+impl Case {
+    pub fn all(&self, name: &str) -> Vec<&[u8]> {
+        self.fields.iter().filter(|(k, _)| k == name).map(|(_, v)| &v[..]).collect()
+    }
+
+    pub fn get(&self, name: &str, nth: usize) -> &[u8] {
+        self.all(name).get(nth).copied().unwrap_or(&[])
+    }
+}
+
+#[derive(Default)]
+struct Outcome {
+    passed: usize,
+    skipped: usize,
+    failures: Vec<String>,
+}
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len() / 2)
+        .map(|i| u8::from_str_radix(&s[2 * i..2 * i + 2], 16).expect("bad hex in response file"))
+        .collect()
+}
+
+fn hex(b: &[u8]) -> String {
+    b.iter().map(|x| format!("{:02x}", x)).collect()
+}
+
+/*
+ * Runs every applicable case of the response file at `path`, adding the
+ * results to `outcome`.
+ */
+fn run_file<T, S, R>(path: &Path, outcome: &mut Outcome, select: S, run: R)
+where
+    S: Fn(&str) -> Option<T>,
+    R: Fn(&T, &Case) -> Result<Vec<u8>, Error>,
+{
+    let text = fs::read_to_string(path).expect("cannot read response file");
+    let mut section = String::new();
+    let mut params = None;
+    let mut case: Option<Case> = None;
+
+    for line in text.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            let header = line.trim_start_matches('[').trim_end_matches(']');
+            if !header.contains('=') {
+                section = header.to_string();
+                params = select(header);
+            }
+            continue;
+        }
+
+        let (key, value) = match line.find('=') {
+            Some(i) => (line[..i].trim(), line[i + 1..].trim()),
+            None => continue,
+        };
+
+        if key == "COUNT" {
+            case = Some(Case { count: value.to_string(), ..Case::default() });
+            continue;
+        }
+
+        let c = match case.as_mut() {
+            Some(c) => c,
+            None => continue,
+        };
+        c.fields.push((key.to_string(), unhex(value)));
+
+        if key != "ReturnedBits" {
+            continue;
+        }
+
+        let c = case.take().unwrap();
+        let params = match params.as_ref() {
+            Some(params) => params,
+            None => {
+                outcome.skipped += 1;
+                continue;
+            }
+        };
+
+        let expected = c.get("ReturnedBits", 0);
+        match run(params, &c) {
+            Ok(ref output) if output[..] == *expected => outcome.passed += 1,
+            Ok(output) => outcome.failures.push(format!(
+                "{} [{}] COUNT = {}: expected {}, got {}",
+                path.display(), section, c.count, hex(expected), hex(&output))),
+            Err(err) => outcome.failures.push(format!(
+                "{} [{}] COUNT = {}: {}", path.display(), section, c.count, err)),
+        }
+    }
+}
+
+/*
+ * Runs the response file `file_name` of every directory in `dirs`, each
+ * of which must hold it, and checks that no case failed and that at
+ * least one applied. Counts are printed per file, so that the coverage
+ * each directory gives can be told apart.
+ */
+pub fn run_dirs<T, S, R>(dirs: &[PathBuf], file_name: &str, select: S, run: R)
+where
+    S: Fn(&str) -> Option<T>,
+    R: Fn(&T, &Case) -> Result<Vec<u8>, Error>,
+{
+    let mut outcome = Outcome::default();
+
+    for dir in dirs {
+        let path = dir.join(file_name);
+        if !path.exists() {
+            panic!("{}: no such response file", path.display());
+        }
+
+        let (passed, skipped) = (outcome.passed, outcome.skipped);
+        run_file(&path, &mut outcome, &select, &run);
+        println!("{}: {} passed, {} skipped", path.display(),
+                 outcome.passed - passed, outcome.skipped - skipped);
+    }
+
+    assert!(outcome.failures.is_empty(), "failed cases:\n{}", outcome.failures.join("\n"));
+    assert!(outcome.passed > 0, "no applicable case in {}", file_name);
+}
@@ -0,0 +1,5 @@
+/*
+ * Code shared by the integration tests.
+ */
+
+pub mod cavp;
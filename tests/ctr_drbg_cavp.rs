@@ -0,0 +1,139 @@
+/*
+ * NIST CAVP known-answer tests for CTR_DRBG
+ *
+ * Runs the DRBGVS response files (CTR_DRBG.rsp format) of
+ * tests/data/ctr_drbg/nist, and of $CTR_DRBG_CAVP_DIR when it is set, so
+ * the complete official set can be checked without bundling it.
+ *
+ * A case is applicable when its section uses AES with the key size this
+ * build was compiled for, with or without the derivation function.
+ * TDEA sections and other key sizes are skipped.
+ *
+ * tests/data/ctr_drbg/nist holds NIST vectors only, and has no AES-192
+ * section and no [AES-128 no df] section. The OpenSSL-computed answers of
+ * tests/data/ctr_drbg/openssl_computed fill those gaps as a
+ * cross-implementation check; the openssl_* tests run them apart, so they
+ * never count as CAVP cases.
+ *
+ * With the ctr-drbg-192-bit-key feature no bundled case applies, so the
+ * CAVP tests are ignored: run them with --ignored and CTR_DRBG_CAVP_DIR
+ * pointing to the NIST drbgvectors, where they must pass AES-192 cases.
+ */
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use mbed::rng::header::ctr_drbg::CtrDrbg;
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_KEYBITS;
+use mbed::rng::header::error::Error;
+
+mod common;
+
+use common::cavp::{self, Case, Mode};
+
+/*
+ * Parse a section header such as "AES-256 use df" into use_df, if the
+ * section uses the key size of this build.
+ */
+fn parse_cipher(header: &str) -> Option<bool> {
+    let applicable = header.starts_with("AES-")
+        && header[4..].split(' ').next() == Some(&MBEDTLS_CTR_DRBG_KEYBITS.to_string()[..]);
+
+    if applicable { Some(header.ends_with("use df")) } else { None }
+}
+
+fn run_case(mode: Mode, use_df: bool, case: &Case) -> Result<Vec<u8>, Error> {
+    let mut ctx = if use_df { CtrDrbg::new() } else { CtrDrbg::new_no_df() };
+    let entropy_input = case.get("EntropyInput", 0);
+    let expected_len = case.get("ReturnedBits", 0).len();
+
+    /* The entropy callback hands out the inputs in the order they are
+     * consumed, like mbedtls_test_entropy_func(). */
+    let mut entropy = entropy_input.to_vec();
+    entropy.extend_from_slice(case.get("EntropyInputReseed", 0));
+    for e in case.all("EntropyInputPR") {
+        entropy.extend_from_slice(e);
+    }
+    let mut offset = 0;
+
+    /* custom = nonce || personalization_string, so that the derivation
+     * function sees entropy_input || nonce || personalization_string. */
+    let mut custom = case.get("Nonce", 0).to_vec();
+    custom.extend_from_slice(case.get("PersonalizationString", 0));
+
+    ctx.set_entropy_len(entropy_input.len());
+    ctx.set_nonce_len(0)?;
+    ctx.seed(move |buf: &mut [u8]| {
+        if offset + buf.len() > entropy.len() {
+            return Err(Error::CtrDrbgEntropySourceFailed);
+        }
+        buf.copy_from_slice(&entropy[offset..offset + buf.len()]);
+        offset += buf.len();
+        Ok(())
+    }, &custom)?;
+
+    match mode {
+        Mode::PrTrue => ctx.set_prediction_resistance(true),
+        Mode::PrFalse => ctx.reseed(case.get("AdditionalInputReseed", 0))?,
+        Mode::NoReseed => {}
+    }
+
+    let mut output = vec![0u8; expected_len];
+    ctx.generate_with_additional(&mut output, case.get("AdditionalInput", 0))?;
+    ctx.generate_with_additional(&mut output, case.get("AdditionalInput", 1))?;
+
+    Ok(output)
+}
+
+fn run_mode(mode: Mode) {
+    let mut dirs = vec![Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/ctr_drbg/nist")];
+    if let Some(dir) = env::var_os("CTR_DRBG_CAVP_DIR") {
+        dirs.push(PathBuf::from(dir));
+    }
+
+    cavp::run_dirs(&dirs, &mode.file_name("CTR_DRBG"), parse_cipher,
+                   |&use_df, case| run_case(mode, use_df, case));
+}
+
+fn run_openssl_mode(mode: Mode) {
+    let dirs = [Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/ctr_drbg/openssl_computed")];
+
+    cavp::run_dirs(&dirs, &mode.file_name("CTR_DRBG"), parse_cipher,
+                   |&use_df, case| run_case(mode, use_df, case));
+}
+
+#[test]
+#[cfg_attr(all(feature = "ctr-drbg-192-bit-key", not(feature = "ctr-drbg-128-bit-key")),
+           ignore = "no NIST AES-192 vectors are bundled; needs CTR_DRBG_CAVP_DIR")]
+fn cavp_prediction_resistance() {
+    run_mode(Mode::PrTrue);
+}
+
+#[test]
+#[cfg_attr(all(feature = "ctr-drbg-192-bit-key", not(feature = "ctr-drbg-128-bit-key")),
+           ignore = "no NIST AES-192 vectors are bundled; needs CTR_DRBG_CAVP_DIR")]
+fn cavp_reseed() {
+    run_mode(Mode::PrFalse);
+}
+
+#[test]
+#[cfg_attr(all(feature = "ctr-drbg-192-bit-key", not(feature = "ctr-drbg-128-bit-key")),
+           ignore = "no NIST AES-192 vectors are bundled; needs CTR_DRBG_CAVP_DIR")]
+fn cavp_no_reseed() {
+    run_mode(Mode::NoReseed);
+}
+
+#[test]
+fn openssl_prediction_resistance() {
+    run_openssl_mode(Mode::PrTrue);
+}
+
+#[test]
+fn openssl_reseed() {
+    run_openssl_mode(Mode::PrFalse);
+}
+
+#[test]
+fn openssl_no_reseed() {
+    run_openssl_mode(Mode::NoReseed);
+}
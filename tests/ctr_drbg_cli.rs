@@ -0,0 +1,108 @@
+/*
+ * The ctr-drbg program
+ *
+ * Runs the binary that cargo builds for the tests: encoded output, a seed
+ * file created and then verified, and the exit status of bad usage.
+ */
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_MAX_INPUT; // The maximum number of additional input bytes.
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN; // The length of the seed file header.
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN; // The length of the seed file integrity tag.
+
+fn ctr_drbg(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ctr-drbg")).args(args).output().unwrap()
+}
+
+/*
+ * A path in a temporary directory of its own, removed on drop.
+ */
+struct TempPath(PathBuf);
+
+impl TempPath {
+    fn new(name: &str) -> Self {
+        let dir = env::temp_dir().join(format!("ctr_drbg_cli.{}.{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        TempPath(dir.join("seed"))
+    }
+
+    fn as_str(&self) -> &str {
+        self.0.to_str().unwrap()
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(self.0.parent().unwrap());
+    }
+}
+
+#[test]
+fn generate_base64() {
+    let output = ctr_drbg(&["generate", "5", "-f", "base64"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    /* Five bytes are two groups, the last padded with one "=". */
+    assert_eq!(stdout.len(), 9, "{:?}", stdout);
+    assert!(stdout.ends_with("=\n"), "{:?}", stdout);
+    assert!(stdout[..7].bytes().all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/'), "{:?}", stdout);
+}
+
+#[test]
+fn seed_create_then_verify() {
+    let path = TempPath::new("verify");
+
+    let output = ctr_drbg(&["seed", "create", path.as_str()]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let data = fs::read(&path.0).unwrap();
+    assert_eq!(data.len(), MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN + MBEDTLS_CTR_DRBG_MAX_INPUT + MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN);
+
+    let output = ctr_drbg(&["seed", "verify", path.as_str()]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("valid seed file, version 1"), "{}", stdout);
+    assert_eq!(fs::read(&path.0).unwrap(), data, "verify rewrote the file");
+
+    /* A damaged file fails verification, and is left alone. */
+    let mut corrupted = data.clone();
+    corrupted[MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN] ^= 0x01;
+    fs::write(&path.0, &corrupted).unwrap();
+
+    let output = ctr_drbg(&["seed", "verify", path.as_str()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("reading the seed file failed"));
+    assert_eq!(fs::read(&path.0).unwrap(), corrupted);
+}
+
+#[test]
+fn unknown_option_exits_with_2() {
+    let output = ctr_drbg(&["--no-such-option", "generate", "1"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    assert!(stderr.contains("unknown option: --no-such-option"), "{}", stderr);
+    assert!(stderr.contains("usage: ctr-drbg"), "{}", stderr);
+}
+
+#[test]
+fn entropy_len_out_of_range_exits_with_2() {
+    for len in ["0", "47", "385"] {
+        let output = ctr_drbg(&["-e", len, "generate", "1"]);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        assert_eq!(output.status.code(), Some(2), "-e {}: {}", len, stderr);
+        assert!(output.stdout.is_empty());
+        assert!(stderr.contains(&format!("invalid value for -e: {} (must be 48 to 384)", len)), "{}", stderr);
+    }
+
+    let output = ctr_drbg(&["-e", "48", "generate", "1"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(output.stdout.len(), 1);
+}
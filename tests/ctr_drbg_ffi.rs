@@ -0,0 +1,65 @@
+/*
+ * C interface of CTR_DRBG
+ *
+ * Compiles tests/c/ctr_drbg_test.c against include/mbedtls/ctr_drbg.h,
+ * links it to the cdylib that cargo builds next to the test binary,
+ * and runs it with a seed file path in a temporary directory. The C
+ * compiler is $CC, or cc; without one the test is skipped.
+ */
+
+#![cfg(unix)]
+
+use std::env;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/*
+ * The directory holding the library: target/<profile>/deps, where cargo
+ * builds every crate type of the library along with the test binaries
+ */
+fn library_dir() -> PathBuf {
+    let exe = env::current_exe().expect("no path to the test binary");
+    exe.parent().expect("test binary without a directory").to_path_buf()
+}
+
+#[test]
+fn c_program_links_and_runs_self_test() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let lib_dir = library_dir();
+    let out_dir = env::temp_dir().join(format!("ctr_drbg_ffi.{}", std::process::id()));
+    std::fs::create_dir_all(&out_dir).unwrap();
+    let exe = out_dir.join("ctr_drbg_test");
+
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(&cc)
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I").arg(root.join("include"))
+        .arg(root.join("tests/c/ctr_drbg_test.c"))
+        .arg("-o").arg(&exe)
+        .arg("-L").arg(&lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-lmbed")
+        .status();
+
+    let status = match status {
+        Ok(status) => status,
+        Err(ref err) if err.kind() == ErrorKind::NotFound => {
+            eprintln!("skipped: no C compiler ({})", cc);
+            return;
+        }
+        Err(err) => panic!("cannot run {}: {}", cc, err),
+    };
+    assert!(status.success(), "compiling the C test failed");
+
+    let seed_path = out_dir.join("seed");
+    let output = Command::new(&exe).arg(&seed_path).output().unwrap();
+    let seed_written = seed_path.exists();
+    let _ = std::fs::remove_dir_all(&out_dir);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "C test failed:\n{}", stdout);
+    assert!(stdout.contains("C interface test passed"), "{}", stdout);
+    assert!(seed_written, "the C test wrote no seed file");
+}
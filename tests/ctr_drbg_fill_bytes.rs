@@ -0,0 +1,89 @@
+/*
+ * Requests of any length on CTR_DRBG
+ *
+ * fill_bytes() splits its output into generate requests of at most
+ * MBEDTLS_CTR_DRBG_MAX_REQUEST bytes. Each test compares it with the
+ * same requests made one by one on a twin context, seeded from the same
+ * deterministic entropy.
+ */
+
+use mbed::rng::header::ctr_drbg::CtrDrbg;
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_MAX_REQUEST; // The maximum number of requested bytes per call.
+use mbed::rng::header::error::Error;
+
+const OUTPUT_LEN: usize = 3000;
+
+/* The chunks fill_bytes(OUTPUT_LEN) is expected to request. */
+const CHUNKS: [usize; 3] = [1024, 1024, 952];
+
+/*
+ * Returns a context seeded from a counter, so that two calls give
+ * identical contexts.
+ */
+fn seeded() -> CtrDrbg<'static> {
+    let mut ctx = CtrDrbg::new();
+    let mut next = 0u8;
+
+    ctx.seed(move |buf: &mut [u8]| -> Result<(), Error> {
+        for b in buf.iter_mut() {
+            *b = next;
+            next = next.wrapping_add(1);
+        }
+        Ok(())
+    }, b"fill_bytes").unwrap();
+    ctx
+}
+
+/*
+ * Generates CHUNKS one request at a time, with `additional[i]` as the
+ * additional data of chunk i.
+ */
+fn generate_chunks(ctx: &mut CtrDrbg<'_>, additional: [&[u8]; 3]) -> Vec<u8> {
+    let mut output = vec![0u8; OUTPUT_LEN];
+    let mut offset = 0;
+
+    for (len, add) in CHUNKS.iter().zip(additional.iter()) {
+        ctx.generate_with_additional(&mut output[offset..offset + len], add).unwrap();
+        offset += len;
+    }
+    output
+}
+
+#[test]
+fn fill_bytes_splits_into_max_requests() {
+    assert_eq!(CHUNKS[0], MBEDTLS_CTR_DRBG_MAX_REQUEST);
+    assert_eq!(CHUNKS.iter().sum::<usize>(), OUTPUT_LEN);
+
+    let mut ctx = seeded();
+    let mut twin = seeded();
+
+    let mut output = vec![0u8; OUTPUT_LEN];
+    ctx.fill_bytes(&mut output).unwrap();
+
+    assert_eq!(output, generate_chunks(&mut twin, [&[], &[], &[]]));
+
+    /* Both contexts made the same number of requests. */
+    let mut next = [0u8; 32];
+    let mut twin_next = [0u8; 32];
+    ctx.generate(&mut next).unwrap();
+    twin.generate(&mut twin_next).unwrap();
+    assert_eq!(next, twin_next);
+}
+
+#[test]
+fn fill_bytes_additional_goes_into_first_request_only() {
+    let additional: &[u8] = b"additional input";
+    let mut ctx = seeded();
+    let mut twin = seeded();
+    let mut every = seeded();
+
+    let mut output = vec![0u8; OUTPUT_LEN];
+    ctx.fill_bytes_with_additional(&mut output, additional).unwrap();
+
+    assert_eq!(output, generate_chunks(&mut twin, [additional, &[], &[]]));
+
+    /* Giving it to every request would change the later chunks. */
+    let repeated = generate_chunks(&mut every, [additional, additional, additional]);
+    assert_eq!(output[..CHUNKS[0]], repeated[..CHUNKS[0]]);
+    assert_ne!(output[CHUNKS[0]..], repeated[CHUNKS[0]..]);
+}
@@ -0,0 +1,102 @@
+/*
+ * Fork safety of CTR_DRBG
+ *
+ * A context seeded before fork() must not hand the same output to the
+ * parent and to its children: the first generation in a new process
+ * reseeds from the entropy source. Each child sends what it generated
+ * back through a pipe and exits without running any destructor.
+ */
+
+#![cfg(unix)]
+
+use std::fs::File;
+use std::io::Read;
+use std::os::raw::{c_int, c_void};
+use std::os::unix::io::FromRawFd;
+
+use mbed::rng::header::ctr_drbg::CtrDrbg;
+
+extern "C" {
+    fn fork() -> c_int;
+    fn pipe(fds: *mut c_int) -> c_int;
+    fn write(fd: c_int, buf: *const c_void, count: usize) -> isize;
+    fn close(fd: c_int) -> c_int;
+    fn waitpid(pid: c_int, status: *mut c_int, options: c_int) -> c_int;
+    fn _exit(status: c_int) -> !;
+}
+
+const OUTPUT_LEN: usize = 32;
+
+/*
+ * Generates OUTPUT_LEN bytes from ctx in a forked child and returns them.
+ */
+fn generate_in_child(ctx: &mut CtrDrbg<'_>) -> [u8; OUTPUT_LEN] {
+    let mut fds = [0 as c_int; 2];
+    assert_eq!(unsafe { pipe(fds.as_mut_ptr()) }, 0, "pipe() failed");
+
+    let pid = unsafe { fork() };
+    assert!(pid >= 0, "fork() failed");
+
+    if pid == 0 {
+        /* Child: no allocation, no unwinding, no destructors. */
+        let mut buf = [0u8; OUTPUT_LEN];
+        let status = match ctx.generate(&mut buf) {
+            Ok(()) => {
+                let n = unsafe { write(fds[1], buf.as_ptr() as *const c_void, buf.len()) };
+                (n != OUTPUT_LEN as isize) as c_int
+            }
+            Err(_) => 1,
+        };
+        unsafe { _exit(status) };
+    }
+
+    unsafe { close(fds[1]) };
+    let mut pipe_out = unsafe { File::from_raw_fd(fds[0]) };
+    let mut buf = [0u8; OUTPUT_LEN];
+    let read = pipe_out.read_exact(&mut buf);
+
+    let mut status: c_int = -1;
+    assert_eq!(unsafe { waitpid(pid, &mut status, 0) }, pid, "waitpid() failed");
+    assert_eq!(status, 0, "child failed to generate");
+    read.expect("short read from child");
+
+    buf
+}
+
+#[test]
+fn forked_children_produce_distinct_streams() {
+    let mut ctx = CtrDrbg::new();
+    let mut parent = [0u8; OUTPUT_LEN];
+
+    ctx.seed_default(b"ctr_drbg fork test").expect("seeding failed");
+    ctx.generate(&mut parent).expect("generation failed");
+
+    let first = generate_in_child(&mut ctx);
+    let second = generate_in_child(&mut ctx);
+    ctx.generate(&mut parent).expect("generation failed");
+
+    assert_ne!(first, second, "both children produced the same output");
+    assert_ne!(first, parent, "a child produced the parent's output");
+    assert_ne!(second, parent, "a child produced the parent's output");
+}
+
+#[test]
+fn fork_forces_reseed_from_entropy_source() {
+    /* A deterministic source that only differs between processes: without
+     * the reseed, child and parent would share the same state. */
+    let mut ctx = CtrDrbg::new();
+    let mut parent = [0u8; OUTPUT_LEN];
+
+    ctx.seed(|buf: &mut [u8]| {
+        let pid = std::process::id().to_be_bytes();
+        for (i, b) in buf.iter_mut().enumerate() {
+            *b = pid[i % pid.len()] ^ i as u8;
+        }
+        Ok(())
+    }, b"").expect("seeding failed");
+
+    let child = generate_in_child(&mut ctx);
+    ctx.generate(&mut parent).expect("generation failed");
+
+    assert_ne!(child, parent, "the child did not reseed after fork()");
+}
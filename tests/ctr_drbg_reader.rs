@@ -0,0 +1,161 @@
+/*
+ * CTR_DRBG as a reader
+ *
+ * CtrDrbgReader must give the same stream whatever the read sizes: each
+ * test compares it with fill_bytes() of the same total length on a twin
+ * context, seeded from the same deterministic entropy. Generator errors
+ * must come out as io::Error carrying the mbedTLS code.
+ */
+
+use std::io::{self, BufRead, Read};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use mbed::rng::header::ctr_drbg::CtrDrbg;
+use mbed::rng::header::ctr_drbg::CtrDrbgReader; // A CTR_DRBG context read as a stream.
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_MAX_REQUEST; // The maximum number of requested bytes per call.
+use mbed::rng::header::ctr_drbg::MBEDTLS_ERR_CTR_DRBG_ENTROPY_SOURCE_FAILED; // The entropy source failed.
+use mbed::rng::header::error::Error;
+
+/*
+ * Returns a context seeded from a counter, so that two calls give
+ * identical contexts.
+ */
+fn seeded() -> CtrDrbg<'static> {
+    let mut ctx = CtrDrbg::new();
+    let mut next = 0u8;
+
+    ctx.seed(move |buf: &mut [u8]| -> Result<(), Error> {
+        for b in buf.iter_mut() {
+            *b = next;
+            next = next.wrapping_add(1);
+        }
+        Ok(())
+    }, b"reader").unwrap();
+    ctx
+}
+
+/*
+ * Returns fill_bytes() output of `len` bytes from a fresh twin.
+ */
+fn expected(len: usize) -> Vec<u8> {
+    let mut output = vec![0u8; len];
+    seeded().fill_bytes(&mut output).unwrap();
+    output
+}
+
+#[test]
+fn small_reads_and_fill_buf_match_fill_bytes() {
+    let mut reader = CtrDrbgReader::new(seeded());
+    let mut output = Vec::new();
+
+    /* Read sizes that straddle request boundaries, mixed with
+     * fill_buf()/consume(). */
+    for (i, &len) in [1usize, 7, 100, 916, 3, 1021, 500, 13].iter().cycle().take(24).enumerate() {
+        if i % 3 == 2 {
+            let available = reader.fill_buf().unwrap();
+            let n = available.len().min(len);
+            output.extend_from_slice(&available[..n]);
+            reader.consume(n);
+        } else {
+            let mut buf = vec![0u8; len];
+            reader.read_exact(&mut buf).unwrap();
+            output.extend_from_slice(&buf);
+        }
+    }
+
+    assert!(output.len() > 3 * MBEDTLS_CTR_DRBG_MAX_REQUEST);
+    assert_eq!(output, expected(output.len()));
+}
+
+#[test]
+fn large_read_bypasses_the_buffer() {
+    let len = 2 * MBEDTLS_CTR_DRBG_MAX_REQUEST + 952;
+
+    /* With nothing buffered, whole requests go straight into the
+     * caller's buffer, and the rest comes from the next request. */
+    let mut reader = CtrDrbgReader::new(seeded());
+    let mut output = vec![0u8; len];
+    assert_eq!(reader.read(&mut output).unwrap(), 2 * MBEDTLS_CTR_DRBG_MAX_REQUEST);
+    reader.read_exact(&mut output[2 * MBEDTLS_CTR_DRBG_MAX_REQUEST..]).unwrap();
+    assert_eq!(output, expected(len));
+
+    /* With output buffered, that comes first. */
+    let mut reader = CtrDrbgReader::new(seeded());
+    let mut output = vec![0u8; len];
+    reader.read_exact(&mut output[..10]).unwrap();
+    assert_eq!(reader.read(&mut output[10..]).unwrap(), MBEDTLS_CTR_DRBG_MAX_REQUEST - 10);
+    reader.read_exact(&mut output[MBEDTLS_CTR_DRBG_MAX_REQUEST..]).unwrap();
+    assert_eq!(output, expected(len));
+}
+
+#[test]
+fn copy_of_take_yields_n_bytes() {
+    let n = 5000u64;
+    let mut sink = Vec::new();
+
+    let copied = io::copy(&mut CtrDrbgReader::new(seeded()).take(n), &mut sink).unwrap();
+
+    assert_eq!(copied, n);
+    assert_eq!(sink, expected(n as usize));
+}
+
+#[test]
+fn empty_read_does_not_generate() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let source_calls = Arc::clone(&calls);
+    let mut ctx = CtrDrbg::new();
+    let mut buf = [0u8; 16];
+
+    /* With a reseed interval of 1, the request after one generate()
+     * reseeds: an empty read that generated would show up as an extra
+     * call to the entropy source. */
+    ctx.seed(move |buf: &mut [u8]| -> Result<(), Error> {
+        source_calls.fetch_add(1, Ordering::SeqCst);
+        buf.fill(0x5A);
+        Ok(())
+    }, b"reader").unwrap();
+    ctx.set_reseed_interval(1);
+
+    let mut reader = CtrDrbgReader::new(ctx);
+    assert_eq!(reader.read(&mut []).unwrap(), 0);
+    assert_eq!(reader.read(&mut []).unwrap(), 0);
+
+    let mut ctx = reader.into_inner();
+    ctx.generate(&mut buf).unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    ctx.generate(&mut buf).unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn drbg_error_is_an_io_error() {
+    let failing = Arc::new(AtomicBool::new(false));
+    let source_fails = Arc::clone(&failing);
+    let mut ctx = CtrDrbg::new();
+    let mut buf = [0u8; 16];
+
+    /* A source that fails once the flag is set, and a generator that
+     * goes back to it on every request. */
+    ctx.seed(move |buf: &mut [u8]| -> Result<(), Error> {
+        if source_fails.load(Ordering::SeqCst) {
+            return Err(Error::CtrDrbgEntropySourceFailed);
+        }
+        buf.fill(0x5A);
+        Ok(())
+    }, b"reader").unwrap();
+    ctx.set_prediction_resistance(true);
+    failing.store(true, Ordering::SeqCst);
+
+    let mut reader = CtrDrbgReader::new(ctx);
+    let err = reader.read(&mut buf).unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+    assert_eq!(Error::from_io(&err), Some(Error::CtrDrbgEntropySourceFailed));
+    assert_eq!(Error::from_io(&err).map(|e| e.code()), Some(MBEDTLS_ERR_CTR_DRBG_ENTROPY_SOURCE_FAILED));
+
+    /* Through fill_buf() and io::copy() as well. */
+    assert_eq!(reader.fill_buf().map_err(|e| Error::from_io(&e)).err(), Some(Some(Error::CtrDrbgEntropySourceFailed)));
+    let err = io::copy(&mut reader.by_ref().take(10), &mut io::sink()).unwrap_err();
+    assert_eq!(Error::from_io(&err), Some(Error::CtrDrbgEntropySourceFailed));
+}
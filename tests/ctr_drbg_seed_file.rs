@@ -0,0 +1,218 @@
+/*
+ * CTR_DRBG seed files
+ *
+ * Writes and reads seed files in a temporary directory per test: the
+ * versioned format round trip, each way a file can be damaged, legacy
+ * raw seeds, and the atomic, owner-only write.
+ */
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use mbed::rng::header::ctr_drbg::CtrDrbg;
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_MAX_INPUT; // The maximum number of additional input bytes.
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN; // The length of the seed file header.
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_SEED_FILE_MAGIC; // The magic bytes a seed file starts with.
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN; // The length of the seed file integrity tag.
+use mbed::rng::header::error::Error;
+
+const SEED_FILE_LEN: usize =
+    MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN + MBEDTLS_CTR_DRBG_MAX_INPUT + MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN;
+
+/*
+ * A temporary directory, removed with its contents on drop.
+ */
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let dir = env::temp_dir().join(format!("ctr_drbg_seed_file.{}.{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.0.join(name)
+    }
+
+    /* The names of the entries of the directory, sorted. */
+    fn entries(&self) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(&self.0).unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/*
+ * Returns a context seeded from a counter, so that two calls give
+ * identical contexts.
+ */
+fn seeded() -> CtrDrbg<'static> {
+    let mut ctx = CtrDrbg::new();
+    let mut next = 0u8;
+
+    ctx.seed(move |buf: &mut [u8]| -> Result<(), Error> {
+        for b in buf.iter_mut() {
+            *b = next;
+            next = next.wrapping_add(1);
+        }
+        Ok(())
+    }, b"seed file").unwrap();
+    ctx
+}
+
+/*
+ * Writes a valid seed file and returns its contents.
+ */
+fn write_valid(dir: &TempDir, name: &str) -> Vec<u8> {
+    let path = dir.path(name);
+    seeded().write_seed_file(&path).unwrap();
+    fs::read(&path).unwrap()
+}
+
+#[test]
+fn round_trip() {
+    let dir = TempDir::new("round_trip");
+    let path = dir.path("seed");
+    let mut writer = seeded();
+    let mut twin = seeded();
+
+    writer.write_seed_file(&path).unwrap();
+    let data = fs::read(&path).unwrap();
+    assert_eq!(data.len(), SEED_FILE_LEN);
+    assert!(data.starts_with(&MBEDTLS_CTR_DRBG_SEED_FILE_MAGIC));
+
+    /* The seed is the first MBEDTLS_CTR_DRBG_MAX_INPUT bytes of output. */
+    let mut seed = [0u8; MBEDTLS_CTR_DRBG_MAX_INPUT];
+    twin.generate(&mut seed).unwrap();
+    assert_eq!(data[MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN..SEED_FILE_LEN - MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN], seed[..]);
+
+    /* Reading it back updates with that seed, then writes a fresh one. */
+    let mut reader = seeded();
+    let mut reference = seeded();
+    reader.update_seed_file(&path).unwrap();
+    reference.update(&seed).unwrap();
+    reference.generate(&mut seed).unwrap();
+
+    let mut output = [0u8; 32];
+    let mut expected = [0u8; 32];
+    reader.generate(&mut output).unwrap();
+    reference.generate(&mut expected).unwrap();
+    assert_eq!(output, expected);
+
+    let fresh = fs::read(&path).unwrap();
+    assert_eq!(fresh.len(), SEED_FILE_LEN);
+    assert_ne!(fresh, data);
+}
+
+#[test]
+fn truncated_file_is_rejected() {
+    let dir = TempDir::new("truncated");
+    let path = dir.path("seed");
+    let data = write_valid(&dir, "seed");
+
+    for len in &[SEED_FILE_LEN - 1, SEED_FILE_LEN - MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN, MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN - 1] {
+        fs::write(&path, &data[..*len]).unwrap();
+        assert_eq!(seeded().update_seed_file(&path), Err(Error::CtrDrbgSeedFileTruncated), "length {}", len);
+        assert_eq!(fs::read(&path).unwrap(), &data[..*len], "the file was rewritten");
+    }
+}
+
+#[test]
+fn flipped_byte_is_rejected() {
+    let dir = TempDir::new("flipped");
+    let path = dir.path("seed");
+    let data = write_valid(&dir, "seed");
+
+    /* A byte of the seed, then a byte of the tag. */
+    for i in &[MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN + 10, SEED_FILE_LEN - 1] {
+        let mut corrupted = data.clone();
+        corrupted[*i] ^= 0x01;
+        fs::write(&path, &corrupted).unwrap();
+
+        assert_eq!(seeded().update_seed_file(&path), Err(Error::CtrDrbgSeedFileCorrupted), "byte {}", i);
+        assert_eq!(fs::read(&path).unwrap(), corrupted, "the file was rewritten");
+    }
+}
+
+#[test]
+fn unknown_version_is_rejected() {
+    let dir = TempDir::new("version");
+    let path = dir.path("seed");
+    let mut data = write_valid(&dir, "seed");
+
+    data[MBEDTLS_CTR_DRBG_SEED_FILE_MAGIC.len()] = 2;
+    fs::write(&path, &data).unwrap();
+
+    assert_eq!(seeded().update_seed_file(&path), Err(Error::CtrDrbgSeedFileBadVersion));
+}
+
+#[test]
+fn legacy_raw_seed_is_accepted() {
+    let dir = TempDir::new("legacy");
+    let path = dir.path("seed");
+    let raw = [0x5Au8; 64];
+    let mut ctx = seeded();
+    let mut reference = seeded();
+
+    fs::write(&path, &raw[..]).unwrap();
+    ctx.update_seed_file(&path).unwrap();
+
+    let mut output = [0u8; 32];
+    let mut expected = [0u8; 32];
+    let mut seed = [0u8; MBEDTLS_CTR_DRBG_MAX_INPUT];
+    reference.update(&raw).unwrap();
+    reference.generate(&mut seed).unwrap();
+    ctx.generate(&mut output).unwrap();
+    reference.generate(&mut expected).unwrap();
+    assert_eq!(output, expected);
+
+    /* It is written back in the current format. */
+    let data = fs::read(&path).unwrap();
+    assert!(data.starts_with(&MBEDTLS_CTR_DRBG_SEED_FILE_MAGIC));
+    assert_eq!(data.len(), SEED_FILE_LEN);
+}
+
+#[cfg(unix)]
+#[test]
+fn seed_file_is_owner_only() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = TempDir::new("mode");
+    let path = dir.path("seed");
+
+    /* Also when it replaces a world-readable file. */
+    fs::write(&path, [0x5Au8; 64]).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+    seeded().update_seed_file(&path).unwrap();
+
+    let mode = fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[test]
+fn no_temporary_file_is_left_behind() {
+    let dir = TempDir::new("temp");
+    let path = dir.path("seed");
+    let mut ctx = seeded();
+
+    ctx.write_seed_file(&path).unwrap();
+    ctx.update_seed_file(&path).unwrap();
+    assert_eq!(dir.entries(), ["seed"]);
+
+    /* Nor after a failure. */
+    fs::write(&path, b"CDSF").unwrap();
+    assert!(ctx.update_seed_file(&path).is_err());
+    assert!(ctx.write_seed_file(dir.path("missing/seed")).is_err());
+    assert_eq!(dir.entries(), ["seed"]);
+}
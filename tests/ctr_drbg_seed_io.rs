@@ -0,0 +1,123 @@
+/*
+ * CTR_DRBG seeds through Read and Write
+ *
+ * write_seed_to() and update_seed_from() on in-memory buffers and on
+ * readers that return less than asked for.
+ */
+
+use std::io::{self, Cursor, Read, Write};
+
+use mbed::rng::header::ctr_drbg::CtrDrbg;
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_MAX_INPUT; // The maximum number of additional input bytes.
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN; // The length of the seed file header.
+use mbed::rng::header::ctr_drbg::MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN; // The length of the seed file integrity tag.
+use mbed::rng::header::error::Error;
+
+const RECORD_LEN: usize =
+    MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN + MBEDTLS_CTR_DRBG_MAX_INPUT + MBEDTLS_CTR_DRBG_SEED_FILE_TAG_LEN;
+
+/*
+ * A reader that returns at most `step` bytes per read, then end of input
+ * once `data` is exhausted.
+ */
+struct ShortReader<'a> {
+    data: &'a [u8],
+    step: usize,
+}
+
+impl Read for ShortReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.step.min(buf.len()).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+/*
+ * A writer that fails every write.
+ */
+struct FailingWriter;
+
+impl Write for FailingWriter {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::other("no space left"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/*
+ * Returns a context seeded from a counter, so that two calls give
+ * identical contexts.
+ */
+fn seeded() -> CtrDrbg<'static> {
+    let mut ctx = CtrDrbg::new();
+    let mut next = 0u8;
+
+    ctx.seed(move |buf: &mut [u8]| -> Result<(), Error> {
+        for b in buf.iter_mut() {
+            *b = next;
+            next = next.wrapping_add(1);
+        }
+        Ok(())
+    }, b"seed io").unwrap();
+    ctx
+}
+
+/*
+ * Returns the next 32 bytes of output of ctx.
+ */
+fn next_output(ctx: &mut CtrDrbg<'_>) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    ctx.generate(&mut output).unwrap();
+    output
+}
+
+#[test]
+fn round_trip_through_vec_and_cursor() {
+    let mut record = Vec::new();
+    seeded().write_seed_to(&mut record).unwrap();
+    assert_eq!(record.len(), RECORD_LEN);
+
+    /* The seed is the first MBEDTLS_CTR_DRBG_MAX_INPUT bytes of output. */
+    let mut seed = [0u8; MBEDTLS_CTR_DRBG_MAX_INPUT];
+    seeded().generate(&mut seed).unwrap();
+
+    let mut ctx = seeded();
+    let mut reference = seeded();
+    ctx.update_seed_from(Cursor::new(&record)).unwrap();
+    reference.update(&seed).unwrap();
+    assert_eq!(next_output(&mut ctx), next_output(&mut reference));
+}
+
+#[test]
+fn short_reads_are_completed() {
+    let mut record = Vec::new();
+    seeded().write_seed_to(&mut record).unwrap();
+
+    let mut ctx = seeded();
+    let mut reference = seeded();
+    ctx.update_seed_from(ShortReader { data: &record, step: 1 }).unwrap();
+    reference.update_seed_from(&record[..]).unwrap();
+    assert_eq!(next_output(&mut ctx), next_output(&mut reference));
+}
+
+#[test]
+fn early_end_of_input_is_truncation() {
+    let mut record = Vec::new();
+    seeded().write_seed_to(&mut record).unwrap();
+
+    let reader = ShortReader { data: &record[..RECORD_LEN - 1], step: 7 };
+    assert_eq!(seeded().update_seed_from(reader), Err(Error::CtrDrbgSeedFileTruncated));
+
+    let reader = Cursor::new(&record[..MBEDTLS_CTR_DRBG_SEED_FILE_HEADER_LEN]);
+    assert_eq!(seeded().update_seed_from(reader), Err(Error::CtrDrbgSeedFileTruncated));
+}
+
+#[test]
+fn writer_failure_is_an_io_error() {
+    assert_eq!(seeded().write_seed_to(FailingWriter), Err(Error::CtrDrbgFileIoError));
+}
@@ -0,0 +1,30 @@
+/*
+ * Entropy accumulator
+ */
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use mbed::rng::header::entropy::EntropyContext;
+use mbed::rng::header::entropy::MBEDTLS_ENTROPY_SOURCE_STRONG;
+use mbed::rng::header::error::Error;
+
+/*
+ * A source that never reaches its threshold makes func() give up after
+ * ENTROPY_MAX_LOOP (256) gathering passes, as in mbedtls_entropy_func().
+ */
+#[test]
+fn func_gives_up_after_256_gathers() {
+    let polls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&polls);
+    let mut ctx = EntropyContext::new();
+
+    ctx.add_source(Box::new(move |_: &mut [u8]| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Ok(0)
+    }), 1, MBEDTLS_ENTROPY_SOURCE_STRONG).unwrap();
+
+    let mut buf = [0u8; 32];
+    assert_eq!(ctx.func(&mut buf), Err(Error::EntropySourceFailed));
+    assert_eq!(polls.load(Ordering::SeqCst), 256);
+}
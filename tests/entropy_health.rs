@@ -0,0 +1,169 @@
+/*
+ * Continuous health tests for entropy sources
+ *
+ * Checks the cutoffs against SP 800-90B section 4.4, the exact point at
+ * which each test trips, and that a DRBG refuses to seed or reseed from
+ * a source that failed.
+ */
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use mbed::rng::header::ctr_drbg::CtrDrbg;
+use mbed::rng::header::ctr_drbg::EntropySource; // A source of entropy for seeding a DRBG.
+use mbed::rng::header::entropy_health::HealthTest; // One of the continuous health tests.
+use mbed::rng::header::entropy_health::HealthTestConfig; // The cutoffs of the continuous health tests.
+use mbed::rng::header::entropy_health::HealthTestedSource; // An entropy source under continuous health tests.
+use mbed::rng::header::entropy_health::MBEDTLS_ENTROPY_HEALTH_APT_WINDOW; // The APT window size for non-binary samples.
+use mbed::rng::header::error::Error;
+
+/*
+ * A source that hands out the bytes of `data`, then zeros.
+ */
+fn replay(data: Vec<u8>) -> impl FnMut(&mut [u8]) -> Result<(), Error> {
+    let mut offset = 0;
+
+    move |buf: &mut [u8]| {
+        for b in buf.iter_mut() {
+            *b = data.get(offset).copied().unwrap_or(0);
+            offset += 1;
+        }
+        Ok(())
+    }
+}
+
+/*
+ * A source counting up from 0, which passes both tests, until `stuck` is
+ * set; from then on it only returns zeros.
+ */
+fn counter(stuck: Arc<AtomicBool>) -> impl FnMut(&mut [u8]) -> Result<(), Error> + Send {
+    let mut next = 0u8;
+
+    move |buf: &mut [u8]| {
+        for b in buf.iter_mut() {
+            *b = if stuck.load(Ordering::SeqCst) { 0 } else { next };
+            next = next.wrapping_add(1);
+        }
+        Ok(())
+    }
+}
+
+/*
+ * Samples where `first` occurs `count` times, never twice in a row, and
+ * every other sample is distinct within the window.
+ */
+fn apt_pattern(first: u8, count: u32, len: usize) -> Vec<u8> {
+    let mut data = Vec::with_capacity(len);
+    let mut other = first;
+    let mut left = count;
+
+    for i in 0..len {
+        if i % 2 == 0 && left > 0 {
+            data.push(first);
+            left -= 1;
+        } else {
+            other = other.wrapping_add(1);
+            if other == first {
+                other = other.wrapping_add(1);
+            }
+            data.push(other);
+        }
+    }
+    data
+}
+
+#[test]
+fn cutoffs_match_sp800_90b() {
+    /* RCT: C = 1 + ceil(20 / H). APT: SP 800-90B Table 2, W = 512. */
+    let expected = [(0.5, 41, 410), (1.0, 21, 311), (2.0, 11, 177), (4.0, 6, 62), (8.0, 4, 13)];
+
+    for &(h, rct, apt) in expected.iter() {
+        let config = HealthTestConfig::from_min_entropy(h).unwrap();
+        assert_eq!(config.rct_cutoff, rct, "RCT cutoff for H = {}", h);
+        assert_eq!(config.apt_cutoff, apt, "APT cutoff for H = {}", h);
+        assert_eq!(config.apt_window, MBEDTLS_ENTROPY_HEALTH_APT_WINDOW);
+    }
+
+    assert!(HealthTestConfig::from_min_entropy(0.0).is_err());
+    assert!(HealthTestConfig::from_min_entropy(8.5).is_err());
+}
+
+#[test]
+fn repetition_count_trips_at_cutoff() {
+    let config = HealthTestConfig::from_min_entropy(1.0).unwrap();
+    let c = config.rct_cutoff as usize;
+
+    /* C - 1 identical samples pass. */
+    let mut source = HealthTestedSource::new(replay(vec![0xA5; c - 1]), config);
+    let mut buf = vec![0u8; c - 1];
+    assert_eq!(source.fill(&mut buf), Ok(()));
+    assert_eq!(source.failure(), None);
+
+    /* The C-th, even in a later request, fails and wipes the buffer. */
+    let mut source = HealthTestedSource::new(replay(vec![0xA5; c]), config);
+    assert_eq!(source.fill(&mut buf), Ok(()));
+    let mut last = [0u8; 1];
+    assert_eq!(source.fill(&mut last), Err(Error::CtrDrbgEntropySourceFailed));
+    assert_eq!(source.failure(), Some(HealthTest::RepetitionCount));
+    assert_eq!(last, [0]);
+
+    /* A failure sticks until reset(). */
+    assert_eq!(source.fill(&mut last), Err(Error::CtrDrbgEntropySourceFailed));
+    source.reset();
+    assert_eq!(source.failure(), None);
+}
+
+#[test]
+fn adaptive_proportion_trips_within_window() {
+    let config = HealthTestConfig::from_min_entropy(8.0).unwrap();
+    let window = config.apt_window as usize;
+    let c = config.apt_cutoff;
+    let mut buf = vec![0u8; window];
+
+    /* C - 1 occurrences of the first sample in a window pass. */
+    let mut source = HealthTestedSource::new(replay(apt_pattern(0x3C, c - 1, window)), config);
+    assert_eq!(source.fill(&mut buf), Ok(()));
+    assert_eq!(source.failure(), None);
+
+    /* C occurrences fail, within the first window. */
+    let mut source = HealthTestedSource::new(replay(apt_pattern(0x3C, c, window)), config);
+    assert_eq!(source.fill(&mut buf), Err(Error::CtrDrbgEntropySourceFailed));
+    assert_eq!(source.failure(), Some(HealthTest::AdaptiveProportion));
+
+    /* The count restarts with each window: C - 1 occurrences in each of
+     * two windows pass. */
+    let mut data = apt_pattern(0x3C, c - 1, window);
+    data.extend(apt_pattern(0x3C, c - 1, window));
+    let mut source = HealthTestedSource::new(replay(data), config);
+    let mut two_windows = vec![0u8; 2 * window];
+    assert_eq!(source.fill(&mut two_windows), Ok(()));
+    assert_eq!(source.failure(), None);
+}
+
+#[test]
+fn drbg_does_not_seed_from_stuck_source() {
+    let config = HealthTestConfig::from_min_entropy(8.0).unwrap();
+    let source = HealthTestedSource::new(counter(Arc::new(AtomicBool::new(true))), config);
+    let mut ctx = CtrDrbg::new();
+
+    assert_eq!(ctx.seed(source, b"health"), Err(Error::CtrDrbgEntropySourceFailed));
+}
+
+#[test]
+fn drbg_does_not_reseed_from_stuck_source() {
+    let config = HealthTestConfig::from_min_entropy(8.0).unwrap();
+    let stuck = Arc::new(AtomicBool::new(false));
+    let source = HealthTestedSource::new(counter(Arc::clone(&stuck)), config);
+    let mut ctx = CtrDrbg::new();
+    let mut output = [0u8; 32];
+
+    ctx.seed(source, b"health").unwrap();
+    ctx.generate(&mut output).unwrap();
+
+    stuck.store(true, Ordering::SeqCst);
+    assert_eq!(ctx.reseed(&[]), Err(Error::CtrDrbgEntropySourceFailed));
+
+    /* Nor on a generate request that has to reseed. */
+    ctx.set_prediction_resistance(true);
+    assert_eq!(ctx.generate(&mut output), Err(Error::CtrDrbgEntropySourceFailed));
+}
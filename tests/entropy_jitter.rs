@@ -0,0 +1,133 @@
+/*
+ * CPU-jitter entropy source
+ *
+ * Runs the source on the monotonic clock, and on injected timers that
+ * stop moving, to check that its health tests catch them.
+ */
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Instant;
+
+use mbed::rng::header::ctr_drbg::CtrDrbg;
+use mbed::rng::header::ctr_drbg::EntropySource; // A source of entropy for seeding a DRBG.
+use mbed::rng::header::entropy::EntropyContext;
+use mbed::rng::header::entropy::MBEDTLS_ENTROPY_SOURCE_STRONG; // Entropy source is strong.
+use mbed::rng::header::entropy::MBEDTLS_ENTROPY_SOURCE_WEAK; // Entropy source is weak.
+use mbed::rng::header::entropy_jitter::JitterEntropy; // The CPU-jitter entropy source.
+use mbed::rng::header::entropy_jitter::MBEDTLS_ENTROPY_MIN_JITTER; // Minimum for the jitter source.
+use mbed::rng::header::error::Error;
+
+/* Switches the clocks below to a frozen value, one flag per test. */
+static FREEZE_AFTER_STARTUP: AtomicBool = AtomicBool::new(false);
+static FREEZE_IN_ACCUMULATOR: AtomicBool = AtomicBool::new(false);
+
+fn nanos() -> u64 {
+    thread_local!(static START: Instant = Instant::now());
+    START.with(|start| start.elapsed().as_nanos() as u64)
+}
+
+/* A timer that never moves. */
+fn stuck_clock() -> u64 {
+    0
+}
+
+/* A timer that advances by the same step on every reading. */
+fn regular_clock() -> u64 {
+    static TICKS: AtomicU64 = AtomicU64::new(0);
+    TICKS.fetch_add(1000, Ordering::SeqCst)
+}
+
+fn freezing_clock() -> u64 {
+    if FREEZE_AFTER_STARTUP.load(Ordering::SeqCst) { 0 } else { nanos() }
+}
+
+fn freezing_clock_in_accumulator() -> u64 {
+    if FREEZE_IN_ACCUMULATOR.load(Ordering::SeqCst) { 0 } else { nanos() }
+}
+
+/*
+ * A strong source for the accumulator, counting up from 0.
+ */
+fn strong_counter() -> impl FnMut(&mut [u8]) -> Result<usize, Error> + Send {
+    let mut next = 0u8;
+
+    move |buf: &mut [u8]| {
+        for b in buf.iter_mut() {
+            *b = next;
+            next = next.wrapping_add(1);
+        }
+        Ok(buf.len())
+    }
+}
+
+#[test]
+fn fills_any_length() {
+    let mut jitter = JitterEntropy::new().unwrap();
+
+    for &len in [0, 1, 63, 64, 65, 200].iter() {
+        let mut buf = vec![0u8; len];
+        assert_eq!(jitter.fill(&mut buf), Ok(()), "length {}", len);
+    }
+
+    let mut first = [0u8; 64];
+    let mut second = [0u8; 64];
+    jitter.fill(&mut first).unwrap();
+    jitter.fill(&mut second).unwrap();
+    assert_ne!(first, second);
+    assert_ne!(first, [0u8; 64]);
+}
+
+#[test]
+fn stuck_timer_fails_startup() {
+    assert_eq!(JitterEntropy::with_clock(stuck_clock).err(), Some(Error::EntropySourceFailed));
+    assert_eq!(JitterEntropy::with_clock(regular_clock).err(), Some(Error::EntropySourceFailed));
+}
+
+#[test]
+fn timer_stuck_after_startup_fails_requests() {
+    let mut jitter = JitterEntropy::with_clock(freezing_clock).unwrap();
+    let mut buf = [0u8; 64];
+
+    jitter.fill(&mut buf).unwrap();
+
+    FREEZE_AFTER_STARTUP.store(true, Ordering::SeqCst);
+    assert_eq!(jitter.fill(&mut buf), Err(Error::CtrDrbgEntropySourceFailed));
+    assert_eq!(buf, [0u8; 64]);
+
+    /* The failure sticks, even once the timer moves again. */
+    FREEZE_AFTER_STARTUP.store(false, Ordering::SeqCst);
+    assert_eq!(jitter.fill(&mut buf), Err(Error::CtrDrbgEntropySourceFailed));
+}
+
+#[test]
+fn seeds_ctr_drbg() {
+    let mut ctx = CtrDrbg::new();
+    let mut output = [0u8; 32];
+
+    ctx.seed(JitterEntropy::new().unwrap(), b"jitter").unwrap();
+    ctx.generate(&mut output).unwrap();
+    ctx.reseed(&[]).unwrap();
+    ctx.generate(&mut output).unwrap();
+}
+
+#[test]
+fn polled_as_weak_source() {
+    let mut ctx = EntropyContext::new();
+    let mut strong_only = EntropyContext::new();
+    let jitter = JitterEntropy::with_clock(freezing_clock_in_accumulator).unwrap();
+
+    ctx.add_source(Box::new(strong_counter()), 32, MBEDTLS_ENTROPY_SOURCE_STRONG).unwrap();
+    ctx.add_source(jitter.into_poll_callback(), MBEDTLS_ENTROPY_MIN_JITTER, MBEDTLS_ENTROPY_SOURCE_WEAK).unwrap();
+    strong_only.add_source(Box::new(strong_counter()), 32, MBEDTLS_ENTROPY_SOURCE_STRONG).unwrap();
+
+    /* The jitter output goes into the accumulator. */
+    let mut output = [0u8; 32];
+    let mut expected = [0u8; 32];
+    ctx.func(&mut output).unwrap();
+    strong_only.func(&mut expected).unwrap();
+    assert_ne!(output, expected);
+
+    /* A health test failure of the weak source fails the accumulator. */
+    FREEZE_IN_ACCUMULATOR.store(true, Ordering::SeqCst);
+    assert_eq!(ctx.func(&mut output), Err(Error::EntropySourceFailed));
+}
@@ -0,0 +1,118 @@
+/*
+ * NIST CAVP known-answer tests for Hash_DRBG
+ *
+ * Runs the DRBGVS response files (Hash_DRBG.rsp format) under
+ * tests/data/hash_drbg, and under $HASH_DRBG_CAVP_DIR when it is set.
+ * The files are named after their drbgvectors directory:
+ * Hash_DRBG_pr_true.rsp, Hash_DRBG_pr_false.rsp and
+ * Hash_DRBG_no_reseed.rsp.
+ *
+ * A case is applicable when its section is a digest of the SHA-2 family
+ * implemented here (SHA-224, SHA-256, SHA-384 or SHA-512). SHA-1 and the
+ * truncated SHA-512/t sections are skipped.
+ *
+ * Only Hash_DRBG_pr_true.rsp is bundled, with a single NIST case; the
+ * other two modes are ignored unless HASH_DRBG_CAVP_DIR is set and the
+ * tests are run with --ignored.
+ */
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use mbed::rng::header::error::Error;
+use mbed::rng::header::hash_drbg::HashDrbg;
+use mbed::rng::header::md::MdType;
+
+mod common;
+
+use common::cavp::{self, Case, Mode};
+
+/*
+ * Parse a section header such as "SHA-256" into the digest, if it is
+ * implemented.
+ */
+fn parse_digest(header: &str) -> Option<MdType> {
+    match header {
+        "SHA-224" => Some(MdType::Sha224),
+        "SHA-256" => Some(MdType::Sha256),
+        "SHA-384" => Some(MdType::Sha384),
+        "SHA-512" => Some(MdType::Sha512),
+        _ => None,
+    }
+}
+
+fn run_case(mode: Mode, md_type: MdType, case: &Case) -> Result<Vec<u8>, Error> {
+    let mut ctx = HashDrbg::new();
+    let entropy_input = case.get("EntropyInput", 0);
+    let expected_len = case.get("ReturnedBits", 0).len();
+
+    /* The entropy callback hands out the inputs in the order they are
+     * consumed: entropy input and nonce at instantiation, then the
+     * reseeds. */
+    let mut entropy = entropy_input.to_vec();
+    entropy.extend_from_slice(case.get("Nonce", 0));
+    entropy.extend_from_slice(case.get("EntropyInputReseed", 0));
+    for e in case.all("EntropyInputPR") {
+        entropy.extend_from_slice(e);
+    }
+    let mut offset = 0;
+
+    ctx.set_entropy_len(entropy_input.len());
+    ctx.seed(md_type, move |buf: &mut [u8]| {
+        if offset + buf.len() > entropy.len() {
+            return Err(Error::HashDrbgEntropySourceFailed);
+        }
+        buf.copy_from_slice(&entropy[offset..offset + buf.len()]);
+        offset += buf.len();
+        Ok(())
+    }, case.get("PersonalizationString", 0))?;
+
+    match mode {
+        Mode::PrTrue => ctx.set_prediction_resistance(true),
+        Mode::PrFalse => ctx.reseed(case.get("AdditionalInputReseed", 0))?,
+        Mode::NoReseed => {}
+    }
+
+    let mut output = vec![0u8; expected_len];
+    ctx.generate_with_additional(&mut output, case.get("AdditionalInput", 0))?;
+    ctx.generate_with_additional(&mut output, case.get("AdditionalInput", 1))?;
+
+    Ok(output)
+}
+
+/*
+ * Runs the cases of `mode` from the bundled vectors, if there are any for
+ * it, and from $HASH_DRBG_CAVP_DIR. A test that needs the variable fails
+ * when it is not set.
+ */
+fn run_mode(mode: Mode, bundled: bool) {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if bundled {
+        dirs.push(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/hash_drbg"));
+    }
+    match env::var_os("HASH_DRBG_CAVP_DIR") {
+        Some(dir) => dirs.push(PathBuf::from(dir)),
+        None if !bundled => panic!("set HASH_DRBG_CAVP_DIR to the NIST drbgvectors"),
+        None => {}
+    }
+
+    cavp::run_dirs(&dirs, &mode.file_name("Hash_DRBG"), parse_digest,
+                   |&md_type, case| run_case(mode, md_type, case));
+}
+
+#[test]
+fn cavp_prediction_resistance() {
+    run_mode(Mode::PrTrue, true);
+}
+
+#[test]
+#[ignore = "no NIST Hash_DRBG_pr_false.rsp is bundled; needs HASH_DRBG_CAVP_DIR"]
+fn cavp_reseed() {
+    run_mode(Mode::PrFalse, false);
+}
+
+#[test]
+#[ignore = "no NIST Hash_DRBG_no_reseed.rsp is bundled; needs HASH_DRBG_CAVP_DIR"]
+fn cavp_no_reseed() {
+    run_mode(Mode::NoReseed, false);
+}
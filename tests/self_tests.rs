@@ -0,0 +1,50 @@
+/*
+ * Checkup routines
+ *
+ * Runs the self_test() of every module, so that their known-answer
+ * vectors are checked by cargo test and not only on demand.
+ */
+
+use mbed::rng::aes;
+use mbed::rng::ctr_drbg;
+use mbed::rng::entropy;
+use mbed::rng::hash_drbg;
+use mbed::rng::hmac_drbg;
+use mbed::rng::sha256;
+use mbed::rng::sha512;
+
+#[test]
+fn aes_self_test() {
+    assert_eq!(aes::self_test(false), 0);
+}
+
+#[test]
+fn ctr_drbg_self_test() {
+    let report = ctr_drbg::self_test();
+    assert!(report.passed(), "\n{}", report);
+}
+
+#[test]
+fn entropy_self_test() {
+    assert_eq!(entropy::self_test(false), 0);
+}
+
+#[test]
+fn hash_drbg_self_test() {
+    assert_eq!(hash_drbg::self_test(false), 0);
+}
+
+#[test]
+fn hmac_drbg_self_test() {
+    assert_eq!(hmac_drbg::self_test(false), 0);
+}
+
+#[test]
+fn sha256_self_test() {
+    assert_eq!(sha256::self_test(false), 0);
+}
+
+#[test]
+fn sha512_self_test() {
+    assert_eq!(sha512::self_test(false), 0);
+}